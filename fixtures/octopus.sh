@@ -0,0 +1,56 @@
+#!/usr/bin/env bash
+# Builds repositories with octopus merges to check the graph against, e.g.
+#   ./fixtures/octopus.sh /tmp/octopus && guitar /tmp/octopus/five
+set -euo pipefail
+
+root="${1:-/tmp/guitar-octopus}"
+rm -rf "$root"
+mkdir -p "$root"
+
+commit() {
+    echo "$1" >> file.txt
+    git add file.txt
+    git commit -q -m "$1"
+}
+
+# Merges the given number of topic branches into main at once
+octopus() {
+    local dir="$1" count="$2"
+    git init -q -b main "$dir"
+    cd "$dir"
+    git config user.name fixture
+    git config user.email fixture@example.com
+    git config commit.gpgsign false
+
+    commit "base"
+    local topics=()
+    for i in $(seq 1 "$count"); do
+        git checkout -q -b "topic-$i" main
+        echo "$i" > "topic-$i.txt"
+        git add "topic-$i.txt"
+        git commit -q -m "topic $i"
+        topics+=("topic-$i")
+    done
+
+    git checkout -q main
+    commit "main moves on"
+    git merge -q --no-edit "${topics[@]}" > /dev/null
+    commit "after the merge"
+    cd - > /dev/null
+}
+
+# Three parents
+octopus "$root/three" 2
+
+# Five parents
+octopus "$root/five" 4
+
+# Five parents, one of them still carrying a branch after the merge
+octopus "$root/five-kept" 4
+cd "$root/five-kept"
+git checkout -q -b follow-up topic-2
+commit "follow-up on topic 2"
+git checkout -q main
+cd - > /dev/null
+
+echo "$root/three $root/five $root/five-kept"
@@ -7,6 +7,7 @@ use std::{
     rc::Rc,
     sync::{
         mpsc::{
+            Receiver,
             channel
         },
         Arc,
@@ -15,7 +16,8 @@ use std::{
         }
     },
     time::{
-        Duration
+        Duration,
+        Instant
     },
     thread,
     io,
@@ -27,6 +29,8 @@ use edtui::{
 };
 #[rustfmt::skip]
 use git2::{
+    Oid,
+    Error,
     Repository
 };
 #[rustfmt::skip]
@@ -77,6 +81,26 @@ use crate::{
         },
         tags::{
             Tags
+        },
+        stashes::{
+            Stashes
+        },
+        rebase::{
+            RebaseStep,
+            RebaseSession
+        },
+        conflicts::{
+            ConflictFile
+        },
+        log::{
+            Log,
+            Severity
+        },
+        search::{
+            Search
+        },
+        error::{
+            AppResult
         }
     },
     helpers::{
@@ -99,7 +123,18 @@ use crate::{
             helpers::{
                 FileChange,
                 UncommittedChanges
+            },
+            remotes::{
+                RemoteInfo,
+                get_remotes
             }
+        },
+        credentials::{
+            Credentials,
+            CredentialPrompt
+        },
+        network::{
+            Progress
         }
     },
 };
@@ -110,7 +145,10 @@ pub enum Viewport {
     Viewer,
     Editor,
     Splash,
-    Settings
+    Settings,
+    Rebase,
+    Conflicts,
+    Log
 }
 
 #[derive(PartialEq, Eq)]
@@ -120,11 +158,88 @@ pub enum Focus {
     StatusTop,
     StatusBottom,
     Branches,
+    Stashes,
+    Tags,
+    Remotes,
     ModalCheckout,
     ModalSolo,
     ModalCommit,
     ModalCreateBranch,
-    ModalDeleteBranch
+    ModalRenameBranch,
+    ModalCreateTag,
+    ModalRemote,
+    ModalRange,
+    ModalPath,
+    ModalDeleteBranch,
+    ModalStash,
+    ModalMerge,
+    ModalRevert,
+    ModalConfirm,
+    ModalCredentials,
+    Search
+}
+
+// Which diff the viewer shows for a file
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum ViewerSource {
+    Commit,   // Changes of the selected commit
+    Workdir,  // All uncommitted changes
+    Staged,   // Changes between HEAD and the index
+    Unstaged, // Changes between the index and the working directory
+}
+
+// Destructive actions that wait for a confirmation
+#[derive(Clone)]
+pub enum ConfirmAction {
+    DiscardFile(String),
+    DeleteUntracked(String),
+    RemoveRemote(String),
+    HardReset(Oid),
+    DeleteBranch(String),
+    ForcePush(String, String), // Remote and branch
+    DropStash(usize),
+    DeleteTag(String),
+    StageConflicted(String),
+    RenameRemoteBranch(String, String), // New name and the remote tracking branch
+}
+
+// What the remote modal asks for
+#[derive(Clone, PartialEq, Eq)]
+pub enum RemoteAction {
+    Add,               // Name of a new remote
+    AddUrl(String),    // Url of the new remote
+    Rename(String),    // New name of a remote
+    SetUrl(String),    // Fetch url of a remote
+    SetPushUrl(String) // Push url of a remote
+}
+
+// What the commit message modal is used for
+#[derive(PartialEq, Eq)]
+pub enum CommitKind {
+    New,
+    Rebase,
+    Merge,
+    Amend,
+    Reword(Oid),
+    Revert,
+    Tag
+}
+
+// Network action to run again once the credentials are typed in
+pub type Retry = Box<dyn FnOnce(&mut App)>;
+
+// What to do with the result of a network thread
+pub type OnNetworkDone = Box<dyn FnOnce(&mut App, AppResult<()>)>;
+
+// Network action running in the background
+pub struct NetworkJob {
+    pub label: String,                                 // What is being done, e.g. fetching origin
+    pub status: String,                                // Latest progress reported by the thread
+    pub progress: Progress,                            // Lets the thread be cancelled
+    pub rx: Receiver<String>,                          // Progress reported by the thread
+    pub handle: thread::JoinHandle<AppResult<()>>,     // The thread itself
+    pub started: Instant,                              // When it was started, for the log
+    pub on_done: OnNetworkDone,
 }
 
 #[derive(PartialEq, Eq)]
@@ -148,24 +263,30 @@ pub struct App {
     // User
     pub name: String,
     pub email: String,
+    pub credentials: Credentials,
 
     // Walker utilities
     pub color: Rc<RefCell<ColorPicker>>,
     pub buffer: RefCell<Buffer>,
-    pub walker_rx: Option<std::sync::mpsc::Receiver<WalkerOutput>>,
+    pub walker_rx: Option<std::sync::mpsc::Receiver<AppResult<WalkerOutput>>>,
     pub walker_cancel: Option<Arc<AtomicBool>>,
     pub walker_handle: Option<std::thread::JoinHandle<()>>,
 
+    // Network
+    pub network: Option<NetworkJob>,
+
     // Walker data
     pub oids: Oids,
     pub branches: Branches,
     pub tags: Tags,
+    pub stashes: Stashes,
     pub uncommitted: UncommittedChanges,
 
     // Cache
     pub current_diff: Vec<FileChange>,
     pub file_name: Option<String>,
     pub viewer_lines: Vec<ListItem<'static>>,
+    pub viewer_hunk_lines: Vec<Option<(usize, usize)>>,
 
     // Interface
     pub layout: Layout,
@@ -173,6 +294,9 @@ pub struct App {
     // Focus
     pub is_minimal: bool,
     pub is_branches: bool,
+    pub is_stashes: bool,
+    pub is_tags: bool,
+    pub is_remotes: bool,
     pub is_status: bool,
     pub is_inspector: bool,
     pub viewport: Viewport,
@@ -182,13 +306,63 @@ pub struct App {
     pub branches_selected: usize,
     pub branches_scroll: Cell<usize>,
 
+    // Stashes
+    pub stashes_selected: usize,
+    pub stashes_scroll: Cell<usize>,
+
+    // Tags
+    pub tags_selected: usize,
+    pub tags_scroll: Cell<usize>,
+
+    // Remotes
+    pub remotes: Vec<RemoteInfo>,
+    pub fetch_prune: bool,
+    pub fetch_tags: bool,
+    pub remotes_selected: usize,
+    pub remotes_scroll: Cell<usize>,
+
     // Graph
     pub graph_selected: usize,
     pub graph_scroll: Cell<usize>,
+    pub graph_marked: Vec<Oid>,
+    pub first_parent: bool,
+    pub range: Option<String>,
+    pub pathspec: Option<String>,
+
+    // Cherry-pick
+    pub cherry_pick_queue: Vec<Oid>,
+    pub cherry_pick_origin: Option<Oid>,
+
+    // Rebase
+    pub rebase_base: Option<Oid>,
+    pub rebase_steps: Vec<RebaseStep>,
+    pub rebase_selected: usize,
+    pub rebase_scroll: Cell<usize>,
+    pub rebase_session: Option<RebaseSession>,
+
+    // Conflicts
+    pub conflicts: Vec<String>,
+    pub conflicts_selected: usize,
+    pub conflict_file: Option<ConflictFile>,
+    pub conflict_selected: usize,
+    pub conflict_scroll: Cell<usize>,
+
+    // Log
+    pub log: Log,
+    pub log_selected: usize,
+    pub log_scroll: Cell<usize>,
+
+    // Search
+    pub search: Option<Search>,
+    pub search_input: String,
+    pub search_pickaxe: bool,
+    pub search_origin: usize,
 
     // Viewer
     pub viewer_selected: usize,
     pub viewer_scroll: Cell<usize>,
+    pub viewer_source: ViewerSource,
+    pub viewer_anchor: Option<usize>,
 
     // Settings
     pub settings_selected: usize,
@@ -219,14 +393,73 @@ pub struct App {
     // Modal commit
     pub commit_editor: EditorState,
     pub commit_editor_event_handler: EditorEventHandler,
+    pub commit_kind: CommitKind,
 
     // Modal create branch
     pub create_branch_editor: EditorState,
     pub create_branch_editor_event_handler: EditorEventHandler,
 
+    // Modal rename branch
+    pub rename_branch_editor: EditorState,
+    pub rename_branch_editor_event_handler: EditorEventHandler,
+    pub rename_branch_name: Option<String>,
+    pub rename_branch_remote: bool,
+
+    // Modal create tag
+    pub tag_editor: EditorState,
+    pub tag_editor_event_handler: EditorEventHandler,
+    pub tag_annotated: bool,
+    pub tag_name: String,
+    pub tag_target: Option<Oid>,
+
+    // Modal remote
+    pub remote_editor: EditorState,
+    pub remote_editor_event_handler: EditorEventHandler,
+    pub remote_action: Option<RemoteAction>,
+
+    // Modal revision range
+    pub range_editor: EditorState,
+    pub range_editor_event_handler: EditorEventHandler,
+    pub range_focus: Focus,
+
+    // Modal path filter
+    pub path_editor: EditorState,
+    pub path_editor_event_handler: EditorEventHandler,
+    pub path_focus: Focus,
+
     // Modal delete a branch
     pub modal_delete_branch_selected: i32,
 
+    // Modal stash
+    pub stash_editor: EditorState,
+    pub stash_editor_event_handler: EditorEventHandler,
+    pub stash_include_untracked: bool,
+    pub stash_keep_index: bool,
+
+    // Modal merge
+    pub modal_merge_selected: i32,
+    pub merge_no_ff: bool,
+
+    // Modal revert
+    pub modal_revert_selected: i32,
+
+    // Modal confirm
+    pub confirm_action: Option<ConfirmAction>,
+    pub confirm_lines: Vec<String>,
+    pub confirm_focus: Focus,
+    pub confirm_hard_reset: bool,
+    pub confirm_delete_branch: bool,
+    pub confirm_force_push: bool,
+
+    // Modal credentials
+    pub credentials_prompt: Option<CredentialPrompt>,
+    pub credentials_input: String,
+    pub credentials_focus: Focus,
+    pub credentials_retry: Option<Retry>,
+
+    // Modal error
+    pub error_message: Option<String>,
+
     // Exit
     pub is_exit: bool,
 }
@@ -289,6 +522,15 @@ impl App  {
             Viewport::Settings => {
                 self.draw_settings(frame);
             }
+            Viewport::Rebase => {
+                self.draw_rebase(frame);
+            }
+            Viewport::Conflicts => {
+                self.draw_conflicts(frame);
+            }
+            Viewport::Log => {
+                self.draw_log(frame);
+            }
         }
 
         // Panes
@@ -299,6 +541,15 @@ impl App  {
                 if self.is_branches {
                     self.draw_branches(frame);
                 }
+                if self.is_stashes {
+                    self.draw_stashes(frame);
+                }
+                if self.is_tags {
+                    self.draw_tags(frame);
+                }
+                if self.is_remotes {
+                    self.draw_remotes(frame);
+                }
                 if self.is_status {
                     self.draw_status(frame);
                 }
@@ -327,11 +578,46 @@ impl App  {
             Focus::ModalCreateBranch => {
                 self.draw_modal_create_branch(frame);
             }
+            Focus::ModalRenameBranch => {
+                self.draw_modal_rename_branch(frame);
+            }
+            Focus::ModalCreateTag => {
+                self.draw_modal_create_tag(frame);
+            }
+            Focus::ModalRemote => {
+                self.draw_modal_remote(frame);
+            }
+            Focus::ModalRange => {
+                self.draw_modal_range(frame);
+            }
+            Focus::ModalPath => {
+                self.draw_modal_path(frame);
+            }
             Focus::ModalDeleteBranch => {
                 self.draw_modal_delete_branch(frame);
             }
+            Focus::ModalStash => {
+                self.draw_modal_stash(frame);
+            }
+            Focus::ModalMerge => {
+                self.draw_modal_merge(frame);
+            }
+            Focus::ModalRevert => {
+                self.draw_modal_revert(frame);
+            }
+            Focus::ModalConfirm => {
+                self.draw_modal_confirm(frame);
+            }
+            Focus::ModalCredentials => {
+                self.draw_modal_credentials(frame);
+            }
             _ => {}
         }
+
+        // Errors show on top of whatever is open
+        if self.error_message.is_some() {
+            self.draw_modal_error(frame);
+        }
     }
 
     pub fn reload(&mut self) {
@@ -363,9 +649,13 @@ impl App  {
         }
 
         // Get user credentials
-        let (name, email) = get_git_user_info(&self.repo).expect("Error");
-        self.name = name.unwrap();
-        self.email = email.unwrap();
+        // Missing identity only matters once committing, which reports it then
+        let (name, email) = get_git_user_info(&self.repo).unwrap_or_default();
+        self.name = name.unwrap_or_default();
+        self.email = email.unwrap_or_default();
+
+        // Update remotes
+        self.remotes = get_remotes(&self.repo).unwrap_or_default();
 
         // Restart the spinner
         self.spinner.start();
@@ -378,14 +668,23 @@ impl App  {
         let (tx, rx) = channel();
         self.walker_rx = Some(rx);
 
-        // Copy the repo path and visible branches
+        // Copy the repo path, visible branches and walk filters
         let path = self.path.clone();
         let visible = self.branches.visible.clone();
+        let first_parent = self.first_parent;
+        let range = self.range.clone();
+        let pathspec = self.pathspec.clone();
 
         // Spawn a thread that computes something; it will check cancel flag between iterations
         let handle = thread::spawn(move || {
-            // Create the walker
-            let mut walk_ctx = Walker::new(path, 10000, visible).expect("Error");
+            // Create the walker, handing over the reason when the repository cannot be read
+            let mut walk_ctx = match Walker::new(path, 10000, visible, first_parent, range, pathspec) {
+                Ok(walk_ctx) => walk_ctx,
+                Err(e) => {
+                    let _ = tx.send(Err(e));
+                    return;
+                }
+            };
             let mut is_first = true;
 
             // Walker loop
@@ -400,17 +699,19 @@ impl App  {
                 let is_again = walk_ctx.walk();
 
                 // Send the message to the main thread
-                if tx.send(WalkerOutput {
+                if tx.send(Ok(WalkerOutput {
                     oids: walk_ctx.oids.clone(),
                     branches_lanes: walk_ctx.branches_lanes.clone(),
                     branches_local: walk_ctx.branches_local.clone(),
                     branches_remote: walk_ctx.branches_remote.clone(),
                     tags_lanes: walk_ctx.tags_lanes.clone(),
                     tags_local: walk_ctx.tags_local.clone(),
+                    stashes_lanes: walk_ctx.stashes_lanes.clone(),
+                    stashes_local: walk_ctx.stashes_local.clone(),
                     buffer: walk_ctx.buffer.clone(),
                     is_first,
                     is_again,
-                }).is_err() {
+                })).is_err() {
                     // Receiver dropped, stop
                     break;
                 }
@@ -429,45 +730,102 @@ impl App  {
 
     pub fn sync(&mut self) {
         if let Some(rx) = &self.walker_rx && let Ok(result) = rx.try_recv() {
+            match result {
+                Ok(result) => self.sync_walker(result),
+                Err(e) => {
+                    // The walker gave up, leave the splash screen and say why
+                    self.spinner.stop();
+                    if self.viewport == Viewport::Splash {
+                        self.viewport = Viewport::Graph;
+                    }
+                    self.notify(Severity::Error, format!("cannot load the history: {}", e.message()));
+                }
+            }
+        }
 
-            // Crude check to see if this is a first iteration
-            if result.is_first {
+        // Collect search matches, following the first one while the query is typed
+        if let Some(search) = self.search.as_mut() && search.sync() && self.focus == Focus::Search {
+            self.select_match(self.search_origin, Direction::Down);
+        }
 
-                // Transition from the splash screen on startup
-                if self.viewport == Viewport::Splash {
-                    self.viewport = Viewport::Graph;
+        // Follow the network thread and hand over its result once it is done
+        if let Some(job) = self.network.as_mut() {
+            while let Ok(status) = job.rx.try_recv() {
+                job.status = status;
+            }
+            if job.handle.is_finished() && let Some(job) = self.network.take() {
+                let result = job.handle.join().unwrap_or_else(|_| Err(Error::from_str("network thread panicked").into()));
+
+                // Log the result under the job, apart from any action still waiting in a modal
+                let pending = self.log.suspend();
+                self.log.begin_at(&job.label, "", job.started);
+                match result {
+                    Err(_) if job.progress.is_cancelled() => {
+                        self.notify(Severity::Warning, format!("cancelled {}", job.label));
+                    }
+                    result => (job.on_done)(self, result),
                 }
+                self.log.discard();
+                self.log.resume(pending);
+            }
+        }
+    }
+
+    // Takes over a batch of walked commits
+    fn sync_walker(&mut self, result: WalkerOutput) {
+        // Crude check to see if this is a first iteration
+        if result.is_first {
 
-                // Get uncomitted changes info
-                self.uncommitted = get_filenames_diff_at_workdir(&self.repo).expect("Error");
-            }
-            
-            
-            // Lookup tables
-            self.oids = result.oids;
-
-            // Buffer
-            self.buffer = result.buffer;
-
-            // Update branches
-            self.branches.feed(
-                &self.oids,
-                &self.color,
-                &result.branches_lanes,
-                result.branches_local,
-                result.branches_remote
-            );
-
-            // Update tags
-            self.tags.feed(
-                &self.oids,
-                &self.color,
-                &result.tags_lanes,
-                result.tags_local
-            );
-
-            if !result.is_again {
-                self.spinner.stop();
+            // Transition from the splash screen on startup
+            if self.viewport == Viewport::Splash {
+                self.viewport = Viewport::Graph;
+            }
+
+            // Get uncomitted changes info
+            match get_filenames_diff_at_workdir(&self.repo) {
+                Ok(uncommitted) => self.uncommitted = uncommitted,
+                Err(e) => self.notify(Severity::Error, format!("cannot read the working directory: {}", e.message())),
+            }
+        }
+        
+        
+        // Lookup tables
+        self.oids = result.oids;
+
+        // Buffer
+        self.buffer = result.buffer;
+
+        // Update branches
+        self.branches.feed(
+            &self.oids,
+            &self.color,
+            &result.branches_lanes,
+            result.branches_local,
+            result.branches_remote
+        );
+
+        // Update tags
+        self.tags.feed(
+            &self.oids,
+            &self.color,
+            &result.tags_lanes,
+            result.tags_local
+        );
+
+        // Update stashes
+        self.stashes.feed(
+            &self.oids,
+            &self.color,
+            &result.stashes_lanes,
+            result.stashes_local
+        );
+
+        if !result.is_again {
+            self.spinner.stop();
+
+            // Aliases change with every walk, look for the query again
+            if self.search.is_some() {
+                self.restart_search();
             }
         }
     }
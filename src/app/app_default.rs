@@ -27,7 +27,9 @@ use crate::{
         app::{
             App,
             Viewport,
-            Focus
+            Focus,
+            CommitKind,
+            ViewerSource
         },
         app_layout::{
             Layout
@@ -43,20 +45,35 @@ use crate::{
         tags::{
             Tags
         },
+        stashes::{
+            Stashes
+        },
         oids::{
             Oids
+        },
+        log::{
+            Log
         }
     },
     helpers::{
         palette::*,
         colors::ColorPicker,
-        spinner::Spinner
+        spinner::Spinner,
+        text::split_filter_args
     },
     git::{
+        actions::{
+            rebase::{
+                get_rebase_session
+            }
+        },
         queries::{
             helpers::{
                 UncommittedChanges
             }
+        },
+        credentials::{
+            Credentials
         }
     }
 };
@@ -65,10 +82,20 @@ impl Default for App {
     fn default() -> Self {
         let args: Vec<String> = env::args().collect();
         let path = if args.len() > 1 { &args[1] } else { &".".to_string() };
+        let (range, pathspec) = split_filter_args(args.get(2..).unwrap_or_default());
         let theme = Theme::default();
         let color = Rc::new(RefCell::new(ColorPicker::from_theme(&theme)));
         let absolute_path: PathBuf = std::fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path));
         let repo = Rc::new(Repository::open(absolute_path.clone()).expect("Could not open repo"));
+        let credentials = Credentials::new(&repo);
+        let rebase_session = get_rebase_session(&repo);
+        let config = repo.config().ok();
+        // Pruning defaults to what git is set up to do, the app's own choice goes under guitar.*
+        let fetch_prune = config.as_ref().and_then(|config| config.get_bool("guitar.fetchprune").or_else(|_| config.get_bool("fetch.prune")).ok()).unwrap_or(false);
+        let fetch_tags = config.as_ref().and_then(|config| config.get_bool("guitar.fetchtags").ok()).unwrap_or(false);
+        let confirm_hard_reset = config.as_ref().and_then(|config| config.get_bool("guitar.confirmhardreset").ok()).unwrap_or(true);
+        let confirm_delete_branch = config.as_ref().and_then(|config| config.get_bool("guitar.confirmdeletebranch").ok()).unwrap_or(true);
+        let confirm_force_push = config.as_ref().and_then(|config| config.get_bool("guitar.confirmforcepush").ok()).unwrap_or(true);
         let logo = vec![
             Span::styled("  g", Style::default().fg(theme.COLOR_GRASS)),
             Span::styled("u", Style::default().fg(theme.COLOR_GRASS)),
@@ -92,6 +119,10 @@ impl Default for App {
             // User
             name: String::new(),
             email: String::new(),
+            credentials,
+
+            // Network
+            network: None,
 
             // Walker utilities
             color,
@@ -104,12 +135,14 @@ impl Default for App {
             oids: Oids::default(),
             branches: Branches::default(),
             tags: Tags::default(),
+            stashes: Stashes::default(),
             uncommitted: UncommittedChanges::default(),
 
             // Cache
             current_diff: Vec::new(),
             file_name: None,
             viewer_lines: Vec::new(),
+            viewer_hunk_lines: Vec::new(),
 
             // Interface
             layout: Layout::default(),
@@ -117,6 +150,9 @@ impl Default for App {
             // Focus
             is_minimal: false,
             is_branches: false,
+            is_stashes: false,
+            is_tags: false,
+            is_remotes: false,
             is_status: false,
             is_inspector: false,
             viewport: Viewport::Splash,
@@ -125,10 +161,58 @@ impl Default for App {
             // Branches
             branches_selected: 0,
             branches_scroll: 0.into(),
+
+            // Stashes
+            stashes_selected: 0,
+            stashes_scroll: 0.into(),
+
+            // Tags
+            tags_selected: 0,
+            tags_scroll: 0.into(),
+
+            // Remotes
+            remotes: Vec::new(),
+            fetch_prune,
+            fetch_tags,
+            remotes_selected: 0,
+            remotes_scroll: 0.into(),
             
             // Graph
             graph_selected: 0,
             graph_scroll: 0.into(),
+            graph_marked: Vec::new(),
+            first_parent: false,
+            range,
+            pathspec,
+
+            // Cherry-pick
+            cherry_pick_queue: Vec::new(),
+            cherry_pick_origin: None,
+
+            // Rebase
+            rebase_base: None,
+            rebase_steps: Vec::new(),
+            rebase_selected: 0,
+            rebase_scroll: 0.into(),
+            rebase_session,
+
+            // Conflicts
+            conflicts: Vec::new(),
+            conflicts_selected: 0,
+            conflict_file: None,
+            conflict_selected: 0,
+            conflict_scroll: 0.into(),
+
+            // Log
+            log: Log::default(),
+            log_selected: 0,
+            log_scroll: 0.into(),
+
+            // Search
+            search: None,
+            search_input: String::new(),
+            search_pickaxe: false,
+            search_origin: 0,
             
             // Settings
             settings_selected: 0,
@@ -137,6 +221,8 @@ impl Default for App {
             // Viewer
             viewer_selected: 0,
             viewer_scroll: 0.into(),
+            viewer_source: ViewerSource::Commit,
+            viewer_anchor: None,
 
             // Editor
             file_editor: EditorState::default(),
@@ -163,14 +249,73 @@ impl Default for App {
             // Modal commit
             commit_editor: EditorState::default(),
             commit_editor_event_handler: EditorEventHandler::default(),
+            commit_kind: CommitKind::New,
 
             // Modal create branch
             create_branch_editor: EditorState::default(),
             create_branch_editor_event_handler: EditorEventHandler::default(),
 
+            // Modal rename branch
+            rename_branch_editor: EditorState::default(),
+            rename_branch_editor_event_handler: EditorEventHandler::default(),
+            rename_branch_name: None,
+            rename_branch_remote: false,
+
+            // Modal create tag
+            tag_editor: EditorState::default(),
+            tag_editor_event_handler: EditorEventHandler::default(),
+            tag_annotated: false,
+            tag_name: String::new(),
+            tag_target: None,
+
+            // Modal remote
+            remote_editor: EditorState::default(),
+            remote_editor_event_handler: EditorEventHandler::default(),
+            remote_action: None,
+
+            // Modal revision range
+            range_editor: EditorState::default(),
+            range_editor_event_handler: EditorEventHandler::default(),
+            range_focus: Focus::Viewport,
+
+            // Modal path filter
+            path_editor: EditorState::default(),
+            path_editor_event_handler: EditorEventHandler::default(),
+            path_focus: Focus::Viewport,
+
             // Modal delete branch
             modal_delete_branch_selected: 0,
 
+            // Modal stash
+            stash_editor: EditorState::default(),
+            stash_editor_event_handler: EditorEventHandler::default(),
+            stash_include_untracked: true,
+            stash_keep_index: false,
+
+            // Modal merge
+            modal_merge_selected: 0,
+            merge_no_ff: false,
+
+            // Modal revert
+            modal_revert_selected: 0,
+
+            // Modal confirm
+            confirm_action: None,
+            confirm_lines: Vec::new(),
+            confirm_focus: Focus::Viewport,
+            confirm_hard_reset,
+            confirm_delete_branch,
+            confirm_force_push,
+
+            // Modal credentials
+            credentials_prompt: None,
+            credentials_input: String::new(),
+            credentials_focus: Focus::Viewport,
+            credentials_retry: None,
+
+            // Modal error
+            error_message: None,
+
             // Exit
             is_exit: false,   
         }
@@ -0,0 +1,179 @@
+#[rustfmt::skip]
+use ratatui::{
+    Frame,
+    style::Style,
+    text::{
+        Line,
+        Span,
+    },
+    widgets::{
+        Block,
+        Borders,
+        Paragraph,
+        Scrollbar,
+        ScrollbarOrientation,
+        ScrollbarState,
+        List,
+        ListItem
+    },
+};
+#[rustfmt::skip]
+use crate::{
+    app::app::{
+        App,
+        Focus
+    },
+    core::{
+        conflicts::{
+            ConflictChunk,
+            ConflictSide
+        }
+    },
+    git::{
+        queries::{
+            conflicts::{
+                get_side_hunks
+            }
+        }
+    }
+};
+
+impl App {
+
+    pub fn draw_conflicts(&mut self, frame: &mut Frame) {
+
+        // Padding
+        let padding = ratatui::widgets::Padding { left: 1, right: 1, top: 0, bottom: 0 };
+
+        // Setup the frame
+        let block = Block::default()
+            .padding(padding)
+            .borders(Borders::RIGHT | Borders::LEFT)
+            .border_style(Style::default().fg(self.theme.COLOR_BORDER))
+            .border_type(ratatui::widgets::BorderType::Rounded);
+        let inner = block.inner(self.layout.graph);
+        frame.render_widget(block, self.layout.graph);
+
+        let chunks_vertical = ratatui::layout::Layout::default()
+            .direction(ratatui::layout::Direction::Vertical)
+            .constraints([
+                ratatui::layout::Constraint::Length(1),
+                ratatui::layout::Constraint::Length(1),
+                ratatui::layout::Constraint::Min(0),
+            ])
+            .split(inner);
+
+        let chunks_columns = ratatui::layout::Layout::default()
+            .direction(ratatui::layout::Direction::Horizontal)
+            .constraints([
+                ratatui::layout::Constraint::Ratio(1, 3),
+                ratatui::layout::Constraint::Ratio(1, 3),
+                ratatui::layout::Constraint::Ratio(1, 3),
+            ])
+            .split(chunks_vertical[2]);
+
+        // Render the conflicted files as tabs
+        let mut tabs: Vec<Span> = Vec::new();
+        for (idx, path) in self.conflicts.iter().enumerate() {
+            let style = if idx == self.conflicts_selected {
+                Style::default().fg(self.theme.COLOR_RED).bg(self.theme.COLOR_GREY_800)
+            } else {
+                Style::default().fg(self.theme.COLOR_GREY_500)
+            };
+            tabs.push(Span::styled(format!(" ! {} ", path), style));
+        }
+        if tabs.is_empty() {
+            tabs.push(Span::styled("no conflicts left, continue the operation", Style::default().fg(self.theme.COLOR_GREY_500)));
+        }
+        frame.render_widget(Paragraph::new(Line::from(tabs)), chunks_vertical[0]);
+
+        // Render the column headers
+        let count = self.conflict_file.as_ref().map(|file| file.get_conflict_count()).unwrap_or(0);
+        let headers = [
+            (format!("base · conflict {}/{}", if count == 0 { 0 } else { self.conflict_selected + 1 }, count), self.theme.COLOR_GREY_500),
+            ("ours".to_string(), self.theme.COLOR_GREEN),
+            ("theirs".to_string(), self.theme.COLOR_BLUE),
+        ];
+        for ((header, color), rect) in headers.iter().zip(chunks_columns.iter()) {
+            let header_rect = ratatui::layout::Rect { y: chunks_vertical[1].y, height: 1, ..*rect };
+            frame.render_widget(Paragraph::new(Span::styled(header.clone(), Style::default().fg(*color))), header_rect);
+        }
+
+        // Align the three versions hunk by hunk, each side drawn like the viewer draws a diff against the base
+        let mut columns: [Vec<ListItem>; 3] = [Vec::new(), Vec::new(), Vec::new()];
+        let mut numbers = [0, 0, 0];
+        let mut selected_row = 0;
+        if let Some(file) = self.conflict_file.as_ref() {
+            let mut conflict_idx = 0;
+            for chunk in file.chunks.iter() {
+                let (sides, is_selected) = match chunk {
+                    ConflictChunk::Clean(lines) => ([(lines, None), (lines, None), (lines, None)], false),
+                    ConflictChunk::Conflict { base, ours, theirs, resolution } => {
+                        // Sides that are not taken show without their changes
+                        let ours_hunks = (*resolution != Some(ConflictSide::Theirs)).then(|| get_side_hunks(&file.path, base, ours).unwrap_or_default());
+                        let theirs_hunks = (*resolution != Some(ConflictSide::Ours)).then(|| get_side_hunks(&file.path, base, theirs).unwrap_or_default());
+                        let is_selected = conflict_idx == self.conflict_selected;
+                        if is_selected {
+                            selected_row = columns[0].len();
+                        }
+                        conflict_idx += 1;
+                        ([(base, None), (ours, ours_hunks), (theirs, theirs_hunks)], is_selected)
+                    }
+                };
+
+                let start = columns[0].len();
+                let base_number = numbers[0];
+                for (idx, (lines, hunks)) in sides.into_iter().enumerate() {
+                    let lines: Vec<String> = lines.iter().map(|line| line.trim_end_matches(['\n', '\r']).to_string()).collect();
+                    let (items, _) = self.get_hunk_lines(&lines, hunks.as_deref().unwrap_or_default(), chunks_columns[idx].width as usize, (base_number, numbers[idx]));
+                    columns[idx].extend(items);
+                    numbers[idx] += lines.len();
+                }
+
+                // Pad the shorter sides so the next chunk starts on the same row
+                let end = columns.iter().map(|column| column.len()).max().unwrap_or(start);
+                for column in columns.iter_mut() {
+                    column.resize(end, ListItem::new(""));
+                    if is_selected && self.focus == Focus::Viewport {
+                        for item in column[start..end].iter_mut() {
+                            *item = item.clone().style(Style::default().bg(self.theme.COLOR_GREY_800));
+                        }
+                    }
+                }
+            }
+        }
+
+        // Get vertical dimensions
+        let total_lines = columns[0].len();
+        let visible_height = chunks_columns[0].height as usize;
+
+        // Trap selection
+        self.trap_selection(selected_row, &self.conflict_scroll, total_lines, visible_height);
+
+        // Calculate scroll
+        let start = self.conflict_scroll.get().min(total_lines.saturating_sub(visible_height));
+        let end = (start + visible_height).min(total_lines);
+
+        // Render the columns
+        for (column, rect) in columns.into_iter().zip(chunks_columns.iter()) {
+            let items: Vec<ListItem> = column.into_iter().skip(start).take(end - start).collect();
+            frame.render_widget(List::new(items), *rect);
+        }
+
+        // Setup the scrollbar
+        let mut scrollbar_state = ScrollbarState::new(total_lines.saturating_sub(visible_height)).position(self.conflict_scroll.get());
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(if (self.is_inspector && self.graph_selected != 0) || self.is_status { Some("─") } else { Some("╮") })
+            .end_symbol(if (self.is_inspector && self.graph_selected != 0) || self.is_status { Some("─") } else { Some("╯") })
+            .track_symbol(Some("│"))
+            .thumb_symbol(if total_lines > visible_height { "▌" } else { "│" })
+            .thumb_style(Style::default().fg(if total_lines > visible_height && self.focus == Focus::Viewport {
+                self.theme.COLOR_GREY_600
+            } else {
+                self.theme.COLOR_BORDER
+            }));
+
+        // Render the scrollbar
+        frame.render_stateful_widget(scrollbar, self.layout.graph_scrollbar, &mut scrollbar_state);
+    }
+}
@@ -16,6 +16,9 @@ use ratatui::{
 #[rustfmt::skip]
 use crate::{
     core::{
+        chunk::{
+            NONE
+        },
         renderers::{
             render_buffer_range,
             render_graph_range,
@@ -57,9 +60,9 @@ impl App {
         let mut buffer = self.buffer.borrow_mut();
         buffer.decompress(start, end + 1);
 
-        // Get head
-        let head_oid = self.repo.head().unwrap().target().unwrap();
-        let head_oid_alias = self.oids.get_alias_by_oid(head_oid);
+        // Get head, an unborn branch has none yet
+        let head_oid = self.repo.head().ok().and_then(|head| head.target());
+        let head_oid_alias = head_oid.map_or(NONE, |oid| self.oids.get_alias_by_oid(oid));
 
         // Rendered lines
         let _buffer_range = render_buffer_range(
@@ -73,6 +76,7 @@ impl App {
             &self.theme,
             &self.oids,
             &self.branches.all,
+            &self.stashes.local,
             &buffer.history,
             head_oid_alias,
             start,
@@ -85,12 +89,15 @@ impl App {
             &self.branches.local,
             &self.branches.visible,
             &self.tags.local,
+            &self.stashes.local,
             &mut self.branches.colors,
             &mut self.tags.colors,
+            &mut self.stashes.colors,
             start,
             end,
             self.graph_selected,
             &self.uncommitted,
+            self.search.as_ref(),
         );
 
         // Add rows
@@ -122,6 +129,8 @@ impl App {
                 // Change the row background if selected
                 if idx + start == self.graph_selected && self.focus == Focus::Viewport {
                     row = row.style(Style::default().bg(self.theme.COLOR_GREY_800));
+                } else if self.graph_marked.contains(self.oids.get_oid_by_idx(idx + start)) {
+                    row = row.style(Style::default().bg(self.theme.COLOR_GREY_700));
                 } else if (idx + start).is_multiple_of(2) {
                     row = row.style(Style::default().bg(self.theme.COLOR_GREY_900));
                 }
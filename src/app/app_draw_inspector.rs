@@ -51,7 +51,9 @@ impl App {
             // Query commit info
             let alias = self.oids.get_alias_by_idx(self.graph_selected);
             let oid = self.oids.get_oid_by_alias(alias);
-            let commit = self.repo.find_commit(*oid).unwrap();
+            let Ok(commit) = self.repo.find_commit(*oid) else {
+                return;
+            };
             let author = commit.author();
             let committer = commit.committer();
             let summary = commit.summary().unwrap_or("⊘ no summary").to_string();
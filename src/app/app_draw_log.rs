@@ -0,0 +1,135 @@
+#[rustfmt::skip]
+use ratatui::{
+    Frame,
+    style::Style,
+    text::{
+        Line,
+        Span,
+    },
+    widgets::{
+        Block,
+        Borders,
+        Scrollbar,
+        ScrollbarOrientation,
+        ScrollbarState,
+        List,
+        ListItem
+    },
+};
+#[rustfmt::skip]
+use crate::{
+    app::app::{
+        App,
+        Focus
+    },
+    core::{
+        log::{
+            Severity
+        }
+    },
+    helpers::{
+        text::{
+            truncate_with_ellipsis
+        }
+    }
+};
+
+impl App {
+
+    pub fn draw_log(&mut self, frame: &mut Frame) {
+
+        // Padding
+        let padding = ratatui::widgets::Padding { left: 1, right: 1, top: 0, bottom: 0 };
+
+        // Calculate maximum available width for text
+        let available_width = self.layout.graph.width as usize - 1;
+
+        // Lines, newest first
+        let mut lines: Vec<Line<'_>> = Vec::new();
+        for entry in self.log.entries.iter().rev() {
+
+            // Text
+            let color = match entry.severity {
+                Severity::Info => self.theme.COLOR_GRASS,
+                Severity::Warning => self.theme.COLOR_YELLOW,
+                Severity::Error => self.theme.COLOR_RED,
+            };
+            let action = if entry.target.is_empty() { entry.action.clone() } else { format!("{} {}", entry.action, entry.target) };
+            let action = truncate_with_ellipsis(&action, 30);
+            let duration = format!("{}ms", entry.duration.as_millis());
+            let max_text_width = available_width.saturating_sub(10 + 31 + 9);
+
+            // Render a log entry
+            lines.push(Line::from(vec![
+                Span::styled(format!("{} ", entry.time.format("%H:%M:%S")), Style::default().fg(self.theme.COLOR_GREY_500)),
+                Span::styled("● ", Style::default().fg(color)),
+                Span::styled(format!("{:<31}", action), Style::default().fg(self.theme.COLOR_TEXT)),
+                Span::styled(format!("{:>7}  ", duration), Style::default().fg(self.theme.COLOR_GREY_500)),
+                Span::styled(truncate_with_ellipsis(&entry.message, max_text_width), Style::default().fg(color)),
+            ]));
+        }
+        if lines.is_empty() {
+            lines.push(Line::from(Span::styled("nothing logged yet", Style::default().fg(self.theme.COLOR_GREY_500))));
+        }
+
+        // Get vertical dimensions
+        let total_lines = lines.len();
+        let visible_height = self.layout.graph.height as usize;
+
+        // Clamp selection
+        if self.log_selected >= total_lines {
+            self.log_selected = total_lines - 1;
+        }
+
+        // Trap selection
+        self.trap_selection(self.log_selected, &self.log_scroll, total_lines, visible_height);
+
+        // Calculate scroll
+        let start = self.log_scroll.get().min(total_lines.saturating_sub(visible_height));
+        let end = (start + visible_height).min(total_lines);
+
+        // Setup list items
+        let list_items: Vec<ListItem> = lines[start..end]
+            .iter()
+            .enumerate()
+            .map(|(idx, line)| {
+                if start + idx == self.log_selected && self.focus == Focus::Viewport {
+                    ListItem::new(line.clone()).style(Style::default().bg(self.theme.COLOR_GREY_800))
+                } else if (idx + start).is_multiple_of(2) {
+                    ListItem::new(line.clone()).style(Style::default().bg(self.theme.COLOR_GREY_900))
+                } else {
+                    ListItem::new(line.clone())
+                }
+            })
+            .collect();
+
+        // Setup the list
+        let list = List::new(list_items)
+            .block(
+                Block::default()
+                    .padding(padding)
+                    .borders(Borders::RIGHT | Borders::LEFT)
+                    .border_style(Style::default().fg(self.theme.COLOR_BORDER))
+                    .border_type(ratatui::widgets::BorderType::Rounded),
+            );
+
+        // Render the list
+        frame.render_widget(list, self.layout.graph);
+
+        // Setup the scrollbar
+        let mut scrollbar_state = ScrollbarState::new(total_lines.saturating_sub(visible_height)).position(self.log_scroll.get());
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(if (self.is_inspector && self.graph_selected != 0) || self.is_status { Some("─") } else { Some("╮") })
+            .end_symbol(if (self.is_inspector && self.graph_selected != 0) || self.is_status { Some("─") } else { Some("╯") })
+            .track_symbol(Some("│"))
+            .thumb_symbol(if total_lines > visible_height { "▌" } else { "│" })
+            .thumb_style(Style::default().fg(if total_lines > visible_height && self.focus == Focus::Viewport {
+                self.theme.COLOR_GREY_600
+            } else {
+                self.theme.COLOR_BORDER
+            }));
+
+        // Render the scrollbar
+        frame.render_stateful_widget(scrollbar, self.layout.graph_scrollbar, &mut scrollbar_state);
+    }
+}
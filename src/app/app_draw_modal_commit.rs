@@ -31,7 +31,8 @@ use edtui::{
 };
 #[rustfmt::skip]
 use crate::app::app::{
-    App
+    App,
+    CommitKind
 };
 
 impl App {
@@ -43,7 +44,15 @@ impl App {
 
         let lines: Vec<Line> = vec![
             Line::from(vec![
-                Span::styled("commit message:", Style::default().fg(self.theme.COLOR_TEXT)),
+                Span::styled(match self.commit_kind {
+                    CommitKind::New => "commit message:",
+                    CommitKind::Rebase => "rebased commit message:",
+                    CommitKind::Merge => "merge commit message:",
+                    CommitKind::Amend => "amended commit message:",
+                    CommitKind::Reword(_) => "reworded commit message:",
+                    CommitKind::Revert => "revert commit message:",
+                    CommitKind::Tag => "annotated tag message:",
+                }, Style::default().fg(self.theme.COLOR_TEXT)),
             ]),
             Line::from(""),
             Line::from(""),
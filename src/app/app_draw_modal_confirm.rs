@@ -0,0 +1,88 @@
+#[rustfmt::skip]
+use ratatui::{
+    Frame,
+    style::Style,
+    layout::{
+        Alignment,
+        Rect
+    },
+    text::{
+        Line,
+        Span,
+        Text
+    },
+    widgets::{
+        Block,
+        Borders,
+        Clear,
+        Paragraph,
+        Widget
+    },
+};
+#[rustfmt::skip]
+use crate::app::app::{
+    App
+};
+
+impl App {
+
+    pub fn draw_modal_confirm(&mut self, frame: &mut Frame) {
+
+        let mut lines = Vec::new();
+        let mut length = 30;
+
+        // Describe what is about to be lost
+        for (idx, text) in self.confirm_lines.iter().enumerate() {
+            length = length.max(text.chars().count());
+            lines.push(Line::from(Span::styled(text.clone(), Style::default().fg(if idx == 0 { self.theme.COLOR_TEXT } else { self.theme.COLOR_GREY_500 }))));
+            if idx == 0 {
+                lines.push(Line::default());
+            }
+        }
+
+        lines.push(Line::default());
+        lines.push(Line::from(vec![
+            Span::styled("(y) ", Style::default().fg(self.theme.COLOR_GREY_500)),
+            Span::styled("confirm", Style::default().fg(self.theme.COLOR_RED)),
+            Span::styled("   (n) ", Style::default().fg(self.theme.COLOR_GREY_500)),
+            Span::styled("cancel", Style::default().fg(self.theme.COLOR_TEXT)),
+        ]));
+
+        let bg_block = Block::default().style(Style::default().fg(self.theme.COLOR_BORDER));
+        bg_block.render(frame.area(), frame.buffer_mut());
+
+        // Modal size (smaller than area)
+        length += 10;
+        let height = lines.len() + 4;
+        let modal_width = length.min((frame.area().width as f32 * 0.8) as usize) as u16;
+        let modal_height = height.min((frame.area().height as f32 * 0.6) as usize) as u16;
+        let x = frame.area().x + (frame.area().width - modal_width) / 2;
+        let y = frame.area().y + (frame.area().height - modal_height) / 2;
+        let modal_area = Rect::new(x, y, modal_width, modal_height);
+
+        frame.render_widget(Clear, modal_area);
+
+        let padding = ratatui::widgets::Padding {
+            left: 3,
+            right: 3,
+            top: 1,
+            bottom: 1,
+        };
+
+        // Modal block
+        let modal_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.COLOR_GREY_600))
+            .title(Span::styled(" (esc) ", Style::default().fg(self.theme.COLOR_GREY_500)))
+            .title_alignment(Alignment::Right)
+            .padding(padding)
+            .border_type(ratatui::widgets::BorderType::Rounded);
+
+        // Modal content
+        let paragraph = Paragraph::new(Text::from(lines))
+            .block(modal_block)
+            .alignment(Alignment::Center);
+
+        paragraph.render(modal_area, frame.buffer_mut());
+    }
+}
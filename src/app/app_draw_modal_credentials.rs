@@ -0,0 +1,95 @@
+#[rustfmt::skip]
+use ratatui::{
+    Frame,
+    style::Style,
+    layout::{
+        Alignment,
+        Rect
+    },
+    text::{
+        Line,
+        Span,
+        Text
+    },
+    widgets::{
+        Block,
+        Borders,
+        Clear,
+        Paragraph,
+        Widget
+    },
+};
+#[rustfmt::skip]
+use crate::{
+    app::app::{
+        App
+    },
+    git::credentials::{
+        CredentialPrompt
+    }
+};
+
+impl App {
+
+    pub fn draw_modal_credentials(&mut self, frame: &mut Frame) {
+
+        let (label, detail, is_secret) = match &self.credentials_prompt {
+            Some(CredentialPrompt::Passphrase(path)) => ("passphrase of the ssh key:".to_string(), path.display().to_string(), true),
+            Some(CredentialPrompt::Username(url)) => ("username:".to_string(), url.clone(), false),
+            Some(CredentialPrompt::Password(url, username)) => (format!("password or token of {}:", username), url.clone(), true),
+            None => (String::new(), String::new(), false),
+        };
+
+        // Secrets never show up on screen
+        let value = if is_secret { "•".repeat(self.credentials_input.chars().count()) } else { self.credentials_input.clone() };
+        let length = 50.max(detail.chars().count() + 10).max(label.chars().count() + 10);
+
+        let lines = vec![
+            Line::from(Span::styled(label, Style::default().fg(self.theme.COLOR_TEXT))),
+            Line::from(Span::styled(detail, Style::default().fg(self.theme.COLOR_GREY_500))),
+            Line::default(),
+            Line::from(vec![
+                Span::styled(value, Style::default().fg(self.theme.COLOR_TEXT)),
+                Span::styled(" ", Style::default().bg(self.theme.COLOR_TEXT)),
+            ]),
+            Line::default(),
+            Line::from(Span::styled("(enter)", Style::default().fg(self.theme.COLOR_GREY_500))),
+        ];
+
+        let bg_block = Block::default().style(Style::default().fg(self.theme.COLOR_BORDER));
+        bg_block.render(frame.area(), frame.buffer_mut());
+
+        // Modal size (smaller than area)
+        let height = lines.len() + 4;
+        let modal_width = length.min((frame.area().width as f32 * 0.8) as usize) as u16;
+        let modal_height = height.min((frame.area().height as f32 * 0.6) as usize) as u16;
+        let x = frame.area().x + (frame.area().width - modal_width) / 2;
+        let y = frame.area().y + (frame.area().height - modal_height) / 2;
+        let modal_area = Rect::new(x, y, modal_width, modal_height);
+
+        frame.render_widget(Clear, modal_area);
+
+        let padding = ratatui::widgets::Padding {
+            left: 3,
+            right: 3,
+            top: 1,
+            bottom: 1,
+        };
+
+        // Modal block
+        let modal_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.COLOR_GREY_600))
+            .title(Span::styled(" (esc) ", Style::default().fg(self.theme.COLOR_GREY_500)))
+            .title_alignment(Alignment::Right)
+            .padding(padding)
+            .border_type(ratatui::widgets::BorderType::Rounded);
+
+        // Modal content
+        let paragraph = Paragraph::new(Text::from(lines))
+            .block(modal_block)
+            .alignment(Alignment::Center);
+
+        paragraph.render(modal_area, frame.buffer_mut());
+    }
+}
@@ -20,6 +20,10 @@ use ratatui::{
     },
 };
 #[rustfmt::skip]
+use git2::{
+    Oid
+};
+#[rustfmt::skip]
 use crate::{
     app::app::{
         App
@@ -55,7 +59,7 @@ impl App {
                 Span::styled(format!("● {}", branch), Style::default().fg(self.theme.COLOR_GRASS)),
             ]));
         } else {
-            let oid = self.repo.head().unwrap().target().unwrap();
+            let oid = self.repo.head().ok().and_then(|head| head.target()).unwrap_or(Oid::zero());
             length = length.max(26);
             lines.push(Line::from(vec![
                 Span::styled("you are on a detached head", Style::default().fg(self.theme.COLOR_TEXT)),
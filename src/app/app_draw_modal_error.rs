@@ -0,0 +1,86 @@
+#[rustfmt::skip]
+use ratatui::{
+    Frame,
+    style::Style,
+    layout::{
+        Alignment,
+        Rect
+    },
+    text::{
+        Line,
+        Span,
+        Text
+    },
+    widgets::{
+        Block,
+        Borders,
+        Clear,
+        Paragraph,
+        Widget,
+        Wrap
+    },
+};
+#[rustfmt::skip]
+use crate::app::app::{
+    App
+};
+
+impl App {
+
+    pub fn draw_modal_error(&mut self, frame: &mut Frame) {
+
+        // What failed, then why, one cause per line
+        let message = self.error_message.clone().unwrap_or_default();
+        let mut parts = message.split(": ");
+        let summary = parts.next().unwrap_or_default().to_string();
+        let causes: Vec<String> = parts.map(|part| part.to_string()).collect();
+        let length = causes.iter().map(|cause| cause.chars().count()).max().unwrap_or(0).max(summary.chars().count()).max(30) + 10;
+
+        let mut lines = vec![
+            Line::from(Span::styled(summary, Style::default().fg(self.theme.COLOR_RED))),
+            Line::default(),
+        ];
+        for cause in causes {
+            lines.push(Line::from(Span::styled(cause, Style::default().fg(self.theme.COLOR_TEXT))));
+        }
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled("(enter) dismiss, details in the log (F3)", Style::default().fg(self.theme.COLOR_GREY_500))));
+
+        let bg_block = Block::default().style(Style::default().fg(self.theme.COLOR_BORDER));
+        bg_block.render(frame.area(), frame.buffer_mut());
+
+        // Modal size (smaller than area)
+        let height = lines.len() + 4;
+        let modal_width = length.min((frame.area().width as f32 * 0.8) as usize) as u16;
+        let modal_height = height.min((frame.area().height as f32 * 0.6) as usize) as u16;
+        let x = frame.area().x + (frame.area().width - modal_width) / 2;
+        let y = frame.area().y + (frame.area().height - modal_height) / 2;
+        let modal_area = Rect::new(x, y, modal_width, modal_height);
+
+        frame.render_widget(Clear, modal_area);
+
+        let padding = ratatui::widgets::Padding {
+            left: 3,
+            right: 3,
+            top: 1,
+            bottom: 1,
+        };
+
+        // Modal block
+        let modal_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.COLOR_GREY_600))
+            .title(Span::styled(" (esc) ", Style::default().fg(self.theme.COLOR_GREY_500)))
+            .title_alignment(Alignment::Right)
+            .padding(padding)
+            .border_type(ratatui::widgets::BorderType::Rounded);
+
+        // Modal content
+        let paragraph = Paragraph::new(Text::from(lines))
+            .block(modal_block)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+
+        paragraph.render(modal_area, frame.buffer_mut());
+    }
+}
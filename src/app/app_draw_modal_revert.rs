@@ -0,0 +1,93 @@
+#[rustfmt::skip]
+use ratatui::{
+    Frame,
+    style::Style,
+    layout::{
+        Alignment,
+        Rect
+    },
+    text::{
+        Line,
+        Span,
+        Text
+    },
+    widgets::{
+        Block,
+        Borders,
+        Clear,
+        Paragraph,
+        Widget
+    },
+};
+#[rustfmt::skip]
+use crate::app::app::{
+    App
+};
+
+impl App {
+
+    pub fn draw_modal_revert(&mut self, frame: &mut Frame) {
+
+        let alias = self.oids.get_alias_by_idx(self.graph_selected);
+        let oid = *self.oids.get_oid_by_alias(alias);
+        let color = self.branches.colors.get(&alias).copied().unwrap_or(self.theme.COLOR_GRASS);
+        let mut length = 40;
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("select the mainline parent to revert against".to_string(), Style::default().fg(self.theme.COLOR_TEXT))
+            ]),
+            Line::from("")
+        ];
+        let mut height = 6;
+
+        // List the parents of the merge with their summaries
+        let parents: Vec<(String, String)> = self.repo.find_commit(oid)
+            .map(|commit| commit.parents().map(|parent| (format!("{:.6}", parent.id()), parent.summary().unwrap_or_default().to_string())).collect())
+            .unwrap_or_default();
+
+        parents.iter().enumerate().for_each(|(idx, (id, summary))| {
+            height += 1;
+            let text = format!("{} #{} {} ", idx + 1, id, summary);
+            length = (10 + text.chars().count()).max(length);
+            lines.push(Line::from(Span::styled(
+                text,
+                Style::default().fg(if idx == self.modal_revert_selected as usize { color } else { self.theme.COLOR_TEXT }),
+            )));
+        });
+
+        let bg_block = Block::default().style(Style::default().fg(self.theme.COLOR_BORDER));
+        bg_block.render(frame.area(), frame.buffer_mut());
+
+        // Modal size (smaller than area)
+        let modal_width = length.min((frame.area().width as f32 * 0.8) as usize) as u16;
+        let modal_height = height.min((frame.area().height as f32 * 0.6) as usize) as u16;
+        let x = frame.area().x + (frame.area().width - modal_width) / 2;
+        let y = frame.area().y + (frame.area().height - modal_height) / 2;
+        let modal_area = Rect::new(x, y, modal_width, modal_height);
+
+        frame.render_widget(Clear, modal_area);
+
+        let padding = ratatui::widgets::Padding {
+            left: 3,
+            right: 3,
+            top: 1,
+            bottom: 1,
+        };
+
+        // Modal block
+        let modal_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.COLOR_GREY_600))
+            .title(Span::styled(" (esc) ", Style::default().fg(self.theme.COLOR_GREY_500)))
+            .title_alignment(Alignment::Right)
+            .padding(padding)
+            .border_type(ratatui::widgets::BorderType::Rounded);
+
+        // Modal content
+        let paragraph = Paragraph::new(Text::from(lines))
+            .block(modal_block)
+            .alignment(Alignment::Center);
+
+        paragraph.render(modal_area, frame.buffer_mut());
+    }
+}
@@ -0,0 +1,132 @@
+#[rustfmt::skip]
+use ratatui::{
+    Frame,
+    style::Style,
+    text::{
+        Line,
+        Span,
+    },
+    widgets::{
+        Block,
+        Borders,
+        Scrollbar,
+        ScrollbarOrientation,
+        ScrollbarState,
+        List,
+        ListItem
+    },
+};
+#[rustfmt::skip]
+use crate::{
+    app::app::{
+        App,
+        Focus
+    },
+    core::{
+        rebase::{
+            RebaseAction
+        }
+    },
+    helpers::{
+        text::{
+            truncate_with_ellipsis
+        }
+    }
+};
+
+impl App {
+
+    pub fn draw_rebase(&mut self, frame: &mut Frame) {
+
+        // Padding
+        let padding = ratatui::widgets::Padding { left: 1, right: 1, top: 0, bottom: 0 };
+
+        // Calculate maximum available width for text
+        let available_width = self.layout.graph.width as usize - 1;
+        let max_text_width = available_width.saturating_sub(20);
+
+        // Lines
+        let mut lines: Vec<Line<'_>> = Vec::new();
+        for step in self.rebase_steps.iter() {
+
+            // Text
+            let color = match step.action {
+                RebaseAction::Pick => self.theme.COLOR_TEXT,
+                RebaseAction::Reword => self.theme.COLOR_BLUE,
+                RebaseAction::Edit => self.theme.COLOR_YELLOW,
+                RebaseAction::Squash | RebaseAction::Fixup => self.theme.COLOR_PURPLE,
+                RebaseAction::Drop => self.theme.COLOR_RED,
+            };
+            let summary_color = if step.action == RebaseAction::Drop { self.theme.COLOR_GREY_600 } else { self.theme.COLOR_TEXT };
+
+            // Render a todo step
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:<7}", step.action.as_str()), Style::default().fg(color)),
+                Span::styled(format!("#{:.6} ", step.oid), Style::default().fg(self.theme.COLOR_GREY_500)),
+                Span::styled(truncate_with_ellipsis(&step.summary, max_text_width), Style::default().fg(summary_color)),
+            ]));
+        }
+
+        // Get vertical dimensions
+        let total_lines = lines.len();
+        let visible_height = self.layout.graph.height as usize;
+
+        // Clamp selection
+        if total_lines == 0 {
+            self.rebase_selected = 0;
+        } else if self.rebase_selected >= total_lines {
+            self.rebase_selected = total_lines - 1;
+        }
+
+        // Trap selection
+        self.trap_selection(self.rebase_selected, &self.rebase_scroll, total_lines, visible_height);
+
+        // Calculate scroll
+        let start = self.rebase_scroll.get().min(total_lines.saturating_sub(visible_height));
+        let end = (start + visible_height).min(total_lines);
+
+        // Setup list items
+        let list_items: Vec<ListItem> = lines[start..end]
+            .iter()
+            .enumerate()
+            .map(|(idx, line)| {
+                if start + idx == self.rebase_selected && self.focus == Focus::Viewport {
+                    ListItem::new(line.clone()).style(Style::default().bg(self.theme.COLOR_GREY_800))
+                } else if (idx + start).is_multiple_of(2) {
+                    ListItem::new(line.clone()).style(Style::default().bg(self.theme.COLOR_GREY_900))
+                } else {
+                    ListItem::new(line.clone())
+                }
+            })
+            .collect();
+
+        // Setup the list
+        let list = List::new(list_items)
+            .block(
+                Block::default()
+                    .padding(padding)
+                    .borders(Borders::RIGHT | Borders::LEFT)
+                    .border_style(Style::default().fg(self.theme.COLOR_BORDER))
+                    .border_type(ratatui::widgets::BorderType::Rounded),
+            );
+
+        // Render the list
+        frame.render_widget(list, self.layout.graph);
+
+        // Setup the scrollbar
+        let mut scrollbar_state = ScrollbarState::new(total_lines.saturating_sub(visible_height)).position(self.rebase_scroll.get());
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(if (self.is_inspector && self.graph_selected != 0) || self.is_status { Some("─") } else { Some("╮") })
+            .end_symbol(if (self.is_inspector && self.graph_selected != 0) || self.is_status { Some("─") } else { Some("╯") })
+            .track_symbol(Some("│"))
+            .thumb_symbol(if total_lines > visible_height { "▌" } else { "│" })
+            .thumb_style(Style::default().fg(if total_lines > visible_height && self.focus == Focus::Viewport {
+                self.theme.COLOR_GREY_600
+            } else {
+                self.theme.COLOR_BORDER
+            }));
+
+        // Render the scrollbar
+        frame.render_stateful_widget(scrollbar, self.layout.graph_scrollbar, &mut scrollbar_state);
+    }
+}
@@ -0,0 +1,126 @@
+#[rustfmt::skip]
+use ratatui::{
+    Frame,
+    style::Style,
+    text::{
+        Line,
+        Span,
+    },
+    widgets::{
+        Block,
+        Borders,
+        Scrollbar,
+        ScrollbarOrientation,
+        ScrollbarState,
+        List,
+        ListItem
+    },
+};
+#[rustfmt::skip]
+use crate::{
+    app::app::{
+        App,
+        Focus
+    },
+    helpers::{
+        symbols::{
+            SYM_REMOTE
+        },
+        text::{
+            truncate_with_ellipsis
+        }
+    }
+};
+
+impl App {
+
+    pub fn draw_remotes(&mut self, frame: &mut Frame) {
+
+        // Padding
+        let padding = ratatui::widgets::Padding { left: 2, right: 0, top: 0, bottom: 0 };
+
+        // Calculate maximum available width for text
+        let available_width = self.layout.remotes.width as usize - 1;
+        let max_text_width = available_width.saturating_sub(3);
+
+        // Lines, each remote spans its name, urls and refspecs
+        let mut lines: Vec<(usize, Line<'_>)> = Vec::new();
+        let mut selected_line = 0;
+        if self.remotes_selected >= self.remotes.len() {
+            self.remotes_selected = self.remotes.len().saturating_sub(1);
+        }
+        for (idx, remote) in self.remotes.iter().enumerate() {
+            if idx == self.remotes_selected {
+                selected_line = lines.len();
+            }
+
+            // Render a remote
+            let name = truncate_with_ellipsis(&remote.name, max_text_width - 1);
+            lines.push((idx, Line::from(Span::styled(format!("{SYM_REMOTE} {name}"), Style::default().fg(self.theme.COLOR_TEXT)))));
+
+            // Render where it fetches from and pushes to
+            let mut details = vec![format!("↓ {}", remote.fetch_url)];
+            if remote.push_url != remote.fetch_url {
+                details.push(format!("↑ {}", remote.push_url));
+            }
+            details.extend(remote.fetch_refspecs.iter().map(|refspec| format!("↓ {}", refspec)));
+            details.extend(remote.push_refspecs.iter().map(|refspec| format!("↑ {}", refspec)));
+            for detail in details {
+                let truncated = truncate_with_ellipsis(&detail, max_text_width - 3);
+                lines.push((idx, Line::from(Span::styled(format!("  {truncated}"), Style::default().fg(self.theme.COLOR_GREY_500)))));
+            }
+        }
+
+        // Get vertical dimensions
+        let total_lines = lines.len();
+        let visible_height = self.layout.remotes.height as usize - 2;
+
+        // Trap selection, keeping the name of the selected remote in view
+        self.trap_selection(selected_line, &self.remotes_scroll, total_lines, visible_height);
+
+        // Calculate scroll
+        let start = self.remotes_scroll.get().min(total_lines.saturating_sub(visible_height));
+        let end = (start + visible_height).min(total_lines);
+
+        // Setup list items
+        let list_items: Vec<ListItem> = lines[start..end]
+            .iter()
+            .map(|(idx, line)| {
+                if *idx == self.remotes_selected && self.focus == Focus::Remotes {
+                    ListItem::new(line.clone()).style(Style::default().bg(self.theme.COLOR_GREY_800))
+                } else if idx.is_multiple_of(2) {
+                    ListItem::new(line.clone()).style(Style::default().bg(self.theme.COLOR_GREY_900))
+                } else {
+                    ListItem::new(line.clone())
+                }
+            })
+            .collect();
+
+        // Setup the list, separated from the panes above when any are shown
+        let list = List::new(list_items)
+            .block(
+                Block::default()
+                    .padding(padding)
+                    .borders(if self.is_branches || self.is_stashes || self.is_tags { Borders::TOP } else { Borders::NONE })
+                    .border_style(Style::default().fg(self.theme.COLOR_BORDER))
+            );
+
+        frame.render_widget(list, self.layout.remotes);
+
+        // Setup the scrollbar
+        let mut scrollbar_state = ScrollbarState::new(total_lines.saturating_sub(visible_height)).position(self.remotes_scroll.get());
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(if self.is_branches || self.is_stashes || self.is_tags { Some("┤") } else { Some("─") })
+            .end_symbol(Some("─"))
+            .track_symbol(Some("│"))
+            .thumb_symbol(if total_lines > visible_height { "▌" } else { "│" })
+            .thumb_style(Style::default().fg(if total_lines > visible_height && self.focus == Focus::Remotes {
+                self.theme.COLOR_GREY_600
+            } else {
+                self.theme.COLOR_BORDER
+            }));
+
+        // Render the scrollbar
+        frame.render_stateful_widget(scrollbar, self.layout.remotes_scrollbar, &mut scrollbar_state);
+    }
+}
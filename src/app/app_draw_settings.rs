@@ -64,7 +64,7 @@ impl App {
         let max_text_width = available_width.saturating_sub(2);
 
         // Credentials
-        let (name, email) = get_git_user_info(&self.repo).unwrap();
+        let (name, email) = get_git_user_info(&self.repo).unwrap_or_default();
 
         // Setup list items
         let mut lines: Vec<Line> = Vec::new();
@@ -110,17 +110,17 @@ impl App {
         ]).centered());
         lines.push(Line::default());
 
-        lines.push(Line::from(Span::styled(fill_width("name:", name.unwrap().as_str(), max_text_width / 2), Style::default().fg(self.theme.COLOR_TEXT).bg(self.theme.COLOR_GREY_900))).centered());
+        lines.push(Line::from(Span::styled(fill_width("name:", name.unwrap_or_default().as_str(), max_text_width / 2), Style::default().fg(self.theme.COLOR_TEXT).bg(self.theme.COLOR_GREY_900))).centered());
         
         // Record the line index as selectable
         self.settings_selections.push(lines.len() - 1);
 
-        lines.push(Line::from(Span::styled(fill_width("email:", email.unwrap().as_str(), max_text_width / 2), Style::default().fg(self.theme.COLOR_TEXT))).centered());
+        lines.push(Line::from(Span::styled(fill_width("email:", email.unwrap_or_default().as_str(), max_text_width / 2), Style::default().fg(self.theme.COLOR_TEXT))).centered());
         
         // Record the line index as selectable
         self.settings_selections.push(lines.len() - 1);
 
-        lines.push(Line::from(Span::styled(fill_width("authorization:", "external ssh agent", max_text_width / 2), Style::default().fg(self.theme.COLOR_TEXT).bg(self.theme.COLOR_GREY_900))).centered());
+        lines.push(Line::from(Span::styled(fill_width("authorization:", self.credentials.method.label(), max_text_width / 2), Style::default().fg(self.theme.COLOR_TEXT).bg(self.theme.COLOR_GREY_900))).centered());
         
         // Record the line index as selectable
         self.settings_selections.push(lines.len() - 1);
@@ -144,6 +144,39 @@ impl App {
         // Record the line index as selectable
         self.settings_selections.push(lines.len() - 1);
 
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled(fill_width("fetch:", "", max_text_width / 2), Style::default().fg(self.theme.COLOR_TEXT))).centered());
+        lines.push(Line::default());
+
+        lines.push(Line::from(Span::styled(fill_width("prune deleted remote branches", format!("({})", if self.fetch_prune {"*"} else {" "}).as_str(), max_text_width / 2), Style::default().fg(self.theme.COLOR_TEXT).bg(self.theme.COLOR_GREY_900))).centered());
+        
+        // Record the line index as selectable
+        self.settings_selections.push(lines.len() - 1);
+
+        lines.push(Line::from(Span::styled(fill_width("fetch all tags", format!("({})", if self.fetch_tags {"*"} else {" "}).as_str(), max_text_width / 2), Style::default().fg(self.theme.COLOR_TEXT))).centered());
+        
+        // Record the line index as selectable
+        self.settings_selections.push(lines.len() - 1);
+
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled(fill_width("confirm before:", "", max_text_width / 2), Style::default().fg(self.theme.COLOR_TEXT))).centered());
+        lines.push(Line::default());
+
+        lines.push(Line::from(Span::styled(fill_width("hard reset", format!("({})", if self.confirm_hard_reset {"*"} else {" "}).as_str(), max_text_width / 2), Style::default().fg(self.theme.COLOR_TEXT).bg(self.theme.COLOR_GREY_900))).centered());
+        
+        // Record the line index as selectable
+        self.settings_selections.push(lines.len() - 1);
+
+        lines.push(Line::from(Span::styled(fill_width("delete branch", format!("({})", if self.confirm_delete_branch {"*"} else {" "}).as_str(), max_text_width / 2), Style::default().fg(self.theme.COLOR_TEXT))).centered());
+        
+        // Record the line index as selectable
+        self.settings_selections.push(lines.len() - 1);
+
+        lines.push(Line::from(Span::styled(fill_width("force push", format!("({})", if self.confirm_force_push {"*"} else {" "}).as_str(), max_text_width / 2), Style::default().fg(self.theme.COLOR_TEXT).bg(self.theme.COLOR_GREY_900))).centered());
+        
+        // Record the line index as selectable
+        self.settings_selections.push(lines.len() - 1);
+
         lines.push(Line::default());
         lines.push(Line::default());
         lines.push(Line::from(Span::styled(" ╭─────────────────────────────────────────────────────────────────────╮".to_string(), Style::default().fg(self.theme.COLOR_GREY_800))).centered());    
@@ -0,0 +1,126 @@
+#[rustfmt::skip]
+use ratatui::{
+    Frame,
+    style::Style,
+    text::{
+        Line,
+        Span,
+    },
+    widgets::{
+        Block,
+        Borders,
+        Scrollbar,
+        ScrollbarOrientation,
+        ScrollbarState,
+        List,
+        ListItem
+    },
+};
+#[rustfmt::skip]
+use crate::{
+    app::app::{
+        App,
+        Focus
+    },
+    helpers::{
+        symbols::{
+            SYM_STASH
+        },
+        text::{
+            truncate_with_ellipsis
+        }
+    }
+};
+
+impl App {
+
+    pub fn draw_stashes(&mut self, frame: &mut Frame) {
+
+        // Padding
+        let padding = ratatui::widgets::Padding { left: 2, right: 0, top: 0, bottom: 0 };
+
+        // Calculate maximum available width for text
+        let available_width = self.layout.stashes.width as usize - 1;
+        let max_text_width = available_width.saturating_sub(3);
+
+        // Lines
+        let mut lines: Vec<Line<'_>> = Vec::new();
+        for (stash_alias, stash_name) in self.stashes.get_sorted_aliases().iter() {
+
+            // Query the stash message
+            let oid = self.oids.get_oid_by_alias(*stash_alias);
+            let summary = self.repo.find_commit(*oid).ok()
+                .and_then(|commit| commit.summary().map(|summary| summary.to_string()))
+                .unwrap_or("⊘ no summary".to_string());
+
+            // Text
+            let name = stash_name.trim_start_matches("stash");
+            let truncated = truncate_with_ellipsis(&format!("{name} {summary}"), max_text_width - 1);
+            let color = self.stashes.get_color(&self.theme, stash_alias);
+
+            // Render a stash
+            lines.push(Line::from(Span::styled(format!("{SYM_STASH} {truncated}"), Style::default().fg(color))));
+        }
+
+        // Get vertical dimensions
+        let total_lines = lines.len();
+        let visible_height = self.layout.stashes.height as usize - 2;
+
+        // Clamp selection
+        if total_lines == 0 {
+            self.stashes_selected = 0;
+        } else if self.stashes_selected >= total_lines {
+            self.stashes_selected = total_lines - 1;
+        }
+
+        // Trap selection
+        self.trap_selection(self.stashes_selected, &self.stashes_scroll, total_lines, visible_height);
+
+        // Calculate scroll
+        let start = self.stashes_scroll.get().min(total_lines.saturating_sub(visible_height));
+        let end = (start + visible_height).min(total_lines);
+
+        // Setup list items
+        let list_items: Vec<ListItem> = lines[start..end]
+            .iter()
+            .enumerate()
+            .map(|(idx, line)| {
+                if start + idx == self.stashes_selected && self.focus == Focus::Stashes {
+                    let spans: Vec<Span> = line.iter().map(|span| { Span::styled(span.content.clone(), span.style) }).collect();
+                    ListItem::new(Line::from(spans)).style(Style::default().bg(self.theme.COLOR_GREY_800))
+                } else if (idx + start).is_multiple_of(2) {
+                    ListItem::new(Line::from(line.clone().spans)).style(Style::default().bg(self.theme.COLOR_GREY_900))
+                } else {
+                    ListItem::new(line.clone())
+                }
+            })
+            .collect();
+
+        // Setup the list, separated from the branches above when both are shown
+        let list = List::new(list_items)
+            .block(
+                Block::default()
+                    .padding(padding)
+                    .borders(if self.is_branches { Borders::TOP } else { Borders::NONE })
+                    .border_style(Style::default().fg(self.theme.COLOR_BORDER))
+            );
+
+        frame.render_widget(list, self.layout.stashes);
+
+        // Setup the scrollbar
+        let mut scrollbar_state = ScrollbarState::new(total_lines.saturating_sub(visible_height)).position(self.stashes_scroll.get());
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(if self.is_branches { Some("┤") } else { Some("─") })
+            .end_symbol(Some("─"))
+            .track_symbol(Some("│"))
+            .thumb_symbol(if total_lines > visible_height { "▌" } else { "│" })
+            .thumb_style(Style::default().fg(if total_lines > visible_height && self.focus == Focus::Stashes {
+                self.theme.COLOR_GREY_600
+            } else {
+                self.theme.COLOR_BORDER
+            }));
+
+        // Render the scrollbar
+        frame.render_stateful_widget(scrollbar, self.layout.stashes_scrollbar, &mut scrollbar_state);
+    }
+}
@@ -101,6 +101,14 @@ impl App {
                 is_staged_changes = true;
             }
             
+            // Conflicted files first, they block any operation in progress
+            for file in self.uncommitted.conflicted.iter() {
+                lines_status_bottom.push(Line::from(vec![
+                    Span::styled("! ", Style::default().fg(self.theme.COLOR_YELLOW)),
+                    Span::styled(truncate_with_ellipsis(file, max_text_width), Style::default().fg(self.theme.COLOR_TEXT)),
+                ]));
+            }
+
             // Unstaged changes with prefix
             for file in self.uncommitted.unstaged.modified.iter() {
                 lines_status_bottom.push(Line::from(vec![
@@ -21,7 +21,8 @@ use crate::{
     git::{
         queries::{
             commits::{
-                get_current_branch
+                get_current_branch,
+                get_repo_state
             }
         }        
     }
@@ -29,19 +30,88 @@ use crate::{
 
 impl App {
     pub fn draw_statusbar(&mut self, frame: &mut Frame) {
-        let lines = match get_current_branch(&self.repo) {
+        let mut lines = match get_current_branch(&self.repo) {
             Some(branch) => Line::from(vec![Span::styled(
                 format!("  ● {}", branch),
                 Style::default().fg(self.theme.COLOR_GRASS),
             )]),
             None => {
-                let oid = self.repo.head().unwrap().target().unwrap();
-                Line::from(vec![Span::styled(
-                    format!("  detached head: #{:.6}", oid),
-                    Style::default().fg(self.theme.COLOR_TEXT),
-                )])
+                let label = match self.repo.head().ok().and_then(|head| head.target()) {
+                    Some(oid) => format!("  detached head: #{:.6}", oid),
+                    None => "  no commits yet".to_string(),
+                };
+                Line::from(vec![Span::styled(label, Style::default().fg(self.theme.COLOR_TEXT))])
             }
         };
+
+        // Show an operation in progress, such as a cherry-pick stopped on conflicts
+        if let Some(session) = self.rebase_session.as_ref() {
+            let (done, total) = session.get_progress();
+            lines.push_span(Span::styled(
+                format!("  (rebasing {}/{})", done, total),
+                Style::default().fg(self.theme.COLOR_YELLOW),
+            ));
+        } else if let Some(state) = get_repo_state(&self.repo) {
+            lines.push_span(Span::styled(
+                format!("  ({})", state),
+                Style::default().fg(self.theme.COLOR_YELLOW),
+            ));
+        }
+
+        // Show the search prompt while typing, and the query as long as its matches are highlighted
+        if self.focus == Focus::Search || self.search.is_some() {
+            let count = self.search.as_ref().map_or(0, |search| search.matches.len());
+            let state = match self.search.as_ref() {
+                Some(search) if search.is_running() => format!("{} matches, searching", count),
+                Some(_) => format!("{} matches", count),
+                None => String::new(),
+            };
+            let cursor = if self.focus == Focus::Search { "▏" } else { "" };
+            lines.push_span(Span::styled(
+                format!("  /{}{}", self.search_input, cursor),
+                Style::default().fg(self.theme.COLOR_YELLOW),
+            ));
+            if self.search_pickaxe {
+                lines.push_span(Span::styled("  +diffs", Style::default().fg(self.theme.COLOR_GREY_500)));
+            }
+            if !state.is_empty() {
+                lines.push_span(Span::styled(format!("  ({})", state), Style::default().fg(self.theme.COLOR_GREY_500)));
+            }
+        }
+
+        // Show the revision range the graph is limited to
+        if let Some(range) = self.range.as_ref() {
+            lines.push_span(Span::styled(
+                format!("  ({})", range),
+                Style::default().fg(self.theme.COLOR_TEXT),
+            ));
+        }
+
+        // Show the path the history is limited to
+        if let Some(pathspec) = self.pathspec.as_ref() {
+            lines.push_span(Span::styled(
+                format!("  (-- {})", pathspec),
+                Style::default().fg(self.theme.COLOR_TEXT),
+            ));
+        }
+
+        // Show that merged branches are collapsed
+        if self.first_parent {
+            lines.push_span(Span::styled(
+                "  (first parent)",
+                Style::default().fg(self.theme.COLOR_TEXT),
+            ));
+        }
+
+        // Show the network thread and what it reported last
+        if let Some(job) = self.network.as_ref() {
+            let status = if job.status.is_empty() { "connecting".to_string() } else { job.status.clone() };
+            lines.push_span(Span::styled(
+                format!("  {}: {}", job.label, status),
+                Style::default().fg(self.theme.COLOR_YELLOW),
+            ));
+        }
+
         let status_paragraph = ratatui::widgets::Paragraph::new(Text::from(lines))
             .left_aligned()
             .block(Block::default());
@@ -71,6 +141,15 @@ impl App {
             Focus::Branches => {
                 self.branches.sorted.len()
             }
+            Focus::Stashes => {
+                self.stashes.sorted.len()
+            }
+            Focus::Tags => {
+                self.tags.sorted.len()
+            }
+            Focus::Remotes => {
+                self.remotes.len()
+            }
             _ => 0,
         };
 
@@ -88,6 +167,9 @@ impl App {
                 Focus::Branches => {
                     self.branches.visible.values().map(|branches| branches.len()).sum()
                 }
+                Focus::Stashes => self.stashes_selected + 1,
+                Focus::Tags => self.tags_selected + 1,
+                Focus::Remotes => self.remotes_selected + 1,
                 _ => 0,
             }
         };
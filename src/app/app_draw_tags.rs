@@ -0,0 +1,120 @@
+#[rustfmt::skip]
+use ratatui::{
+    Frame,
+    style::Style,
+    text::{
+        Line,
+        Span,
+    },
+    widgets::{
+        Block,
+        Borders,
+        Scrollbar,
+        ScrollbarOrientation,
+        ScrollbarState,
+        List,
+        ListItem
+    },
+};
+#[rustfmt::skip]
+use crate::{
+    app::app::{
+        App,
+        Focus
+    },
+    helpers::{
+        symbols::{
+            SYM_TAG
+        },
+        text::{
+            truncate_with_ellipsis
+        }
+    }
+};
+
+impl App {
+
+    pub fn draw_tags(&mut self, frame: &mut Frame) {
+
+        // Padding
+        let padding = ratatui::widgets::Padding { left: 2, right: 0, top: 0, bottom: 0 };
+
+        // Calculate maximum available width for text
+        let available_width = self.layout.tags.width as usize - 1;
+        let max_text_width = available_width.saturating_sub(3);
+
+        // Lines
+        let mut lines: Vec<Line<'_>> = Vec::new();
+        for (tag_alias, tag_name) in self.tags.get_sorted_aliases().iter() {
+
+            // Text
+            let oid = self.oids.get_oid_by_alias(*tag_alias);
+            let truncated = truncate_with_ellipsis(&format!("{tag_name} #{oid:.6}"), max_text_width - 1);
+            let color = self.tags.get_color(&self.theme, tag_alias);
+
+            // Render a tag
+            lines.push(Line::from(Span::styled(format!("{SYM_TAG} {truncated}"), Style::default().fg(color))));
+        }
+
+        // Get vertical dimensions
+        let total_lines = lines.len();
+        let visible_height = self.layout.tags.height as usize - 2;
+
+        // Clamp selection
+        if total_lines == 0 {
+            self.tags_selected = 0;
+        } else if self.tags_selected >= total_lines {
+            self.tags_selected = total_lines - 1;
+        }
+
+        // Trap selection
+        self.trap_selection(self.tags_selected, &self.tags_scroll, total_lines, visible_height);
+
+        // Calculate scroll
+        let start = self.tags_scroll.get().min(total_lines.saturating_sub(visible_height));
+        let end = (start + visible_height).min(total_lines);
+
+        // Setup list items
+        let list_items: Vec<ListItem> = lines[start..end]
+            .iter()
+            .enumerate()
+            .map(|(idx, line)| {
+                if start + idx == self.tags_selected && self.focus == Focus::Tags {
+                    let spans: Vec<Span> = line.iter().map(|span| { Span::styled(span.content.clone(), span.style) }).collect();
+                    ListItem::new(Line::from(spans)).style(Style::default().bg(self.theme.COLOR_GREY_800))
+                } else if (idx + start).is_multiple_of(2) {
+                    ListItem::new(Line::from(line.clone().spans)).style(Style::default().bg(self.theme.COLOR_GREY_900))
+                } else {
+                    ListItem::new(line.clone())
+                }
+            })
+            .collect();
+
+        // Setup the list, separated from the panes above when any are shown
+        let list = List::new(list_items)
+            .block(
+                Block::default()
+                    .padding(padding)
+                    .borders(if self.is_branches || self.is_stashes { Borders::TOP } else { Borders::NONE })
+                    .border_style(Style::default().fg(self.theme.COLOR_BORDER))
+            );
+
+        frame.render_widget(list, self.layout.tags);
+
+        // Setup the scrollbar
+        let mut scrollbar_state = ScrollbarState::new(total_lines.saturating_sub(visible_height)).position(self.tags_scroll.get());
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(if self.is_branches || self.is_stashes { Some("┤") } else { Some("─") })
+            .end_symbol(Some("─"))
+            .track_symbol(Some("│"))
+            .thumb_symbol(if total_lines > visible_height { "▌" } else { "│" })
+            .thumb_style(Style::default().fg(if total_lines > visible_height && self.focus == Focus::Tags {
+                self.theme.COLOR_GREY_600
+            } else {
+                self.theme.COLOR_BORDER
+            }));
+
+        // Render the scrollbar
+        frame.render_stateful_widget(scrollbar, self.layout.tags_scrollbar, &mut scrollbar_state);
+    }
+}
@@ -23,15 +23,22 @@ use crate::{
     app::app::{
         App,
         Focus,
-        Viewport
+        Viewport,
+        ViewerSource
     },
     git::{
         queries::{
             diffs::{
                 get_file_at_oid,
+                get_file_at_index,
                 get_file_at_workdir,
                 get_file_diff_at_oid,
-                get_file_diff_at_workdir
+                get_file_diff_at_workdir,
+                get_file_diff_staged,
+                get_file_diff_unstaged
+            },
+            helpers::{
+                Hunk
             }
         }
     },
@@ -76,6 +83,9 @@ impl App {
         let start = self.viewer_scroll.get().min(total_lines.saturating_sub(visible_height));
         let end = (start + visible_height).min(total_lines);
 
+        // Lines marked for staging run from the anchor to the cursor
+        let marked = self.viewer_anchor.map(|anchor| anchor.min(self.viewer_selected)..=anchor.max(self.viewer_selected));
+
         // Setup list items
         let list_items: Vec<ListItem> = self.viewer_lines[start..end]
             .iter()
@@ -83,7 +93,8 @@ impl App {
             .map(|(i, line)| {
                 let absolute_idx = start + i;
                 let mut item = line.clone();
-                if absolute_idx == self.viewer_selected && self.focus == Focus::Viewport {
+                let is_marked = marked.as_ref().is_some_and(|marked| marked.contains(&absolute_idx));
+                if (absolute_idx == self.viewer_selected || is_marked) && self.focus == Focus::Viewport {
                     item = item.style(Style::default().bg(self.theme.COLOR_GREY_800));
                 }
                 item
@@ -138,6 +149,8 @@ impl App {
 
                     // Update the viewer to show the file at the selected commit OID
                     let oid = self.oids.get_oid_by_idx(self.graph_selected);
+                    self.viewer_source = ViewerSource::Commit;
+                    self.viewer_anchor = None;
                     self.update_viewer(*oid);
                     self.viewport = Viewport::Viewer;
 
@@ -165,40 +178,52 @@ impl App {
                             .cloned()
                     };
 
-                    // Update viewer for uncommitted file (Oid::zero indicates workdir)
+                    // Update viewer for staged file (Oid::zero indicates workdir)
+                    self.viewer_source = ViewerSource::Staged;
+                    self.viewer_anchor = None;
                     self.update_viewer(Oid::zero());
                     self.viewport = Viewport::Viewer;
                 }
             }
-            Focus::StatusBottom => {
-
-                // If uncommitted unstaged changes exist in bottom status view
-                if self.graph_selected == 0 && self.uncommitted.is_unstaged {
-                    let modified_len = self.uncommitted.unstaged.modified.len();
-                    let added_len = self.uncommitted.unstaged.added.len();
-                    let index = self.status_bottom_selected;
-
-                    // Select the file name from unstaged changes depending on index
-                    self.file_name = if index < modified_len {
-                        self.uncommitted.unstaged.modified.get(index).cloned()
-                    } else if index < modified_len + added_len {
-                        self.uncommitted
-                            .unstaged
-                            .added
-                            .get(index - modified_len)
-                            .cloned()
-                    } else {
-                        self.uncommitted
-                            .unstaged
-                            .deleted
-                            .get(index - modified_len - added_len)
-                            .cloned()
-                    };
-
-                    // Update viewer for uncommitted file
+            // If uncommitted unstaged changes exist in bottom status view
+            Focus::StatusBottom if self.graph_selected == 0 && (self.uncommitted.is_unstaged || self.uncommitted.is_conflicted) => {
+                let conflicted_len = self.uncommitted.conflicted.len();
+                let modified_len = self.uncommitted.unstaged.modified.len();
+                let added_len = self.uncommitted.unstaged.added.len();
+
+                // Conflicted files are listed above the unstaged ones
+                if self.status_bottom_selected < conflicted_len {
+                    self.file_name = self.uncommitted.conflicted.get(self.status_bottom_selected).cloned();
+                    self.viewer_source = ViewerSource::Workdir;
+                    self.viewer_anchor = None;
                     self.update_viewer(Oid::zero());
                     self.viewport = Viewport::Viewer;
+                    return;
                 }
+                let index = self.status_bottom_selected - conflicted_len;
+
+                // Select the file name from unstaged changes depending on index
+                self.file_name = if index < modified_len {
+                    self.uncommitted.unstaged.modified.get(index).cloned()
+                } else if index < modified_len + added_len {
+                    self.uncommitted
+                        .unstaged
+                        .added
+                        .get(index - modified_len)
+                        .cloned()
+                } else {
+                    self.uncommitted
+                        .unstaged
+                        .deleted
+                        .get(index - modified_len - added_len)
+                        .cloned()
+                };
+
+                // Update viewer for unstaged file
+                self.viewer_source = ViewerSource::Unstaged;
+                self.viewer_anchor = None;
+                self.update_viewer(Oid::zero());
+                self.viewport = Viewport::Viewer;
             }
             _ => {}
         }
@@ -209,20 +234,41 @@ impl App {
         // Clone the current file name
         let filename = self.file_name.clone().unwrap();
 
-        // Decide whether to use committed version or uncommitted (workdir)
-        let (original_lines, hunks) = if oid == Oid::zero() {(
-            get_file_at_workdir(&self.repo, &filename), // get current file in workdir
-            get_file_diff_at_workdir(&self.repo, &filename).unwrap_or_default(), // get diff for workdir
-        )} else {(
-            get_file_at_oid(&self.repo, oid, &filename), // get file at commit
-            get_file_diff_at_oid(&self.repo, oid, &filename).unwrap_or_default(), // get diff for commit
-        )};
+        // Decide whether to use committed version or uncommitted (index or workdir)
+        let (original_lines, hunks) = match self.viewer_source {
+            _ if oid != Oid::zero() => (
+                get_file_at_oid(&self.repo, oid, &filename), // get file at commit
+                get_file_diff_at_oid(&self.repo, oid, &filename).unwrap_or_default(), // get diff for commit
+            ),
+            ViewerSource::Staged => (
+                get_file_at_index(&self.repo, &filename), // get file as staged
+                get_file_diff_staged(&self.repo, &filename).unwrap_or_default(), // get diff between HEAD and index
+            ),
+            ViewerSource::Unstaged => (
+                get_file_at_workdir(&self.repo, &filename), // get current file in workdir
+                get_file_diff_unstaged(&self.repo, &filename).unwrap_or_default(), // get diff between index and workdir
+            ),
+            _ => (
+                get_file_at_workdir(&self.repo, &filename), // get current file in workdir
+                get_file_diff_at_workdir(&self.repo, &filename).unwrap_or_default(), // get diff for workdir
+            ),
+        };
 
-        self.viewer_lines.clear(); // Clear current viewer lines
+        let width = self.layout.graph.width as usize;
+        let (lines, hunk_lines) = self.get_hunk_lines(&original_lines, &hunks, width, (0, 0));
+        self.viewer_lines = lines;
+        self.viewer_hunk_lines = hunk_lines;
+    }
+
+    // Numbered and colored lines of a file around its hunks, with the hunk line each one came from
+    // The first lines of the old and new side are offset by the given numbers, for files shown in parts
+    pub fn get_hunk_lines(&self, original_lines: &[String], hunks: &[Hunk], width: usize, first: (usize, usize)) -> (Vec<ListItem<'static>>, Vec<Option<(usize, usize)>>) {
+        let mut viewer_lines = Vec::new(); // Lines to show
+        let mut viewer_hunk_lines = Vec::new(); // The hunk line of every line to show
         let mut current_line: usize = 0; // Current line in new file
         let mut current_line_old: usize = 0; // Current line in old file
 
-        for hunk in hunks.iter() {
+        for (hunk_idx, hunk) in hunks.iter().enumerate() {
             // Parse hunk header to extract old file start line and length
             // Example header: "@@ -22,8 +22,14 @@"
             let header = &hunk.header;
@@ -249,15 +295,16 @@ impl App {
                 // Wrap line to fit viewport width
                 let wrapped = wrap_words(
                     original_lines[current_line].clone(),
-                    (self.layout.graph.width as usize).saturating_sub(8),
+                    width.saturating_sub(8),
                 );
                 for (idx, line) in wrapped.into_iter().enumerate() {
 
                     // Push each wrapped line into viewer with line numbers
-                    self.viewer_lines.push(ListItem::new(
+                    viewer_hunk_lines.push(None);
+                    viewer_lines.push(ListItem::new(
                         Line::from(vec![
                             Span::styled(
-                                (if idx == 0 { format!("{:3}  ", current_line + 1) } else { "     ".to_string() }).to_string(),
+                                (if idx == 0 { format!("{:3}  ", first.1 + current_line + 1) } else { "     ".to_string() }).to_string(),
                                 Style::default().fg(self.theme.COLOR_BORDER),
                             ),
                             Span::styled(line.to_string(), Style::default().fg(self.theme.COLOR_GREY_500)),
@@ -270,23 +317,24 @@ impl App {
             }
             
             // Process lines in the hunk
-            for line in hunk.lines.iter().filter(|l| l.origin != 'H') {
+            for (line_idx, line) in hunk.lines.iter().filter(|l| l.origin != 'H').enumerate() {
                 let text = line.content.trim_end_matches('\n'); // remove trailing newline
 
                 // Determine styling, prefix, color, and line number based on line origin
                 let (style, prefix, side, fg, count) = match line.origin {
-                    '-' => (Style::default().bg(self.theme.COLOR_DARK_RED).fg(self.theme.COLOR_RED), "- ".to_string(), self.theme.COLOR_RED, self.theme.COLOR_RED, current_line_old + 1),
-                    '+' => (Style::default().bg(self.theme.COLOR_LIGHT_GREEN_900).fg(self.theme.COLOR_GREEN), "+ ".to_string(), self.theme.COLOR_GREEN, self.theme.COLOR_GREEN, current_line + 1),
-                    ' ' => (Style::default(), "".to_string(), self.theme.COLOR_BORDER, self.theme.COLOR_GREY_500, current_line + 1),
+                    '-' => (Style::default().bg(self.theme.COLOR_DARK_RED).fg(self.theme.COLOR_RED), "- ".to_string(), self.theme.COLOR_RED, self.theme.COLOR_RED, first.0 + current_line_old + 1),
+                    '+' => (Style::default().bg(self.theme.COLOR_LIGHT_GREEN_900).fg(self.theme.COLOR_GREEN), "+ ".to_string(), self.theme.COLOR_GREEN, self.theme.COLOR_GREEN, first.1 + current_line + 1),
+                    ' ' => (Style::default(), "".to_string(), self.theme.COLOR_BORDER, self.theme.COLOR_GREY_500, first.1 + current_line + 1),
                     _ => (Style::default(), "".to_string(), self.theme.COLOR_BORDER, self.theme.COLOR_GREY_500, 0)
                 };
 
                 // Wrap the line to viewport width
-                let wrapped = wrap_words(format!("{}{}", prefix, text), (self.layout.graph.width as usize).saturating_sub(9));
+                let wrapped = wrap_words(format!("{}{}", prefix, text), width.saturating_sub(9));
                 for (idx, line) in wrapped.into_iter().enumerate() {
                     
                     // Push each wrapped line into the viewer
-                    self.viewer_lines.push(
+                    viewer_hunk_lines.push(Some((hunk_idx, line_idx)));
+                    viewer_lines.push(
                         ListItem::new(Line::from(vec![
                             Span::styled(
                                 (if idx == 0 {format!("{:3}  ", count)} else {"     ".to_string()}).to_string(),
@@ -319,13 +367,14 @@ impl App {
         while current_line < original_lines.len() {
             let wrapped = wrap_words(
                 original_lines[current_line].clone(),
-                (self.layout.graph.width as usize).saturating_sub(8),
+                width.saturating_sub(8),
             );
             for (idx, line) in wrapped.into_iter().enumerate() {
-                self.viewer_lines.push(
+                viewer_hunk_lines.push(None);
+                viewer_lines.push(
                     ListItem::new(Line::from(vec![
                         Span::styled(
-                            (if idx == 0 {format!("{:3}  ", current_line + 1)} else {"     ".to_string()}).to_string(),
+                            (if idx == 0 {format!("{:3}  ", first.1 + current_line + 1)} else {"     ".to_string()}).to_string(),
                             Style::default().fg(self.theme.COLOR_BORDER),
                         ),
                         Span::styled(line.to_string(), Style::default().fg(self.theme.COLOR_GREY_500)),
@@ -335,5 +384,7 @@ impl App {
             }
             current_line += 1;
         }
+
+        (viewer_lines, viewer_hunk_lines)
     }
 }
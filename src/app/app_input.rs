@@ -1,6 +1,13 @@
 #[rustfmt::skip]
 use std::{
-    io
+    io,
+    thread,
+    sync::mpsc::Receiver,
+    time::Instant,
+    collections::{
+        HashMap,
+        HashSet
+    }
 };
 #[rustfmt::skip]
 use indexmap::IndexMap;
@@ -17,8 +24,17 @@ use ratatui::{
     }
 };
 #[rustfmt::skip]
+use git2::{
+    BranchType,
+    Oid,
+    Repository,
+    RepositoryState
+};
+#[rustfmt::skip]
 use edtui::{
     EditorMode,
+    EditorState,
+    Lines
 };
 #[rustfmt::skip]
 use crate::{
@@ -26,7 +42,38 @@ use crate::{
         App,
         Focus,
         Viewport,
-        Direction
+        Direction,
+        CommitKind,
+        ConfirmAction,
+        RemoteAction,
+        NetworkJob,
+        ViewerSource
+    },
+    core::{
+        batcher::{
+            push_revisions
+        },
+        chunk::{
+            NONE
+        },
+        search::{
+            Search
+        },
+        rebase::{
+            RebaseAction,
+            RebaseStep,
+            RebaseSession
+        },
+        conflicts::{
+            ConflictSide
+        },
+        log::{
+            Severity
+        },
+        error::{
+            AppError,
+            AppResult
+        }
     },
     git::{
         actions::{
@@ -34,28 +81,124 @@ use crate::{
                 checkout_head,
                 checkout_branch,
                 commit_staged,
+                amend_head,
+                reword_commit,
                 git_add_all,
                 reset_to_commit,
                 unstage_all,
                 fetch_over_ssh,
+                stage_file,
+                unstage_file,
+                discard_file,
+                delete_untracked,
                 push_over_ssh,
+                PushMode,
                 create_branch,
-                delete_branch
+                rename_branch,
+                rename_remote_branch,
+                delete_branch,
+                cherry_pick,
+                cherry_pick_commit,
+                revert,
+                commit_revert,
+                abort_operation
+            },
+            rebase::{
+                get_rebase_steps,
+                rebase_start,
+                rebase_step,
+                rebase_continue,
+                rebase_finish,
+                rebase_abort,
+                save_rebase_session,
+                clear_rebase_session,
+                squash_head,
+                reword_head,
+                amend_head_with_index
+            },
+            merges::{
+                MergeOutcome,
+                merge_revision,
+                commit_merge,
+                fast_forward
+            },
+            conflicts::{
+                write_conflict_file,
+                take_conflict_side,
+                mark_resolved
+            },
+            hunks::{
+                LineAction,
+                stage_lines,
+                unstage_lines,
+                discard_lines
+            },
+            pull::{
+                Upstream,
+                PullStrategy,
+                get_upstream,
+                get_upstream_oid,
+                get_pull_strategy
+            },
+            stashes::{
+                stash_save,
+                stash_apply,
+                stash_pop,
+                stash_drop
+            },
+            tags::{
+                create_tag,
+                delete_tag,
+                get_tag_names,
+                push_tags
+            },
+            remotes::{
+                add_remote,
+                remove_remote,
+                rename_remote,
+                set_remote_url,
+                set_local_bool
             }
         },
         queries::{
             diffs::{
                 get_filenames_diff_at_oid,
+                get_filenames_diff_at_workdir,
+                has_tracked_changes,
+                get_file_diff_unstaged
+            },
+            helpers::{
+                FileStatus
             },
             commits::{
+                count_unique_commits,
+                get_branch_tips,
                 get_current_branch
+            },
+            conflicts::{
+                get_conflicted_paths,
+                get_conflict_file
+            },
+            remotes::{
+                get_fetch_remote,
+                get_push_remote,
+                get_fetched_refs,
+                get_fetch_summary
             }
+        },
+        credentials::{
+            CredentialPrompt
+        },
+        network::{
+            Progress
         }
     },
     helpers::{
         palette::Theme,
         text::{
             editor_state_to_string,
+            editor_state_to_message,
+            pascal_to_spaced
         }
     }
 };
@@ -79,6 +222,10 @@ pub enum Command {
     ScrollDownCommit,
     GoToBeginning,
     GoToEnd,
+    Mark,
+    Search,
+    NextMatch,
+    PreviousMatch,
     
     // Branches
     JumpToBranch,
@@ -86,24 +233,57 @@ pub enum Command {
     
     // Git
     Fetch,
+    FetchAll,
     Checkout,
     HardReset,
     MixedReset,
     UnstageAll,
     StageAll,
+    Stage,
+    Unstage,
+    Discard,
     Commit,
+    Amend,
+    Reword,
     Push,
+    ForcePushWithLease,
     CreateANewBranch,
+    RenameBranch,
+    CreateATag,
+    DeleteATag,
+    PushTag,
+    PushAllTags,
     DeleteABranch,
+    Stash,
+    PopStash,
+    ApplyStash,
+    DropStash,
+    CherryPick,
+    Revert,
+    Continue,
+    Abort,
+    InteractiveRebase,
+    Merge,
+    MergeNoFastForward,
+    Pull,
+    Conflicts,
+    CancelNetwork,
     
     // Layout
     GoBack,
     Reload,
     Minimize,
     ToggleBranches,
+    ToggleStashes,
+    ToggleTags,
+    ToggleRemotes,
     ToggleStatus,
     ToggleInspector,
     ToggleSettings,
+    ToggleLog,
+    ToggleFirstParent,
+    FilterRange,
+    FilterPath,
     Exit,
 }
 
@@ -119,6 +299,23 @@ impl KeyBinding {
     }
 }
 
+impl Command {
+
+    // Commands that touch the repository, these always end up in the log
+    pub fn is_git(&self) -> bool {
+        matches!(
+            self,
+            Command::Fetch | Command::FetchAll | Command::Checkout | Command::HardReset | Command::MixedReset
+                | Command::UnstageAll | Command::StageAll | Command::Stage | Command::Unstage | Command::Discard
+                | Command::Commit | Command::Amend | Command::Reword | Command::Push | Command::ForcePushWithLease
+                | Command::CreateANewBranch | Command::RenameBranch | Command::CreateATag | Command::DeleteATag
+                | Command::PushTag | Command::PushAllTags | Command::DeleteABranch | Command::Stash | Command::PopStash
+                | Command::ApplyStash | Command::DropStash | Command::CherryPick | Command::Revert | Command::Continue
+                | Command::Abort | Command::InteractiveRebase | Command::Merge | Command::MergeNoFastForward | Command::Pull
+        )
+    }
+}
+
 impl App {
 
     pub fn load_keymap(&mut self) {
@@ -140,6 +337,10 @@ impl App {
         map.insert(KeyBinding::new(Down, KeyModifiers::ALT), Command::ScrollDownCommit);
         map.insert(KeyBinding::new(Home, KeyModifiers::NONE), Command::GoToBeginning);
         map.insert(KeyBinding::new(End, KeyModifiers::NONE), Command::GoToEnd);
+        map.insert(KeyBinding::new(Char(' '), KeyModifiers::NONE), Command::Mark);
+        map.insert(KeyBinding::new(Char('/'), KeyModifiers::NONE), Command::Search);
+        map.insert(KeyBinding::new(Char(']'), KeyModifiers::NONE), Command::NextMatch);
+        map.insert(KeyBinding::new(Char('['), KeyModifiers::NONE), Command::PreviousMatch);
 
         // Branches
         map.insert(KeyBinding::new(Char('j'), KeyModifiers::NONE), Command::JumpToBranch);
@@ -147,15 +348,41 @@ impl App {
         
         // Git
         map.insert(KeyBinding::new(Char('f'), KeyModifiers::NONE), Command::Fetch);
+        map.insert(KeyBinding::new(Char('F'), KeyModifiers::SHIFT), Command::FetchAll);
         map.insert(KeyBinding::new(Char('c'), KeyModifiers::NONE), Command::Checkout);
         map.insert(KeyBinding::new(Char('h'), KeyModifiers::NONE), Command::HardReset);
         map.insert(KeyBinding::new(Char('m'), KeyModifiers::NONE), Command::MixedReset);
         map.insert(KeyBinding::new(Char('u'), KeyModifiers::NONE), Command::UnstageAll);
         map.insert(KeyBinding::new(Char('s'), KeyModifiers::NONE), Command::StageAll);
+        map.insert(KeyBinding::new(Char('S'), KeyModifiers::SHIFT), Command::Stage);
+        map.insert(KeyBinding::new(Char('U'), KeyModifiers::SHIFT), Command::Unstage);
+        map.insert(KeyBinding::new(Char('x'), KeyModifiers::NONE), Command::Discard);
         map.insert(KeyBinding::new(Char('a'), KeyModifiers::NONE), Command::Commit);
+        map.insert(KeyBinding::new(Char('A'), KeyModifiers::SHIFT), Command::Amend);
+        map.insert(KeyBinding::new(Char('w'), KeyModifiers::NONE), Command::Reword);
         map.insert(KeyBinding::new(Char('p'), KeyModifiers::NONE), Command::Push);
+        map.insert(KeyBinding::new(Char('P'), KeyModifiers::SHIFT), Command::ForcePushWithLease);
         map.insert(KeyBinding::new(Char('b'), KeyModifiers::NONE), Command::CreateANewBranch);
+        map.insert(KeyBinding::new(Char('n'), KeyModifiers::NONE), Command::RenameBranch);
+        map.insert(KeyBinding::new(Char('t'), KeyModifiers::NONE), Command::CreateATag);
+        map.insert(KeyBinding::new(Char('T'), KeyModifiers::SHIFT), Command::DeleteATag);
+        map.insert(KeyBinding::new(Char('t'), KeyModifiers::ALT), Command::PushTag);
+        map.insert(KeyBinding::new(Char('T'), KeyModifiers::SHIFT | KeyModifiers::ALT), Command::PushAllTags);
         map.insert(KeyBinding::new(Char('d'), KeyModifiers::NONE), Command::DeleteABranch);
+        map.insert(KeyBinding::new(Char('z'), KeyModifiers::NONE), Command::Stash);
+        map.insert(KeyBinding::new(Char('Z'), KeyModifiers::SHIFT), Command::PopStash);
+        map.insert(KeyBinding::new(Char('y'), KeyModifiers::NONE), Command::ApplyStash);
+        map.insert(KeyBinding::new(Char('Y'), KeyModifiers::SHIFT), Command::DropStash);
+        map.insert(KeyBinding::new(Char('k'), KeyModifiers::NONE), Command::CherryPick);
+        map.insert(KeyBinding::new(Char('v'), KeyModifiers::NONE), Command::Revert);
+        map.insert(KeyBinding::new(Char('C'), KeyModifiers::SHIFT), Command::Continue);
+        map.insert(KeyBinding::new(Char('Q'), KeyModifiers::SHIFT), Command::Abort);
+        map.insert(KeyBinding::new(Char('i'), KeyModifiers::NONE), Command::InteractiveRebase);
+        map.insert(KeyBinding::new(Char('g'), KeyModifiers::NONE), Command::Merge);
+        map.insert(KeyBinding::new(Char('G'), KeyModifiers::SHIFT), Command::MergeNoFastForward);
+        map.insert(KeyBinding::new(Char('l'), KeyModifiers::NONE), Command::Pull);
+        map.insert(KeyBinding::new(F(2), KeyModifiers::NONE), Command::Conflicts);
+        map.insert(KeyBinding::new(Char('x'), KeyModifiers::CONTROL), Command::CancelNetwork);
 
         // Layout
         map.insert(KeyBinding::new(Esc, KeyModifiers::NONE), Command::GoBack);
@@ -163,8 +390,15 @@ impl App {
         map.insert(KeyBinding::new(Char('.'), KeyModifiers::NONE), Command::Minimize);
         map.insert(KeyBinding::new(Char('`'), KeyModifiers::NONE), Command::ToggleBranches);
         map.insert(KeyBinding::new(Char('2'), KeyModifiers::NONE), Command::ToggleStatus);
+        map.insert(KeyBinding::new(Char('3'), KeyModifiers::NONE), Command::ToggleStashes);
+        map.insert(KeyBinding::new(Char('4'), KeyModifiers::NONE), Command::ToggleTags);
+        map.insert(KeyBinding::new(Char('5'), KeyModifiers::NONE), Command::ToggleRemotes);
         map.insert(KeyBinding::new(Char('1'), KeyModifiers::NONE), Command::ToggleInspector);
         map.insert(KeyBinding::new(F(1), KeyModifiers::NONE), Command::ToggleSettings);
+        map.insert(KeyBinding::new(F(3), KeyModifiers::NONE), Command::ToggleLog);
+        map.insert(KeyBinding::new(F(4), KeyModifiers::NONE), Command::ToggleFirstParent);
+        map.insert(KeyBinding::new(Char('R'), KeyModifiers::SHIFT), Command::FilterRange);
+        map.insert(KeyBinding::new(Char('H'), KeyModifiers::SHIFT), Command::FilterPath);
         map.insert(KeyBinding::new(Char('c'), KeyModifiers::CONTROL), Command::Exit);
 
         self.keymap = map;
@@ -182,6 +416,93 @@ impl App {
 
     pub fn handle_key_event(&mut self, key_event: KeyEvent) {
 
+        // An error takes every key until it is dismissed
+        if self.error_message.is_some() {
+            if matches!(key_event.code, KeyCode::Esc | KeyCode::Enter) {
+                self.error_message = None;
+            }
+            return;
+        }
+
+        let key_binding = KeyBinding::new(key_event.code, key_event.modifiers);
+        let is_cancel = key_event.code == KeyCode::Esc
+            || (self.focus == Focus::ModalConfirm && matches!(key_event.code, KeyCode::Char('n') | KeyCode::Char('N')));
+        let is_busy = self.network.is_some();
+
+        // Keys typed into a modal belong to the action that opened it
+        let is_logged = if self.is_modal() && self.log.is_running() {
+            self.log.restart();
+            true
+        } else {
+            let (action, is_git) = self.get_log_action(&key_binding);
+            let target = self.get_log_target();
+            self.log.begin(&action, &target);
+            is_git
+        };
+
+        self.handle_key(key_event);
+
+        // Wait for the modal to be submitted
+        if self.is_modal() {
+            if !is_logged {
+                self.log.discard();
+            }
+            return;
+        }
+
+        // Network threads are logged once they finish
+        if is_logged && !is_cancel && (is_busy || self.network.is_none()) {
+            self.log.end();
+        } else {
+            self.log.discard();
+        }
+    }
+
+    // Shows a result in the title bar and keeps it in the log, errors also open a modal
+    pub fn notify(&mut self, severity: Severity, message: String) {
+        self.log.record(severity, &message);
+        if severity == Severity::Error {
+            self.error_message = Some(message.clone());
+        }
+        self.hint = message;
+    }
+
+    pub fn is_modal(&self) -> bool {
+        !matches!(
+            self.focus,
+            Focus::Viewport | Focus::Inspector | Focus::StatusTop | Focus::StatusBottom | Focus::Branches | Focus::Stashes | Focus::Tags | Focus::Remotes
+        )
+    }
+
+    // Names what a key does for the log, and whether it is git work
+    fn get_log_action(&self, key_binding: &KeyBinding) -> (String, bool) {
+        match self.focus {
+            Focus::ModalCredentials => return ("authenticate".to_string(), false),
+            Focus::Viewport if self.viewport == Viewport::Conflicts => return ("resolve conflicts".to_string(), false),
+            Focus::Viewport if self.viewport == Viewport::Rebase => return ("interactive rebase".to_string(), false),
+            _ => {}
+        }
+        match self.keymap.get(key_binding) {
+            Some(cmd) => (pascal_to_spaced(&format!("{:?}", cmd)).to_lowercase(), cmd.is_git()),
+            None => (String::new(), false),
+        }
+    }
+
+    // Names what the selection points at for the log
+    fn get_log_target(&self) -> String {
+        match self.focus {
+            Focus::Branches => self.branches.sorted.get(self.branches_selected).map(|(_, name)| name.clone()).unwrap_or_default(),
+            Focus::Stashes => self.stashes.sorted.get(self.stashes_selected).map(|(_, name)| name.clone()).unwrap_or_default(),
+            Focus::Tags => self.tags.sorted.get(self.tags_selected).map(|(_, name)| name.clone()).unwrap_or_default(),
+            Focus::Remotes => self.remotes.get(self.remotes_selected).map(|remote| remote.name.clone()).unwrap_or_default(),
+            Focus::Viewport if self.viewport == Viewport::Viewer => self.file_name.clone().unwrap_or_default(),
+            _ if self.graph_selected == 0 => "working tree".to_string(),
+            _ => format!("#{:.6}", self.oids.get_oid_by_idx(self.graph_selected)),
+        }
+    }
+
+    fn handle_key(&mut self, key_event: KeyEvent) {
+
         let key_binding = KeyBinding::new(key_event.code, key_event.modifiers);
         
         // Handle text editing
@@ -190,20 +511,123 @@ impl App {
                 if self.commit_editor.mode == EditorMode::Normal {
                     match key_event.code {
                         KeyCode::Esc => {
+                            self.commit_kind = CommitKind::New;
                             self.focus = Focus::Viewport;
                         }
+                        KeyCode::Enter if self.commit_kind == CommitKind::Rebase => {
+                            match reword_head(&self.repo, &editor_state_to_message(&self.commit_editor)) {
+                                Ok(_) => {
+                                    self.commit_editor = edtui::EditorState::default();
+                                    self.commit_kind = CommitKind::New;
+                                    self.focus = Focus::Viewport;
+                                    if let Some(session) = self.rebase_session.as_mut() {
+                                        session.current = None;
+                                    }
+                                    self.run_rebase_queue();
+                                }
+                                Err(e) => {
+                                    self.notify(Severity::Error, format!("reword failed: {}", e.message()));
+                                }
+                            }
+                        }
+                        KeyCode::Enter if self.commit_kind == CommitKind::Merge => {
+                            match commit_merge(&self.repo, &editor_state_to_message(&self.commit_editor), &self.name, &self.email) {
+                                Ok(_) => {
+                                    self.notify(Severity::Info, "merged".to_string());
+                                    self.commit_editor = edtui::EditorState::default();
+                                    self.commit_kind = CommitKind::New;
+                                    self.branches.visible.clear();
+                                    self.reload();
+                                    self.focus = Focus::Viewport;
+                                }
+                                Err(e) => {
+                                    self.notify(Severity::Error, format!("merge failed: {}", e.message()));
+                                }
+                            }
+                        }
+                        KeyCode::Enter if self.commit_kind == CommitKind::Revert => {
+                            match commit_revert(&self.repo, &editor_state_to_message(&self.commit_editor), &self.name, &self.email) {
+                                Ok(_) => {
+                                    self.notify(Severity::Info, "reverted".to_string());
+                                    self.commit_editor = edtui::EditorState::default();
+                                    self.commit_kind = CommitKind::New;
+                                    self.branches.visible.clear();
+                                    self.reload();
+                                    self.focus = Focus::Viewport;
+                                }
+                                Err(e) => {
+                                    self.notify(Severity::Error, format!("revert failed: {}", e.message()));
+                                }
+                            }
+                        }
+                        KeyCode::Enter if self.commit_kind == CommitKind::Tag => {
+                            let Some(target) = self.tag_target else {
+                                self.commit_kind = CommitKind::New;
+                                self.focus = Focus::Viewport;
+                                return;
+                            };
+                            let message = editor_state_to_message(&self.commit_editor);
+                            match create_tag(&self.repo, &self.tag_name, target, Some(&message), &self.name, &self.email) {
+                                Ok(_) => {
+                                    self.notify(Severity::Info, format!("tagged #{:.6} as {}", target, self.tag_name));
+                                    self.commit_editor = edtui::EditorState::default();
+                                    self.commit_kind = CommitKind::New;
+                                    self.tag_target = None;
+                                    self.reload();
+                                    self.focus = Focus::Viewport;
+                                }
+                                Err(e) => {
+                                    self.notify(Severity::Error, format!("tag failed: {}", e.message()));
+                                }
+                            }
+                        }
+                        KeyCode::Enter if self.commit_kind == CommitKind::Amend => {
+                            match amend_head(&self.repo, &editor_state_to_message(&self.commit_editor), &self.name, &self.email) {
+                                Ok(_) => {
+                                    self.notify(Severity::Info, "amended".to_string());
+                                    self.commit_editor = edtui::EditorState::default();
+                                    self.commit_kind = CommitKind::New;
+                                    self.branches.visible.clear();
+                                    self.reload();
+                                    self.focus = Focus::Viewport;
+                                }
+                                Err(e) => {
+                                    self.notify(Severity::Error, format!("amend failed: {}", e.message()));
+                                }
+                            }
+                        }
+                        KeyCode::Enter if let CommitKind::Reword(oid) = self.commit_kind => {
+                            match reword_commit(&self.repo, oid, &editor_state_to_message(&self.commit_editor), &self.name, &self.email) {
+                                Ok(_) => {
+                                    self.notify(Severity::Info, format!("reworded #{:.6}", oid));
+                                    self.commit_editor = edtui::EditorState::default();
+                                    self.commit_kind = CommitKind::New;
+                                    self.branches.visible.clear();
+                                    self.reload();
+                                    self.focus = Focus::Viewport;
+                                }
+                                Err(e) => {
+                                    self.notify(Severity::Error, format!("reword failed: {}", e.message()));
+                                }
+                            }
+                        }
                         KeyCode::Enter => {
-                            commit_staged(
+                            match commit_staged(
                                 &self.repo,
-                                &editor_state_to_string(&self.commit_editor),
+                                &editor_state_to_message(&self.commit_editor),
                                 &self.name,
                                 &self.email,
-                            )
-                            .expect("Error");
-                            self.commit_editor = edtui::EditorState::default();
-                            self.branches.visible.clear();
-                            self.reload();
-                            self.focus = Focus::Viewport;
+                            ) {
+                                Ok(_) => {
+                                    self.commit_editor = edtui::EditorState::default();
+                                    self.branches.visible.clear();
+                                    self.reload();
+                                    self.focus = Focus::Viewport;
+                                }
+                                Err(e) => {
+                                    self.notify(Severity::Error, format!("commit failed: {}", e.message()));
+                                }
+                            }
                         }
                         _ => {
                             self.commit_editor_event_handler
@@ -231,8 +655,8 @@ impl App {
                                     self.reload();
                                     self.focus = Focus::Viewport;
                                 }
-                                Err(_) => {
-                                    // TODO
+                                Err(e) => {
+                                    self.notify(Severity::Error, format!("cannot create the branch: {}", e.message()));
                                 }
                             }
                         }
@@ -247,25 +671,335 @@ impl App {
                 }
                 return;
             }
-            Focus::Viewport => {
-                if self.viewport == Viewport::Editor {
-                    if self.file_editor.mode == EditorMode::Normal {
-                        match key_event.code {
-                            KeyCode::Char('c')
-                                if key_event.modifiers.contains(KeyModifiers::CONTROL) => {}
-                            KeyCode::Esc => {
-                                self.viewport = Viewport::Graph;
+            Focus::ModalRenameBranch => {
+                match key_event.code {
+                    KeyCode::Char('r') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                        self.rename_branch_remote = !self.rename_branch_remote;
+                    }
+                    KeyCode::Esc if self.rename_branch_editor.mode == EditorMode::Normal => {
+                        self.rename_branch_name = None;
+                        self.focus = Focus::Viewport;
+                    }
+                    KeyCode::Enter if self.rename_branch_editor.mode == EditorMode::Normal => {
+                        self.rename_selected_branch();
+                    }
+                    _ => {
+                        self.rename_branch_editor_event_handler
+                            .on_key_event(key_event, &mut self.rename_branch_editor);
+                    }
+                }
+                return;
+            }
+            Focus::ModalCreateTag => {
+                match key_event.code {
+                    KeyCode::Char('a') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                        self.tag_annotated = !self.tag_annotated;
+                    }
+                    KeyCode::Esc if self.tag_editor.mode == EditorMode::Normal => {
+                        self.tag_target = None;
+                        self.focus = Focus::Viewport;
+                    }
+                    KeyCode::Enter if self.tag_editor.mode == EditorMode::Normal => {
+                        self.create_selected_tag();
+                    }
+                    _ => {
+                        self.tag_editor_event_handler
+                            .on_key_event(key_event, &mut self.tag_editor);
+                    }
+                }
+                return;
+            }
+            Focus::ModalRemote => {
+                match key_event.code {
+                    KeyCode::Esc if self.remote_editor.mode == EditorMode::Normal => {
+                        self.remote_action = None;
+                        self.focus = Focus::Remotes;
+                    }
+                    KeyCode::Enter if self.remote_editor.mode == EditorMode::Normal => {
+                        self.apply_remote_action();
+                    }
+                    _ => {
+                        self.remote_editor_event_handler
+                            .on_key_event(key_event, &mut self.remote_editor);
+                    }
+                }
+                return;
+            }
+            Focus::ModalRange => {
+                match key_event.code {
+                    KeyCode::Esc if self.range_editor.mode == EditorMode::Normal => {
+                        self.focus = std::mem::replace(&mut self.range_focus, Focus::Viewport);
+                    }
+                    KeyCode::Enter if self.range_editor.mode == EditorMode::Normal => {
+                        self.apply_range();
+                    }
+                    _ => {
+                        self.range_editor_event_handler
+                            .on_key_event(key_event, &mut self.range_editor);
+                    }
+                }
+                return;
+            }
+            Focus::ModalPath => {
+                match key_event.code {
+                    KeyCode::Esc if self.path_editor.mode == EditorMode::Normal => {
+                        self.focus = std::mem::replace(&mut self.path_focus, Focus::Viewport);
+                    }
+                    KeyCode::Enter if self.path_editor.mode == EditorMode::Normal => {
+                        let pathspec = editor_state_to_string(&self.path_editor).trim().to_string();
+                        self.focus = std::mem::replace(&mut self.path_focus, Focus::Viewport);
+                        self.apply_pathspec(Some(pathspec).filter(|pathspec| !pathspec.is_empty()));
+                    }
+                    _ => {
+                        self.path_editor_event_handler
+                            .on_key_event(key_event, &mut self.path_editor);
+                    }
+                }
+                return;
+            }
+            Focus::Search => {
+                match key_event.code {
+                    KeyCode::Esc => {
+                        // Back to where the search started
+                        self.search = None;
+                        self.search_input.clear();
+                        self.graph_selected = self.search_origin;
+                        self.focus = Focus::Viewport;
+                    }
+                    KeyCode::Enter => {
+                        self.focus = Focus::Viewport;
+                        if self.graph_selected != 0 && self.graph_selected < self.oids.get_commit_count() {
+                            let oid = self.oids.get_oid_by_idx(self.graph_selected);
+                            self.load_current_diff(*oid);
+                        }
+                    }
+                    KeyCode::Char('d') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                        self.search_pickaxe = !self.search_pickaxe;
+                        self.restart_search();
+                    }
+                    KeyCode::Backspace => {
+                        self.search_input.pop();
+                        self.restart_search();
+                    }
+                    KeyCode::Char(c) => {
+                        self.search_input.push(c);
+                        self.restart_search();
+                    }
+                    _ => {}
+                }
+                return;
+            }
+            Focus::ModalCredentials => {
+                match key_event.code {
+                    KeyCode::Esc => self.cancel_credentials(),
+                    KeyCode::Enter => self.submit_credentials(),
+                    KeyCode::Backspace => {
+                        self.credentials_input.pop();
+                    }
+                    KeyCode::Char(c) => self.credentials_input.push(c),
+                    _ => {}
+                }
+                return;
+            }
+            // Remote actions that only make sense in the remotes pane
+            Focus::Remotes => {
+                let selected = self.remotes.get(self.remotes_selected).map(|remote| remote.name.clone());
+                match (key_event.code, selected) {
+                    (KeyCode::Char('a'), _) => {
+                        self.open_remote_modal(RemoteAction::Add, "");
+                        return;
+                    }
+                    (KeyCode::Char('e'), Some(name)) => {
+                        let url = self.remotes[self.remotes_selected].fetch_url.clone();
+                        self.open_remote_modal(RemoteAction::SetUrl(name), &url);
+                        return;
+                    }
+                    (KeyCode::Char('E'), Some(name)) => {
+                        let url = self.remotes[self.remotes_selected].push_url.clone();
+                        self.open_remote_modal(RemoteAction::SetPushUrl(name), &url);
+                        return;
+                    }
+                    _ => {}
+                }
+            }
+            Focus::ModalStash => {
+                match key_event.code {
+                    KeyCode::Char('u') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                        self.stash_include_untracked = !self.stash_include_untracked;
+                    }
+                    KeyCode::Char('k') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                        self.stash_keep_index = !self.stash_keep_index;
+                    }
+                    KeyCode::Esc if self.stash_editor.mode == EditorMode::Normal => {
+                        self.focus = Focus::Viewport;
+                    }
+                    KeyCode::Enter if self.stash_editor.mode == EditorMode::Normal => {
+                        match stash_save(
+                            &self.path,
+                            &editor_state_to_string(&self.stash_editor),
+                            &self.name,
+                            &self.email,
+                            self.stash_include_untracked,
+                            self.stash_keep_index,
+                        ) {
+                            Ok(_) => {
+                                self.notify(Severity::Info, "changes stashed".to_string());
+                                self.stash_editor = edtui::EditorState::default();
+                                self.reload();
+                                self.focus = Focus::Viewport;
                             }
-                            _ => {
-                                self.file_editor_event_handler
-                                    .on_key_event(key_event, &mut self.file_editor);
+                            Err(e) => {
+                                self.notify(Severity::Error, format!("stash failed: {}", e.message()));
                             }
                         }
-                    } else {
-                        self.file_editor_event_handler
-                            .on_key_event(key_event, &mut self.file_editor);
+                    }
+                    _ => {
+                        self.stash_editor_event_handler
+                            .on_key_event(key_event, &mut self.stash_editor);
+                    }
+                }
+                return;
+            }
+            Focus::Viewport if self.viewport == Viewport::Rebase => {
+                let action = match key_event.code {
+                    KeyCode::Char('p') => Some(RebaseAction::Pick),
+                    KeyCode::Char('r') => Some(RebaseAction::Reword),
+                    KeyCode::Char('e') => Some(RebaseAction::Edit),
+                    KeyCode::Char('s') => Some(RebaseAction::Squash),
+                    KeyCode::Char('f') => Some(RebaseAction::Fixup),
+                    KeyCode::Char('d') => Some(RebaseAction::Drop),
+                    _ => None,
+                };
+                if let Some(action) = action {
+                    if let Some(step) = self.rebase_steps.get_mut(self.rebase_selected) {
+                        step.action = action;
+                    }
+                    return;
+                }
+                match key_event.code {
+                    KeyCode::Char('K') => {
+                        if self.rebase_selected > 0 && self.rebase_selected < self.rebase_steps.len() {
+                            self.rebase_steps.swap(self.rebase_selected, self.rebase_selected - 1);
+                            self.rebase_selected -= 1;
+                        }
+                        return;
+                    }
+                    KeyCode::Char('J') => {
+                        if self.rebase_selected + 1 < self.rebase_steps.len() {
+                            self.rebase_steps.swap(self.rebase_selected, self.rebase_selected + 1);
+                            self.rebase_selected += 1;
+                        }
+                        return;
+                    }
+                    KeyCode::Enter => {
+                        self.start_rebase();
+                        return;
+                    }
+                    KeyCode::Esc => {
+                        self.rebase_steps.clear();
+                        self.rebase_base = None;
+                        self.viewport = Viewport::Graph;
+                        return;
+                    }
+                    _ => {}
+                }
+
+                // Only let the navigation through while editing the todo list
+                if !matches!(
+                    self.keymap.get(&key_binding),
+                    Some(Command::ScrollUp | Command::ScrollDown | Command::PageUp | Command::PageDown | Command::ScrollUpHalf | Command::ScrollDownHalf | Command::GoToBeginning | Command::GoToEnd | Command::NextPane | Command::PreviousPane | Command::Minimize | Command::Exit)
+                ) {
+                    return;
+                }
+            }
+            Focus::ModalConfirm => {
+                match key_event.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => self.on_confirm(),
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => self.on_cancel_confirm(),
+                    _ => {}
+                }
+                return;
+            }
+            Focus::Viewport if self.viewport == Viewport::Conflicts => {
+                match key_event.code {
+                    KeyCode::Char('o') => {
+                        self.resolve_conflict(ConflictSide::Ours);
+                        return;
+                    }
+                    KeyCode::Char('t') => {
+                        self.resolve_conflict(ConflictSide::Theirs);
+                        return;
+                    }
+                    KeyCode::Char('O') => {
+                        self.take_conflict_file(ConflictSide::Ours);
+                        return;
+                    }
+                    KeyCode::Char('T') => {
+                        self.take_conflict_file(ConflictSide::Theirs);
                         return;
                     }
+                    KeyCode::Char('s') => {
+                        self.stage_conflict_file();
+                        return;
+                    }
+                    KeyCode::Up => {
+                        self.conflict_selected = self.conflict_selected.saturating_sub(1);
+                        return;
+                    }
+                    KeyCode::Down => {
+                        let count = self.conflict_file.as_ref().map(|file| file.get_conflict_count()).unwrap_or(0);
+                        if self.conflict_selected + 1 < count {
+                            self.conflict_selected += 1;
+                        }
+                        return;
+                    }
+                    KeyCode::Left => {
+                        if self.conflicts_selected > 0 {
+                            self.conflicts_selected -= 1;
+                            self.load_conflict_file();
+                        }
+                        return;
+                    }
+                    KeyCode::Right => {
+                        if self.conflicts_selected + 1 < self.conflicts.len() {
+                            self.conflicts_selected += 1;
+                            self.load_conflict_file();
+                        }
+                        return;
+                    }
+                    KeyCode::Esc => {
+                        self.viewport = Viewport::Graph;
+                        return;
+                    }
+                    _ => {}
+                }
+
+                // Leave the view once the operation moves on
+                match self.keymap.get(&key_binding) {
+                    Some(Command::Continue | Command::Abort) => {
+                        self.viewport = Viewport::Graph;
+                    }
+                    Some(Command::Conflicts | Command::NextPane | Command::PreviousPane | Command::Minimize | Command::Exit) => {}
+                    _ => return,
+                }
+            }
+            Focus::Viewport if self.viewport == Viewport::Editor => {
+                if self.file_editor.mode == EditorMode::Normal {
+                    match key_event.code {
+                        KeyCode::Char('c')
+                            if key_event.modifiers.contains(KeyModifiers::CONTROL) => {}
+                        KeyCode::Esc => {
+                            self.viewport = Viewport::Graph;
+                        }
+                        _ => {
+                            self.file_editor_event_handler
+                                .on_key_event(key_event, &mut self.file_editor);
+                        }
+                    }
+                } else {
+                    self.file_editor_event_handler
+                        .on_key_event(key_event, &mut self.file_editor);
+                    return;
                 }
             }
             _ => {}
@@ -290,6 +1024,10 @@ impl App {
                 Command::ScrollDownCommit => self.on_scroll_down_commit(),
                 Command::GoToBeginning => self.on_scroll_to_beginning(),
                 Command::GoToEnd => self.on_scroll_to_end(),
+                Command::Mark => self.on_mark(),
+                Command::Search => self.on_search(),
+                Command::NextMatch => self.on_next_match(),
+                Command::PreviousMatch => self.on_previous_match(),
 
                 // Branches
                 Command::JumpToBranch => self.on_jump_to_branch(),
@@ -297,24 +1035,57 @@ impl App {
 
                 // Git
                 Command::Fetch => self.on_fetch(),
+                Command::FetchAll => self.on_fetch_all(),
                 Command::Checkout => self.on_checkout(),
                 Command::HardReset => self.on_hard_reset(),
                 Command::MixedReset => self.on_mixed_reset(),
                 Command::UnstageAll => self.on_unstage_all(),
                 Command::StageAll => self.on_stage_all(),
+                Command::Stage => self.on_stage(),
+                Command::Unstage => self.on_unstage(),
+                Command::Discard => self.on_discard(),
                 Command::Commit => self.on_commit(),
-                Command::Push => self.on_push(),
+                Command::Amend => self.on_amend(),
+                Command::Reword => self.on_reword(),
+                Command::Push => self.on_push(PushMode::Normal),
+                Command::ForcePushWithLease => self.on_push(PushMode::ForceWithLease),
                 Command::CreateANewBranch => self.on_create_branch(),
+                Command::RenameBranch => self.on_rename_branch(),
+                Command::CreateATag => self.on_create_tag(),
+                Command::DeleteATag => self.on_delete_tag(),
+                Command::PushTag => self.on_push_tag(),
+                Command::PushAllTags => self.on_push_all_tags(),
                 Command::DeleteABranch => self.on_delete_branch(),
+                Command::Stash => self.on_stash(),
+                Command::PopStash => self.on_pop_stash(),
+                Command::ApplyStash => self.on_apply_stash(),
+                Command::DropStash => self.on_drop_stash(),
+                Command::CherryPick => self.on_cherry_pick(),
+                Command::Revert => self.on_revert(),
+                Command::Continue => self.on_continue(),
+                Command::Abort => self.on_abort(),
+                Command::InteractiveRebase => self.on_interactive_rebase(),
+                Command::Merge => self.on_merge(false),
+                Command::MergeNoFastForward => self.on_merge(true),
+                Command::Pull => self.on_pull(),
+                Command::Conflicts => self.on_conflicts(),
+                Command::CancelNetwork => self.on_cancel_network(),
                 
                 // Layout
                 Command::GoBack => self.on_go_back(),
                 Command::Reload => self.on_reload(),
                 Command::Minimize => self.on_minimize(),
                 Command::ToggleBranches => self.on_toggle_branches(),
+                Command::ToggleStashes => self.on_toggle_stashes(),
+                Command::ToggleTags => self.on_toggle_tags(),
+                Command::ToggleRemotes => self.on_toggle_remotes(),
                 Command::ToggleStatus => self.on_toggle_status(),
                 Command::ToggleInspector => self.on_toggle_inspector(),
                 Command::ToggleSettings => self.on_toggle_settings(),
+                Command::ToggleLog => self.on_toggle_log(),
+                Command::ToggleFirstParent => self.on_toggle_first_parent(),
+                Command::FilterRange => self.on_filter_range(),
+                Command::FilterPath => self.on_filter_path(),
                 Command::Exit => self.on_exit(),
             }
         }
@@ -323,7 +1094,9 @@ impl App {
     pub fn on_select(&mut self) {
         match self.focus {
             Focus::Branches => {
-                let (oid, branch) = self.branches.sorted.get(self.branches_selected).unwrap();
+                let Some((oid, branch)) = self.branches.sorted.get(self.branches_selected) else {
+                    return;
+                };
 
                 let branch = branch.clone(); // clone because we may insert/remove it
 
@@ -355,9 +1128,44 @@ impl App {
                 if self.viewport == Viewport::Settings
                     && let Some(position) = self.settings_selections.iter().position(|&x| x == self.settings_selected) {
                         match position {
-                            3 => self.theme = Theme::classic(),
+                            2 => {
+                                if let Err(e) = self.credentials.cycle(&self.repo) {
+                                    self.notify(Severity::Error, format!("could not save the authorization: {}", e.message()));
+                                }
+                            }
+                            3 => self.theme = Theme::classic(),
                             4 => self.theme = Theme::ansi(),
                             5 => self.theme = Theme::monochrome(),
+                            6 => {
+                                self.fetch_prune = !self.fetch_prune;
+                                if let Err(e) = set_local_bool(&self.repo, "guitar.fetchprune", self.fetch_prune) {
+                                    self.notify(Severity::Error, format!("could not save the fetch settings: {}", e.message()));
+                                }
+                            }
+                            7 => {
+                                self.fetch_tags = !self.fetch_tags;
+                                if let Err(e) = set_local_bool(&self.repo, "guitar.fetchtags", self.fetch_tags) {
+                                    self.notify(Severity::Error, format!("could not save the fetch settings: {}", e.message()));
+                                }
+                            }
+                            8 => {
+                                self.confirm_hard_reset = !self.confirm_hard_reset;
+                                if let Err(e) = set_local_bool(&self.repo, "guitar.confirmhardreset", self.confirm_hard_reset) {
+                                    self.notify(Severity::Error, format!("could not save the confirmation settings: {}", e.message()));
+                                }
+                            }
+                            9 => {
+                                self.confirm_delete_branch = !self.confirm_delete_branch;
+                                if let Err(e) = set_local_bool(&self.repo, "guitar.confirmdeletebranch", self.confirm_delete_branch) {
+                                    self.notify(Severity::Error, format!("could not save the confirmation settings: {}", e.message()));
+                                }
+                            }
+                            10 => {
+                                self.confirm_force_push = !self.confirm_force_push;
+                                if let Err(e) = set_local_bool(&self.repo, "guitar.confirmforcepush", self.confirm_force_push) {
+                                    self.notify(Severity::Error, format!("could not save the confirmation settings: {}", e.message()));
+                                }
+                            }
                             _ => {}
                         }
                         self.reload();
@@ -366,16 +1174,21 @@ impl App {
             Focus::ModalCheckout => {
                 let alias = self.oids.get_alias_by_idx(self.graph_selected);
                 let branches = self.branches.visible.get(&alias).cloned().unwrap_or_default();
-                checkout_branch(
+                let Some(branch) = branches.get(self.modal_checkout_selected as usize) else {
+                    return;
+                };
+                let result = checkout_branch(
                     &self.repo,
                     &mut self.branches.visible,
                     &mut self.branches.local,
                     alias,
-                    branches.get(self.modal_checkout_selected as usize).unwrap(),
-                )
-                .expect("Error");
+                    branch,
+                );
                 self.modal_checkout_selected = 0;
                 self.focus = Focus::Viewport;
+                if let Err(e) = result {
+                    self.notify(Severity::Error, format!("checkout failed: {}", e.message()));
+                }
                 self.reload();
             }
             Focus::ModalSolo => {
@@ -385,7 +1198,9 @@ impl App {
                     .get(&alias)
                     .cloned()
                     .unwrap_or_default();
-                let branch = branches.get(self.modal_solo_selected as usize).unwrap();
+                let Some(branch) = branches.get(self.modal_solo_selected as usize) else {
+                    return;
+                };
                 
                 // Check if the same branch is already the only one visible
                 let already_visible = 
@@ -406,33 +1221,77 @@ impl App {
                 self.focus = Focus::Viewport;
                 self.reload();
             }
+            Focus::ModalMerge => {
+                let alias = self.oids.get_alias_by_idx(self.graph_selected);
+                let branches = self.branches.all.get(&alias).cloned().unwrap_or_default();
+                if let Some(branch) = branches.get(self.modal_merge_selected as usize) {
+                    self.merge(branch);
+                }
+                self.modal_merge_selected = 0;
+                if self.focus == Focus::ModalMerge {
+                    self.focus = Focus::Viewport;
+                }
+            }
+            Focus::ModalRevert => {
+                let oid = *self.oids.get_oid_by_idx(self.graph_selected);
+                self.focus = Focus::Viewport;
+                self.revert(oid, self.modal_revert_selected as u32 + 1);
+                self.modal_revert_selected = 0;
+            }
             Focus::ModalDeleteBranch => {
                 let alias = self.oids.get_alias_by_idx(self.graph_selected);
                 let branches = self.branches.visible.get(&alias).cloned().unwrap_or_default();
-                let branch = branches.get(self.modal_delete_branch_selected as usize).unwrap();
-                match delete_branch(&self.repo, branch) {
-                    Ok(_) => {
-                        self.branches.visible.clear();
-                        self.modal_delete_branch_selected = 0;
-                        self.focus = Focus::Viewport;
-                        self.reload();
-                    }
-                    Err(_) => {
-                        // TODO
-                    }
-                }
+                let Some(branch) = branches.get(self.modal_delete_branch_selected as usize) else {
+                    return;
+                };
+                self.modal_delete_branch_selected = 0;
+                self.focus = Focus::Viewport;
+                self.request_delete_branch(branch.clone());
             }
             Focus::StatusTop | Focus::StatusBottom => {
                 self.open_viewer();
                 self.focus = Focus::Viewport;
             }
+            Focus::Stashes => {
+                self.on_jump_to_stash();
+            }
+            Focus::Tags => {
+                self.on_jump_to_tag();
+            }
             _ => {}
         };
     }
 
     pub fn on_next_pane(&mut self) {
         self.focus = match self.focus {
-            Focus::Branches => Focus::Viewport,
+            Focus::Branches => {
+                if self.is_stashes {
+                    Focus::Stashes
+                } else if self.is_tags {
+                    Focus::Tags
+                } else if self.is_remotes {
+                    Focus::Remotes
+                } else {
+                    Focus::Viewport
+                }
+            }
+            Focus::Stashes => {
+                if self.is_tags {
+                    Focus::Tags
+                } else if self.is_remotes {
+                    Focus::Remotes
+                } else {
+                    Focus::Viewport
+                }
+            }
+            Focus::Tags => {
+                if self.is_remotes {
+                    Focus::Remotes
+                } else {
+                    Focus::Viewport
+                }
+            }
+            Focus::Remotes => Focus::Viewport,
             Focus::Viewport => {
                 if self.focus == Focus::Viewport && (self.viewport == Viewport::Editor || self.viewport == Viewport::Settings) {
                     return;
@@ -443,6 +1302,12 @@ impl App {
                     Focus::StatusTop
                 } else if self.is_branches {
                     Focus::Branches
+                } else if self.is_stashes {
+                    Focus::Stashes
+                } else if self.is_tags {
+                    Focus::Tags
+                } else if self.is_remotes {
+                    Focus::Remotes
                 } else {
                     Focus::Viewport
                 }
@@ -452,6 +1317,12 @@ impl App {
                     Focus::StatusTop
                 } else if self.is_branches {
                     Focus::Branches
+                } else if self.is_stashes {
+                    Focus::Stashes
+                } else if self.is_tags {
+                    Focus::Tags
+                } else if self.is_remotes {
+                    Focus::Remotes
                 } else {
                     Focus::Viewport
                 }
@@ -461,6 +1332,12 @@ impl App {
                     Focus::StatusBottom
                 } else if self.is_branches {
                     Focus::Branches
+                } else if self.is_stashes {
+                    Focus::Stashes
+                } else if self.is_tags {
+                    Focus::Tags
+                } else if self.is_remotes {
+                    Focus::Remotes
                 } else {
                     Focus::Viewport
                 }
@@ -468,6 +1345,12 @@ impl App {
             Focus::StatusBottom => {
                 if self.is_branches {
                     Focus::Branches
+                } else if self.is_stashes {
+                    Focus::Stashes
+                } else if self.is_tags {
+                    Focus::Tags
+                } else if self.is_remotes {
+                    Focus::Remotes
                 } else {
                     Focus::Viewport
                 }
@@ -478,7 +1361,16 @@ impl App {
     
     pub fn on_previous_pane(&mut self) {
         self.focus = match self.focus {
-            Focus::Branches => {
+            Focus::Remotes if self.is_tags => {
+                Focus::Tags
+            }
+            Focus::Tags | Focus::Remotes if self.is_stashes => {
+                Focus::Stashes
+            }
+            Focus::Stashes | Focus::Tags | Focus::Remotes if self.is_branches => {
+                Focus::Branches
+            }
+            Focus::Branches | Focus::Stashes | Focus::Tags | Focus::Remotes => {
                 if self.is_status && self.graph_selected == 0 {
                     Focus::StatusBottom
                 } else if self.is_status {
@@ -493,7 +1385,13 @@ impl App {
                 if self.focus == Focus::Viewport && (self.viewport == Viewport::Editor || self.viewport == Viewport::Settings) {
                     return;
                 }
-                if self.is_branches {
+                if self.is_remotes {
+                    Focus::Remotes
+                } else if self.is_tags {
+                    Focus::Tags
+                } else if self.is_stashes {
+                    Focus::Stashes
+                } else if self.is_branches {
                     Focus::Branches
                 } else if self.is_status && self.graph_selected == 0 {
                     Focus::StatusBottom
@@ -526,6 +1424,18 @@ impl App {
                 let page = self.layout.branches.height as usize - 1;
                 self.branches_selected = self.branches_selected.saturating_sub(page);
             }
+            Focus::Stashes => {
+                let page = self.layout.stashes.height as usize - 1;
+                self.stashes_selected = self.stashes_selected.saturating_sub(page);
+            }
+            Focus::Tags => {
+                let page = self.layout.tags.height as usize - 1;
+                self.tags_selected = self.tags_selected.saturating_sub(page);
+            }
+            Focus::Remotes => {
+                let page = self.layout.remotes.height as usize - 1;
+                self.remotes_selected = self.remotes_selected.saturating_sub(page);
+            }
             Focus::Viewport => {
                 let page = self.layout.graph.height as usize - 1;
                 match self.viewport {
@@ -537,7 +1447,7 @@ impl App {
                         }
                         if self.graph_selected != 0 && self.graph_selected < self.oids.get_commit_count() {
                             let oid = self.oids.get_oid_by_idx(self.graph_selected);
-                            self.current_diff = get_filenames_diff_at_oid(&self.repo, *oid);
+                            self.load_current_diff(*oid);
                         }
                     }
                     Viewport::Viewer => {
@@ -551,6 +1461,12 @@ impl App {
                         self.settings_selected = self.settings_selected.saturating_sub(page);
                         self.last_input_direction = Some(Direction::Up);
                     }
+                    Viewport::Rebase => {
+                        self.rebase_selected = self.rebase_selected.saturating_sub(page);
+                    }
+                    Viewport::Log => {
+                        self.log_selected = self.log_selected.saturating_sub(page);
+                    }
                     _ => {}
                 }
             }
@@ -571,7 +1487,7 @@ impl App {
 
         if self.graph_selected != 0 && self.graph_selected < self.oids.get_commit_count() {
             let oid = self.oids.get_oid_by_idx(self.graph_selected);
-            self.current_diff = get_filenames_diff_at_oid(&self.repo, *oid);
+            self.load_current_diff(*oid);
         }
     }
 
@@ -581,6 +1497,18 @@ impl App {
                 let page = self.layout.branches.height as usize - 1;
                 self.branches_selected += page;
             }
+            Focus::Stashes => {
+                let page = self.layout.stashes.height as usize - 1;
+                self.stashes_selected += page;
+            }
+            Focus::Tags => {
+                let page = self.layout.tags.height as usize - 1;
+                self.tags_selected += page;
+            }
+            Focus::Remotes => {
+                let page = self.layout.remotes.height as usize - 1;
+                self.remotes_selected += page;
+            }
             Focus::Viewport => {
                 let page = self.layout.graph.height as usize - 1;
                 match self.viewport {
@@ -592,7 +1520,7 @@ impl App {
                         }
                         if self.graph_selected != 0 && self.graph_selected < self.oids.get_commit_count() {
                             let oid = self.oids.get_oid_by_idx(self.graph_selected);
-                            self.current_diff = get_filenames_diff_at_oid(&self.repo, *oid);
+                            self.load_current_diff(*oid);
                         }
                     }
                     Viewport::Viewer => {
@@ -606,6 +1534,12 @@ impl App {
                         self.settings_selected += page;
                         self.last_input_direction = Some(Direction::Down);
                     }
+                    Viewport::Rebase => {
+                        self.rebase_selected += page;
+                    }
+                    Viewport::Log => {
+                        self.log_selected += page;
+                    }
                     _ => {}
                 }
             }
@@ -625,7 +1559,7 @@ impl App {
         };
         if self.graph_selected != 0 && self.graph_selected < self.oids.get_commit_count() {
             let oid = self.oids.get_oid_by_idx(self.graph_selected);
-            self.current_diff = get_filenames_diff_at_oid(&self.repo, *oid);
+            self.load_current_diff(*oid);
         }
     }
 
@@ -634,6 +1568,15 @@ impl App {
             Focus::Branches => {
                 self.branches_selected = self.branches_selected.saturating_sub(1);
             }
+            Focus::Stashes => {
+                self.stashes_selected = self.stashes_selected.saturating_sub(1);
+            }
+            Focus::Tags => {
+                self.tags_selected = self.tags_selected.saturating_sub(1);
+            }
+            Focus::Remotes => {
+                self.remotes_selected = self.remotes_selected.saturating_sub(1);
+            }
             Focus::Viewport => {
                 match self.viewport {
                     Viewport::Graph => {
@@ -645,18 +1588,22 @@ impl App {
                         }
                         if self.graph_selected != 0 && self.graph_selected < self.oids.get_commit_count() {
                             let oid = self.oids.get_oid_by_idx(self.graph_selected);
-                            self.current_diff = get_filenames_diff_at_oid(&self.repo, *oid);
+                            self.load_current_diff(*oid);
                         }
                     }
-                    Viewport::Viewer => {
-                        if self.viewer_selected > 0 {
-                            self.viewer_selected -= 1;
-                        }
+                    Viewport::Viewer if self.viewer_selected > 0 => {
+                        self.viewer_selected -= 1;
                     }
                     Viewport::Settings => {
                         self.settings_selected = self.settings_selected.saturating_sub(1);
                         self.last_input_direction = Some(Direction::Up);
                     }
+                    Viewport::Rebase => {
+                        self.rebase_selected = self.rebase_selected.saturating_sub(1);
+                    }
+                    Viewport::Log => {
+                        self.log_selected = self.log_selected.saturating_sub(1);
+                    }
                     _ => {}
                 }
                 if self.viewport == Viewport::Graph {}
@@ -688,6 +1635,23 @@ impl App {
                     self.modal_solo_selected - 1
                 };
             }
+            Focus::ModalMerge => {
+                let alias = self.oids.get_alias_by_idx(self.graph_selected);
+                let branches = self.branches.all.entry(alias).or_default();
+                self.modal_merge_selected = if self.modal_merge_selected - 1 < 0 {
+                    branches.len() as i32 - 1
+                } else {
+                    self.modal_merge_selected - 1
+                };
+            }
+            Focus::ModalRevert => {
+                let parents = self.get_selected_parent_count();
+                self.modal_revert_selected = if self.modal_revert_selected - 1 < 0 {
+                    parents - 1
+                } else {
+                    self.modal_revert_selected - 1
+                };
+            }
             Focus::ModalDeleteBranch => {
                 let alias = self.oids.get_alias_by_idx(self.graph_selected);
                 let branches = self.branches.visible.entry(alias).or_default();
@@ -710,6 +1674,15 @@ impl App {
             Focus::Branches => {
                 self.branches_selected += 1;
             }
+            Focus::Stashes => {
+                self.stashes_selected += 1;
+            }
+            Focus::Tags => {
+                self.tags_selected += 1;
+            }
+            Focus::Remotes => {
+                self.remotes_selected += 1;
+            }
             Focus::Viewport => match self.viewport {
                 Viewport::Graph => {
                     if self.graph_selected + 1 < self.oids.get_commit_count() {
@@ -717,18 +1690,22 @@ impl App {
                     }
                     if self.graph_selected != 0 && self.graph_selected < self.oids.get_commit_count() {
                         let oid = self.oids.get_oid_by_idx(self.graph_selected);
-                        self.current_diff = get_filenames_diff_at_oid(&self.repo, *oid);
+                        self.load_current_diff(*oid);
                     }
                 }
-                Viewport::Viewer => {
-                    if self.viewer_selected + 1 < self.viewer_lines.len() {
-                        self.viewer_selected += 1;
-                    }
+                Viewport::Viewer if self.viewer_selected + 1 < self.viewer_lines.len() => {
+                    self.viewer_selected += 1;
                 }
                 Viewport::Settings => {
                     self.settings_selected += 1;
                     self.last_input_direction = Some(Direction::Down);
                 }
+                Viewport::Rebase => {
+                    self.rebase_selected += 1;
+                }
+                Viewport::Log => {
+                    self.log_selected += 1;
+                }
                 _ => {}
             },
             Focus::Inspector => {
@@ -758,6 +1735,23 @@ impl App {
                     self.modal_solo_selected + 1
                 };
             }
+            Focus::ModalMerge => {
+                let alias = self.oids.get_alias_by_idx(self.graph_selected);
+                let branches = self.branches.all.entry(alias).or_default();
+                self.modal_merge_selected = if self.modal_merge_selected + 1 > branches.len() as i32 - 1 {
+                    0
+                } else {
+                    self.modal_merge_selected + 1
+                };
+            }
+            Focus::ModalRevert => {
+                let parents = self.get_selected_parent_count();
+                self.modal_revert_selected = if self.modal_revert_selected + 1 > parents - 1 {
+                    0
+                } else {
+                    self.modal_revert_selected + 1
+                };
+            }
             Focus::ModalDeleteBranch => {
                 let alias = self.oids.get_alias_by_idx(self.graph_selected);
                 let branches = self.branches.visible.entry(alias).or_default();
@@ -777,19 +1771,28 @@ impl App {
 
     pub fn on_scroll_up_half(&mut self) {
         match self.focus {
-            Focus::Viewport => if self.viewport == Viewport::Graph {
+            Focus::Viewport if self.viewport == Viewport::Graph => {
                 self.graph_selected /= 2;
             },
             Focus::Branches => {
                 self.branches_selected /= 2
             },
+            Focus::Stashes => {
+                self.stashes_selected /= 2
+            },
+            Focus::Tags => {
+                self.tags_selected /= 2
+            },
+            Focus::Remotes => {
+                self.remotes_selected /= 2
+            },
             _ => {}
         };
     }
 
     pub fn on_scroll_down_half(&mut self) {
         match self.focus {
-            Focus::Viewport => if self.viewport == Viewport::Graph {
+            Focus::Viewport if self.viewport == Viewport::Graph => {
                 self.graph_selected = (self.oids.get_commit_count() - 1)
                     .min(self.graph_selected + (self.oids.get_commit_count() - self.graph_selected) / 2);
             },
@@ -797,6 +1800,18 @@ impl App {
                 let total = self.branches.sorted.len();
                 self.branches_selected = self.branches_selected + (total - self.branches_selected) / 2
             },
+            Focus::Stashes => {
+                let total = self.stashes.sorted.len();
+                self.stashes_selected = self.stashes_selected + (total - self.stashes_selected) / 2
+            },
+            Focus::Tags => {
+                let total = self.tags.sorted.len();
+                self.tags_selected = self.tags_selected + (total - self.tags_selected) / 2
+            },
+            Focus::Remotes => {
+                let total = self.remotes.len();
+                self.remotes_selected = self.remotes_selected + (total - self.remotes_selected) / 2
+            },
             _ => {}
         };
     }
@@ -862,18 +1877,21 @@ impl App {
                 return;
             }
 
-            let commit = self.repo.find_commit(*oid).unwrap();
-            let mut parents = commit.parent_ids();
+            let parent_oid = self.repo.find_commit(*oid).map(|commit| commit.parent_ids().next());
+            let parent_oid = match parent_oid {
+                Ok(parent_oid) => parent_oid,
+                Err(e) => {
+                    self.notify(Severity::Error, format!("cannot read #{:.6}: {}", oid, e.message()));
+                    return;
+                }
+            };
 
-            if parents.len() == 0 {
-            } else {
-                let parent_oid = parents.next().unwrap();
+            // The parent may not be loaded yet, or hidden by the branch filter
+            if let Some(parent_oid) = parent_oid {
                 let parent_alias = self.oids.get_alias_by_oid(parent_oid);
-                let next = self.oids.get_sorted_aliases()
-                    .iter()
-                    .position(|&alias| alias == parent_alias)
-                    .unwrap();
-                self.graph_selected = next;
+                if let Some(next) = self.oids.get_sorted_aliases().iter().position(|&alias| alias == parent_alias) {
+                    self.graph_selected = next;
+                }
             }
         }
     }
@@ -883,6 +1901,15 @@ impl App {
             Focus::Branches => {
                 self.branches_selected = 0;
             }
+            Focus::Stashes => {
+                self.stashes_selected = 0;
+            }
+            Focus::Tags => {
+                self.tags_selected = 0;
+            }
+            Focus::Remotes => {
+                self.remotes_selected = 0;
+            }
             Focus::Viewport => match self.viewport {
                 Viewport::Graph => {
                     self.graph_selected = 0;
@@ -893,6 +1920,12 @@ impl App {
                 Viewport::Settings => {
                     self.settings_selected = 0;
                 }
+                Viewport::Rebase => {
+                    self.rebase_selected = 0;
+                }
+                Viewport::Log => {
+                    self.log_selected = 0;
+                }
                 _ => {}
             },
             Focus::Inspector => {
@@ -913,6 +1946,15 @@ impl App {
             Focus::Branches => {
                 self.branches_selected = usize::MAX;
             }
+            Focus::Stashes => {
+                self.stashes_selected = usize::MAX;
+            }
+            Focus::Tags => {
+                self.tags_selected = usize::MAX;
+            }
+            Focus::Remotes => {
+                self.remotes_selected = usize::MAX;
+            }
             Focus::Viewport => match self.viewport {
                 Viewport::Graph => {
                     self.graph_selected = usize::MAX;
@@ -923,6 +1965,12 @@ impl App {
                 Viewport::Settings => {
                     self.settings_selected = usize::MAX;
                 }
+                Viewport::Rebase => {
+                    self.rebase_selected = usize::MAX;
+                }
+                Viewport::Log => {
+                    self.log_selected = usize::MAX;
+                }
                 _ => {}
             },
             Focus::Inspector => {
@@ -938,18 +1986,132 @@ impl App {
         };
     }
 
+    pub fn on_mark(&mut self) {
+        // In the viewer, start or drop a range of lines to stage
+        if self.focus == Focus::Viewport && self.viewport == Viewport::Viewer && matches!(self.viewer_source, ViewerSource::Staged | ViewerSource::Unstaged) {
+            self.viewer_anchor = match self.viewer_anchor {
+                Some(_) => None,
+                None => Some(self.viewer_selected),
+            };
+            return;
+        }
+        if self.focus == Focus::Viewport && self.viewport == Viewport::Graph && self.graph_selected != 0 {
+            let oid = *self.oids.get_oid_by_idx(self.graph_selected);
+            if let Some(pos) = self.graph_marked.iter().position(|marked| *marked == oid) {
+                self.graph_marked.remove(pos);
+            } else {
+                self.graph_marked.push(oid);
+            }
+        }
+    }
+
+    pub fn on_search(&mut self) {
+        if self.focus == Focus::Viewport && self.viewport == Viewport::Graph {
+            self.search = None;
+            self.search_input.clear();
+            self.search_origin = self.graph_selected;
+            self.focus = Focus::Search;
+        }
+    }
+
+    pub fn on_next_match(&mut self) {
+        if self.focus == Focus::Viewport && self.viewport == Viewport::Graph {
+            self.select_match(self.graph_selected + 1, Direction::Down);
+        }
+    }
+
+    pub fn on_previous_match(&mut self) {
+        if self.focus == Focus::Viewport && self.viewport == Viewport::Graph && self.graph_selected > 0 {
+            self.select_match(self.graph_selected - 1, Direction::Up);
+        }
+    }
+
+    // Look for the query again from the start, in graph order beginning where the search started
+    pub fn restart_search(&mut self) {
+        if self.search_input.is_empty() {
+            self.search = None;
+            return;
+        }
+        let aliases = self.oids.get_sorted_aliases();
+        let origin = self.search_origin.min(aliases.len());
+        let candidates = aliases[origin..]
+            .iter()
+            .chain(aliases[..origin].iter())
+            .filter(|&&alias| alias != NONE)
+            .map(|&alias| (alias, *self.oids.get_oid_by_alias(alias)))
+            .collect();
+        self.search = Some(Search::start(self.path.clone(), candidates, self.search_input.clone(), self.search_pickaxe));
+    }
+
+    // Select the closest match from a row on, wrapping around the graph
+    pub fn select_match(&mut self, from: usize, direction: Direction) {
+        let Some(search) = self.search.as_ref() else {
+            return;
+        };
+        let aliases = self.oids.get_sorted_aliases();
+        let count = aliases.len();
+        if count == 0 {
+            return;
+        }
+        let found = (0..count)
+            .map(|step| match direction {
+                Direction::Down => (from + step) % count,
+                Direction::Up => (from + count - step) % count,
+            })
+            .find(|&idx| search.matches.contains(&aliases[idx]));
+
+        if let Some(idx) = found && idx != self.graph_selected {
+            self.graph_selected = idx;
+            if self.focus != Focus::Search {
+                let oid = self.oids.get_oid_by_idx(idx);
+                self.load_current_diff(*oid);
+            }
+        }
+    }
+
     pub fn on_jump_to_branch(&mut self) {
-        if self.focus == Focus::Branches {
-            self.viewport = Viewport::Graph;
-            let oidi = self.branches.sorted.get(self.branches_selected).unwrap().0;
-            self.graph_selected = self.oids.get_sorted_aliases().iter().position(|o| o == &oidi).unwrap_or(0);
+        match self.focus {
+            Focus::Branches => {
+                self.viewport = Viewport::Graph;
+                let Some(&(oidi, _)) = self.branches.sorted.get(self.branches_selected) else {
+                    return;
+                };
+                self.graph_selected = self.oids.get_sorted_aliases().iter().position(|o| o == &oidi).unwrap_or(0);
+            }
+            Focus::Stashes => self.on_jump_to_stash(),
+            Focus::Tags => self.on_jump_to_tag(),
+            _ => {}
         };
     }
+
+    pub fn on_jump_to_tag(&mut self) {
+        if let Some((oidi, _)) = self.tags.sorted.get(self.tags_selected) {
+            self.viewport = Viewport::Graph;
+            self.graph_selected = self.oids.get_sorted_aliases().iter().position(|o| o == oidi).unwrap_or(0);
+            if self.graph_selected != 0 {
+                let oid = self.oids.get_oid_by_idx(self.graph_selected);
+                self.load_current_diff(*oid);
+            }
+        }
+    }
+
+    pub fn on_jump_to_stash(&mut self) {
+        if let Some((oidi, _)) = self.stashes.sorted.get(self.stashes_selected) {
+            self.viewport = Viewport::Graph;
+            self.graph_selected = self.oids.get_sorted_aliases().iter().position(|o| o == oidi).unwrap_or(0);
+            if self.graph_selected != 0 {
+                let oid = self.oids.get_oid_by_idx(self.graph_selected);
+                self.load_current_diff(*oid);
+            }
+        }
+    }
     
     pub fn on_solo_branch(&mut self) {
         match self.focus {
             Focus::Branches => {
-                let (oid, branch) = self.branches.sorted.get(self.branches_selected).unwrap();
+                let Some((oid, branch)) = self.branches.sorted.get(self.branches_selected) else {
+                    return;
+                };
 
                 // Check if the same branch is already the only one visible
                 let already_visible = 
@@ -984,8 +2146,7 @@ impl App {
                 if branches.is_empty() {
                     return;
                 }
-                if branches.len() == 1 {
-                    let branch = branches.first().unwrap();
+                if let [branch] = branches.as_slice() {
                     if self.branches.visible.len() == 1 && self.branches.visible.entry(alias).or_default().len() == 1 && self.branches.visible.entry(alias).or_default().contains(branch) {
                         self.branches.visible.clear();
                     } else {
@@ -1005,17 +2166,53 @@ impl App {
 
     pub fn on_fetch(&mut self) {
         if self.viewport != Viewport::Settings {
-            let handle = fetch_over_ssh(&self.path, "origin");
-            match handle.join().expect("Thread panicked") {
-                Ok(_) => {
-                    self.branches.visible.clear();
-                    self.reload();
+            let remote = match self.get_selected_remote(get_fetch_remote) {
+                Ok(remote) => remote,
+                Err(e) => {
+                    self.notify(Severity::Error, format!("fetch failed: {}", e.message()));
+                    return;
                 }
-                Err(e) => eprintln!("Fetch failed: {}", e),
+            };
+            self.fetch(vec![remote]);
+        }
+    }
+
+    // Fetches every configured remote
+    pub fn on_fetch_all(&mut self) {
+        if self.viewport != Viewport::Settings {
+            if self.remotes.is_empty() {
+                self.notify(Severity::Warning, "no remotes to fetch".to_string());
+                return;
             }
+            self.fetch(self.remotes.iter().map(|remote| remote.name.clone()).collect());
         }
     }
 
+    pub fn fetch(&mut self, remotes: Vec<String>) {
+        if self.is_network_busy() {
+            return;
+        }
+
+        // Remember the refs to tell what the fetch changed
+        let before = get_fetched_refs(&self.repo).unwrap_or_default();
+        let label = match remotes.as_slice() {
+            [remote] => format!("fetching {}", remote),
+            _ => format!("fetching {} remotes", remotes.len()),
+        };
+
+        let (progress, rx) = Progress::new();
+        let handle = fetch_over_ssh(&self.path, remotes.clone(), self.fetch_prune, self.fetch_tags, &self.credentials, &progress);
+        self.track_network(label, progress, rx, handle, move |app, result| match result {
+            Ok(_) => {
+                let after = get_fetched_refs(&app.repo).unwrap_or_default();
+                app.notify(Severity::Info, format!("fetched {}: {}", remotes.join(", "), get_fetch_summary(&before, &after).describe()));
+                app.branches.visible.clear();
+                app.reload();
+            }
+            Err(e) => app.on_network_error("fetch", e, move |app| app.fetch(remotes)),
+        });
+    }
+
     pub fn on_checkout(&mut self) {
         if self.focus == Focus::Viewport {
             if self.focus == Focus::Viewport && self.viewport != Viewport::Graph {
@@ -1031,26 +2228,29 @@ impl App {
             let oid = self.oids.get_oid_by_alias(alias);
             let branches = self.branches.all.entry(alias).or_default();
 
-            if branches.is_empty() {
-                checkout_head(&self.repo, *oid);
-                self.focus = Focus::Viewport;
-                self.branches.visible.clear();
-                self.reload();
-            } else if branches.len() == 1 {
-                checkout_branch(
-                    &self.repo,
-                    &mut self.branches.visible,
-                    &mut self.branches.local,
-                    alias,
-                    branches.first().unwrap(),
-                )
-                .expect("Error");
-                self.focus = Focus::Viewport;
-                self.branches.visible.clear();
-                self.reload();
-            } else {
-                self.focus = Focus::ModalCheckout;
+            let result = match branches.as_slice() {
+                [] => checkout_head(&self.repo, *oid),
+                [branch] => {
+                    let branch = branch.clone();
+                    checkout_branch(
+                        &self.repo,
+                        &mut self.branches.visible,
+                        &mut self.branches.local,
+                        alias,
+                        &branch,
+                    )
+                }
+                _ => {
+                    self.focus = Focus::ModalCheckout;
+                    return;
+                }
+            };
+            self.focus = Focus::Viewport;
+            if let Err(e) = result {
+                self.notify(Severity::Error, format!("checkout failed: {}", e.message()));
             }
+            self.branches.visible.clear();
+            self.reload();
         }
     }
 
@@ -1059,12 +2259,57 @@ impl App {
             if self.focus == Focus::Viewport && self.viewport != Viewport::Graph {
                 return;
             }
-            let oid = self.oids.get_oid_by_idx(self.graph_selected);
-            reset_to_commit(&self.repo, *oid, git2::ResetType::Hard).expect("Error");
-            self.branches.visible.clear();
-            self.reload();
-            self.focus = Focus::Viewport;
+            let oid = *self.oids.get_oid_by_idx(self.graph_selected);
+            if !self.confirm_hard_reset {
+                self.apply_hard_reset(oid);
+                return;
+            }
+
+            // Tracked files only, a hard reset leaves untracked ones alone
+            let staged = &self.uncommitted.staged;
+            let unstaged = &self.uncommitted.unstaged;
+            let files: HashSet<&String> = staged.modified.iter()
+                .chain(&staged.added)
+                .chain(&staged.deleted)
+                .chain(&unstaged.modified)
+                .chain(&unstaged.deleted)
+                .chain(&self.uncommitted.conflicted)
+                .collect();
+
+            // Commits only the moved branch kept
+            let current = get_current_branch(&self.repo);
+            let head = self.repo.head().ok().and_then(|head| head.target());
+            let left_behind = head
+                .map(|head| {
+                    get_branch_tips(&self.repo, current.as_deref())
+                        .and_then(|mut others| {
+                            others.push(oid);
+                            count_unique_commits(&self.repo, head, &others)
+                        })
+                        .unwrap_or(0)
+                })
+                .unwrap_or(0);
+
+            let mut lines = vec![format!("hard reset {} to #{:.6}?", current.unwrap_or("HEAD".to_string()), oid)];
+            if files.is_empty() {
+                lines.push("there are no uncommitted changes to lose".to_string());
+            } else {
+                lines.push(format!("uncommitted changes to {} files will be lost", files.len()));
+            }
+            if left_behind > 0 {
+                lines.push(format!("{} commits on no other branch will be left behind", left_behind));
+            }
+            self.open_confirm(ConfirmAction::HardReset(oid), lines);
+        }
+    }
+
+    pub fn apply_hard_reset(&mut self, oid: Oid) {
+        if let Err(e) = reset_to_commit(&self.repo, oid, git2::ResetType::Hard) {
+            self.notify(Severity::Error, format!("reset failed: {}", e.message()));
         }
+        self.branches.visible.clear();
+        self.reload();
+        self.focus = Focus::Viewport;
     }
 
     pub fn on_mixed_reset(&mut self) {
@@ -1073,7 +2318,9 @@ impl App {
                 return;
             }
             let oid = self.oids.get_oid_by_idx(self.graph_selected);
-            reset_to_commit(&self.repo, *oid, git2::ResetType::Mixed).expect("Error");
+            if let Err(e) = reset_to_commit(&self.repo, *oid, git2::ResetType::Mixed) {
+                self.notify(Severity::Error, format!("reset failed: {}", e.message()));
+            }
             self.branches.visible.clear();
             self.reload();
             self.focus = Focus::Viewport;
@@ -1082,10 +2329,12 @@ impl App {
 
     pub fn on_unstage_all(&mut self) {
         match self.viewport {
-            Viewport::Settings | Viewport::Viewer | Viewport::Editor => {}
+            Viewport::Settings | Viewport::Log | Viewport::Viewer | Viewport::Editor => {}
             _ => {
                 if self.uncommitted.is_staged {
-                    unstage_all(&self.repo).expect("Error");
+                    if let Err(e) = unstage_all(&self.repo) {
+                        self.notify(Severity::Error, format!("unstage failed: {}", e.message()));
+                    }
                     self.reload();
                 }
             }
@@ -1094,112 +2343,1669 @@ impl App {
 
     pub fn on_stage_all(&mut self) {
         match self.viewport {
-            Viewport::Settings | Viewport::Viewer | Viewport::Editor => {}
+            Viewport::Settings | Viewport::Log | Viewport::Viewer | Viewport::Editor => {}
             _ => {
                 if self.uncommitted.is_unstaged {
-                    git_add_all(&self.repo).expect("Error");
+                    if let Err(e) = git_add_all(&self.repo) {
+                        self.notify(Severity::Error, format!("stage failed: {}", e.message()));
+                    }
                     self.reload();
                 }
             }
         }
     }
 
-    pub fn on_commit(&mut self) {
-        match self.viewport {
-            Viewport::Settings | Viewport::Viewer | Viewport::Editor => {}
-            _ => {
-                if self.uncommitted.is_staged {
-                    self.focus = Focus::ModalCommit;
-                    self.commit_editor.mode = EditorMode::Insert;
+    pub fn on_stage(&mut self) {
+        match self.focus {
+            Focus::Viewport if self.viewport == Viewport::Viewer => {
+                if self.viewer_source != ViewerSource::Unstaged {
+                    self.notify(Severity::Warning, "open an unstaged file to stage lines".to_string());
+                    return;
                 }
+                self.apply_viewer_selection(stage_lines, "stage", "staged");
             }
-        }
-    }
+            Focus::StatusBottom if self.graph_selected == 0 => {
+                let conflicted_len = self.uncommitted.conflicted.len();
 
-    pub fn on_push(&mut self) {
-        match self.viewport {
-            Viewport::Settings | Viewport::Viewer | Viewport::Editor => {}
-            _ => {
-                let handle = push_over_ssh(
-                    &self.path,
-                    "origin",
-                    get_current_branch(&self.repo).unwrap().as_str(),
-                    true,
-                );
-                match handle.join().expect("Thread panicked") {
-                    Ok(_) => {
-                        self.branches.visible.clear();
-                        self.reload();
-                    }
-                    Err(e) => eprintln!("Fetch failed: {}", e),
+                // Staging a conflicted file marks it as resolved
+                let path = if self.status_bottom_selected < conflicted_len {
+                    self.uncommitted.conflicted.get(self.status_bottom_selected).cloned()
+                } else {
+                    self.uncommitted.get_unstaged_file(self.status_bottom_selected - conflicted_len).map(|file| file.filename)
+                };
+                let Some(path) = path else {
+                    return;
+                };
+                match stage_file(&self.repo, &path) {
+                    Ok(_) => self.notify(Severity::Info, format!("staged {}", path)),
+                    Err(e) => self.notify(Severity::Error, format!("cannot stage {}: {}", path, e.message())),
                 }
+                self.refresh_uncommitted();
             }
+            _ => {}
         }
     }
 
-    pub fn on_create_branch(&mut self) {
-        match self.viewport {
-            Viewport::Settings | Viewport::Viewer | Viewport::Editor => {}
-            _ => {
-                if self.graph_selected != 0 {
-                    self.focus = Focus::ModalCreateBranch;
-                    self.create_branch_editor.mode = EditorMode::Insert;
+    pub fn on_unstage(&mut self) {
+        match self.focus {
+            Focus::Viewport if self.viewport == Viewport::Viewer => {
+                if self.viewer_source != ViewerSource::Staged {
+                    self.notify(Severity::Warning, "open a staged file to unstage lines".to_string());
+                    return;
+                }
+                self.apply_viewer_selection(unstage_lines, "unstage", "unstaged");
+            }
+            Focus::StatusTop if self.graph_selected == 0 => {
+                let Some(file) = self.uncommitted.get_staged_file(self.status_top_selected) else {
+                    return;
+                };
+                match unstage_file(&self.repo, &file.filename) {
+                    Ok(_) => self.notify(Severity::Info, format!("unstaged {}", file.filename)),
+                    Err(e) => self.notify(Severity::Error, format!("cannot unstage {}: {}", file.filename, e.message())),
                 }
+                self.refresh_uncommitted();
             }
+            _ => {}
         }
     }
 
+    pub fn on_discard(&mut self) {
+        match self.focus {
+            Focus::Viewport if self.viewport == Viewport::Viewer => {
+                if self.viewer_source != ViewerSource::Unstaged {
+                    self.notify(Severity::Warning, "open an unstaged file to discard lines".to_string());
+                    return;
+                }
+                self.apply_viewer_selection(discard_lines, "discard", "discarded");
+            }
+            Focus::StatusBottom if self.graph_selected == 0 => {
+                let conflicted_len = self.uncommitted.conflicted.len();
+                if self.status_bottom_selected < conflicted_len {
+                    self.notify(Severity::Warning, "resolve conflicted files in the conflict view".to_string());
+                    return;
+                }
+                let Some(file) = self.uncommitted.get_unstaged_file(self.status_bottom_selected - conflicted_len) else {
+                    return;
+                };
+
+                // Spell out what is about to be lost
+                match file.status {
+                    FileStatus::Added => {
+                        let lines = vec![
+                            format!("delete untracked {}?", file.filename),
+                            "it was never committed and cannot be recovered".to_string(),
+                        ];
+                        self.open_confirm(ConfirmAction::DeleteUntracked(file.filename), lines);
+                    }
+                    _ => {
+                        let hunks = get_file_diff_unstaged(&self.repo, &file.filename).unwrap_or_default();
+                        let lines = hunks.iter().flat_map(|hunk| hunk.lines.iter());
+                        let (added, removed) = lines.fold((0, 0), |(added, removed), line| match line.origin {
+                            '+' => (added + 1, removed),
+                            '-' => (added, removed + 1),
+                            _ => (added, removed),
+                        });
+                        let lines = vec![
+                            format!("discard changes to {}?", file.filename),
+                            format!("{} added and {} removed lines will be lost", added, removed),
+                        ];
+                        self.open_confirm(ConfirmAction::DiscardFile(file.filename), lines);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Asks before a destructive action, the first line is the question and the rest what will be lost
+    pub fn open_confirm(&mut self, action: ConfirmAction, lines: Vec<String>) {
+        self.confirm_action = Some(action);
+        self.confirm_lines = lines;
+        self.confirm_focus = std::mem::replace(&mut self.focus, Focus::ModalConfirm);
+    }
+
+    // Runs the action the confirmation modal was opened for
+    pub fn on_confirm(&mut self) {
+        let Some(action) = self.confirm_action.take() else {
+            return;
+        };
+        self.focus = std::mem::replace(&mut self.confirm_focus, Focus::Viewport);
+        match action {
+            ConfirmAction::DiscardFile(path) => {
+                match discard_file(&self.repo, &path) {
+                    Ok(_) => self.notify(Severity::Info, format!("discarded changes to {}", path)),
+                    Err(e) => self.notify(Severity::Error, format!("cannot discard {}: {}", path, e.message())),
+                }
+                self.refresh_uncommitted();
+            }
+            ConfirmAction::DeleteUntracked(path) => {
+                match delete_untracked(&self.repo, &path) {
+                    Ok(_) => self.notify(Severity::Info, format!("deleted {}", path)),
+                    Err(e) => self.notify(Severity::Error, format!("cannot delete {}: {}", path, e.message())),
+                }
+                self.refresh_uncommitted();
+            }
+            ConfirmAction::RemoveRemote(name) => {
+                match remove_remote(&self.repo, &name) {
+                    Ok(_) => self.notify(Severity::Info, format!("removed remote {}", name)),
+                    Err(e) => self.notify(Severity::Error, format!("cannot remove {}: {}", name, e.message())),
+                }
+                self.branches.visible.clear();
+                self.reload();
+            }
+            ConfirmAction::HardReset(oid) => self.apply_hard_reset(oid),
+            ConfirmAction::DeleteBranch(branch) => self.apply_delete_branch(branch),
+            ConfirmAction::ForcePush(remote, branch) => self.push(remote, branch, PushMode::ForceWithLease),
+            ConfirmAction::DropStash(index) => self.apply_drop_stash(index),
+            ConfirmAction::DeleteTag(tag) => self.apply_delete_tag(tag),
+            ConfirmAction::StageConflicted(path) => self.apply_stage_conflict_file(path),
+            ConfirmAction::RenameRemoteBranch(new_name, tracking) => self.rename_remote_tracking(new_name, tracking),
+        }
+    }
+
+    pub fn on_cancel_confirm(&mut self) {
+        self.confirm_action = None;
+        self.focus = std::mem::replace(&mut self.confirm_focus, Focus::Viewport);
+    }
+
+    // Operations aborted with a hard reset only start when there are no local changes to lose
+    pub fn is_worktree_clean(&mut self) -> bool {
+        match has_tracked_changes(&self.repo) {
+            Ok(false) => true,
+            Ok(true) => {
+                self.notify(Severity::Warning, "commit or stash your changes first".to_string());
+                false
+            }
+            Err(e) => {
+                self.notify(Severity::Error, format!("cannot read the status: {}", e.message()));
+                false
+            }
+        }
+    }
+
+    // Updates the uncommitted changes without walking the graph again
+    pub fn refresh_uncommitted(&mut self) {
+        match get_filenames_diff_at_workdir(&self.repo) {
+            Ok(uncommitted) => self.uncommitted = uncommitted,
+            Err(e) => self.notify(Severity::Error, format!("cannot read the status: {}", e.message())),
+        }
+    }
+
+    // Applies a line action to the marked lines, or to the hunk under the cursor
+    pub fn apply_viewer_selection(&mut self, action: LineAction, verb: &str, done: &str) {
+        let Some(filename) = self.file_name.clone() else {
+            return;
+        };
+
+        let selection: HashSet<(usize, usize)> = match self.viewer_anchor {
+            Some(anchor) => {
+                let (from, to) = (anchor.min(self.viewer_selected), anchor.max(self.viewer_selected));
+                self.viewer_hunk_lines.iter().skip(from).take(to - from + 1).flatten().copied().collect()
+            }
+            None => match self.viewer_hunk_lines.get(self.viewer_selected).copied().flatten() {
+                Some((hunk, _)) => self.viewer_hunk_lines.iter().flatten().filter(|(idx, _)| *idx == hunk).copied().collect(),
+                None => HashSet::new(),
+            },
+        };
+        if selection.is_empty() {
+            self.notify(Severity::Warning, "move to a hunk, or mark lines with space".to_string());
+            return;
+        }
+
+        match action(&self.repo, &filename, &selection) {
+            Ok(_) => {
+                self.notify(Severity::Info, format!("{} {} of {}", done, if self.viewer_anchor.is_some() { "lines" } else { "hunk" }, filename));
+                self.viewer_anchor = None;
+                self.reload();
+                self.update_viewer(Oid::zero());
+            }
+            Err(e) => {
+                self.notify(Severity::Error, format!("cannot {}: {}", verb, e.message()));
+            }
+        }
+    }
+
+    pub fn on_commit(&mut self) {
+        match self.viewport {
+            Viewport::Settings | Viewport::Log | Viewport::Viewer | Viewport::Editor => {}
+            _ => {
+                if self.uncommitted.is_staged {
+                    self.focus = Focus::ModalCommit;
+                    self.commit_editor.mode = EditorMode::Insert;
+                }
+            }
+        }
+    }
+
+    pub fn on_amend(&mut self) {
+        match self.viewport {
+            Viewport::Settings | Viewport::Log | Viewport::Viewer | Viewport::Editor | Viewport::Rebase => {}
+            _ => {
+                if self.rebase_session.is_some() || self.repo.state() != RepositoryState::Clean {
+                    self.notify(Severity::Warning, "finish or abort the operation in progress first".to_string());
+                    return;
+                }
+                let message = self.repo.head()
+                    .and_then(|head| head.peel_to_commit())
+                    .map(|head| head.message().unwrap_or_default().to_string());
+                match message {
+                    Ok(message) => {
+                        self.open_commit_modal(CommitKind::Amend, message.trim_end());
+                    }
+                    Err(_) => {
+                        self.notify(Severity::Warning, "nothing to amend yet".to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn on_reword(&mut self) {
+        match self.viewport {
+            Viewport::Settings | Viewport::Log | Viewport::Viewer | Viewport::Editor | Viewport::Rebase => {}
+            _ => {
+                if self.focus != Focus::Viewport || self.graph_selected == 0 {
+                    return;
+                }
+                if self.rebase_session.is_some() || self.repo.state() != RepositoryState::Clean {
+                    self.notify(Severity::Warning, "finish or abort the operation in progress first".to_string());
+                    return;
+                }
+                let oid = *self.oids.get_oid_by_idx(self.graph_selected);
+                let message = self.repo.find_commit(oid).map(|commit| commit.message().unwrap_or_default().to_string());
+                match message {
+                    Ok(message) => {
+                        self.open_commit_modal(CommitKind::Reword(oid), message.trim_end());
+                    }
+                    Err(e) => {
+                        self.notify(Severity::Error, format!("reword failed: {}", e.message()));
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn on_push(&mut self, mode: PushMode) {
+        match self.viewport {
+            Viewport::Settings | Viewport::Log | Viewport::Viewer | Viewport::Editor => {}
+            _ => {
+                let Some(branch) = get_current_branch(&self.repo) else {
+                    self.notify(Severity::Warning, "detached head, check out a branch to push".to_string());
+                    return;
+                };
+                let remote = match self.get_selected_remote(get_push_remote) {
+                    Ok(remote) => remote,
+                    Err(e) => {
+                        self.notify(Severity::Error, format!("push failed: {}", e.message()));
+                        return;
+                    }
+                };
+                if mode == PushMode::ForceWithLease && self.confirm_force_push {
+                    let lines = self.describe_force_push(&remote, &branch);
+                    self.open_confirm(ConfirmAction::ForcePush(remote, branch), lines);
+                    return;
+                }
+                self.push(remote, branch, mode);
+            }
+        }
+    }
+
+    // Spells out what a force push replaces on the remote, as last fetched
+    fn describe_force_push(&self, remote: &str, branch: &str) -> Vec<String> {
+        let refname = format!("refs/heads/{}", branch);
+        let upstream_remote = self.repo.branch_upstream_remote(&refname).ok().and_then(|name| name.as_str().map(|name| name.to_string()));
+        let tracking = match self.repo.branch_upstream_name(&refname) {
+            Ok(tracking) if upstream_remote.as_deref() == Some(remote) => tracking.as_str().unwrap_or_default().to_string(),
+            _ => format!("refs/remotes/{}/{}", remote, branch),
+        };
+        let short = tracking.trim_start_matches("refs/remotes/").to_string();
+
+        let mut lines = vec![format!("force push {} to {}?", branch, remote)];
+        match (self.repo.refname_to_id(&tracking), self.repo.refname_to_id(&refname)) {
+            (Ok(theirs), Ok(ours)) => {
+                let overwritten = count_unique_commits(&self.repo, theirs, &[ours]).unwrap_or(0);
+                if overwritten > 0 {
+                    lines.push(format!("{} commits on {} will be overwritten", overwritten, short));
+                } else {
+                    lines.push(format!("{} is already part of {}, nothing is overwritten", short, branch));
+                }
+            }
+            _ => lines.push(format!("{} has no {} yet, nothing is overwritten", remote, branch)),
+        }
+        lines.push(format!("the push is refused if {} moved on since the last fetch", short));
+        lines
+    }
+
+    pub fn push(&mut self, remote: String, branch: String, mode: PushMode) {
+        if self.is_network_busy() {
+            return;
+        }
+
+        // A branch without an upstream starts tracking what it is pushed to
+        let is_new = self.repo.branch_upstream_remote(&format!("refs/heads/{}", branch)).is_err();
+        let label = match mode {
+            PushMode::Normal => format!("pushing {} to {}", branch, remote),
+            PushMode::ForceWithLease => format!("force pushing {} to {}", branch, remote),
+        };
+
+        let (progress, rx) = Progress::new();
+        let handle = push_over_ssh(&self.path, &remote, &branch, mode, &self.credentials, &progress);
+        self.track_network(label, progress, rx, handle, move |app, result| match result {
+            Ok(_) => {
+                let tracking = app.repo.branch_upstream_name(&format!("refs/heads/{}", branch)).ok()
+                    .and_then(|tracking| tracking.as_str().map(|tracking| tracking.trim_start_matches("refs/remotes/").to_string()));
+                match tracking {
+                    Some(tracking) if is_new => app.notify(Severity::Info, format!("pushed {} to {}, now tracking {}", branch, remote, tracking)),
+                    _ => app.notify(Severity::Info, format!("pushed {} to {}", branch, remote)),
+                }
+                app.branches.visible.clear();
+                app.reload();
+            }
+            Err(e) => app.on_network_error("push", e, move |app| app.push(remote, branch, mode)),
+        });
+    }
+
+    pub fn on_create_branch(&mut self) {
+        match self.viewport {
+            Viewport::Settings | Viewport::Log | Viewport::Viewer | Viewport::Editor => {}
+            _ => {
+                if self.graph_selected != 0 {
+                    self.focus = Focus::ModalCreateBranch;
+                    self.create_branch_editor.mode = EditorMode::Insert;
+                }
+            }
+        }
+    }
+
+    pub fn on_rename_branch(&mut self) {
+        match self.viewport {
+            Viewport::Settings | Viewport::Log | Viewport::Viewer | Viewport::Editor => {}
+            _ => {
+                let branch = match self.focus {
+                    Focus::Remotes => {
+                        if let Some(remote) = self.remotes.get(self.remotes_selected) {
+                            let name = remote.name.clone();
+                            self.open_remote_modal(RemoteAction::Rename(name.clone()), &name);
+                        }
+                        return;
+                    }
+                    Focus::Branches => {
+                        let Some((_, branch)) = self.branches.sorted.get(self.branches_selected).cloned() else {
+                            return;
+                        };
+                        if !self.branches.local.values().any(|branches| branches.contains(&branch)) {
+                            self.notify(Severity::Warning, "only local branches can be renamed".to_string());
+                            return;
+                        }
+                        branch
+                    }
+                    Focus::Viewport if self.graph_selected != 0 => {
+                        let alias = self.oids.get_alias_by_idx(self.graph_selected);
+                        let branches = self.branches.local.get(&alias).cloned().unwrap_or_default();
+                        let current = get_current_branch(&self.repo);
+                        match branches.len() {
+                            0 => {
+                                self.notify(Severity::Warning, "no local branch to rename here".to_string());
+                                return;
+                            }
+                            1 => branches[0].clone(),
+                            // Prefer the checked out branch, otherwise ask to pick one from the pane
+                            _ => match current.filter(|current| branches.contains(current)) {
+                                Some(current) => current,
+                                None => {
+                                    self.notify(Severity::Warning, "several branches here, rename from the branches pane".to_string());
+                                    return;
+                                }
+                            },
+                        }
+                    }
+                    _ => return,
+                };
+
+                // Renaming on the remote deletes the old branch there, so it has to be asked for
+                self.rename_branch_remote = false;
+                self.rename_branch_editor = EditorState::new(Lines::from(branch.as_str()));
+                self.rename_branch_editor.mode = EditorMode::Insert;
+                self.rename_branch_name = Some(branch);
+                self.focus = Focus::ModalRenameBranch;
+            }
+        }
+    }
+
+    pub fn rename_selected_branch(&mut self) {
+        let Some(old_name) = self.rename_branch_name.clone() else {
+            self.focus = Focus::Viewport;
+            return;
+        };
+        let new_name = editor_state_to_string(&self.rename_branch_editor).trim().to_string();
+        if new_name.is_empty() || new_name == old_name {
+            self.notify(Severity::Warning, "pick a different name".to_string());
+            return;
+        }
+
+        // Remember the remote tracking name, it changes along with the remote branch
+        let tracking = self.repo.branch_upstream_name(&format!("refs/heads/{}", old_name)).ok()
+            .and_then(|tracking| tracking.as_str().map(|tracking| tracking.trim_start_matches("refs/remotes/").to_string()));
+
+        if let Err(e) = rename_branch(&self.repo, &old_name, &new_name) {
+            self.notify(Severity::Error, format!("rename failed: {}", e.message()));
+            return;
+        }
+        self.notify(Severity::Info, format!("renamed {} to {}", old_name, new_name));
+        self.rename_visible_branch(&old_name, &new_name);
+
+        self.rename_branch_name = None;
+        self.rename_branch_editor = EditorState::default();
+        self.reload();
+        self.focus = Focus::Viewport;
+
+        if self.rename_branch_remote && let Some(tracking) = tracking {
+            let Some((remote, _)) = tracking.split_once('/') else {
+                return;
+            };
+            let lines = vec![
+                format!("rename {} on {} too?", tracking, remote),
+                format!("{} gets deleted from {}", tracking, remote),
+                "anyone tracking it loses their upstream".to_string(),
+            ];
+            self.open_confirm(ConfirmAction::RenameRemoteBranch(new_name, tracking), lines);
+        }
+    }
+
+    // Renames the branch on its remote, the tracking name follows
+    pub fn rename_remote_tracking(&mut self, new_name: String, tracking: String) {
+        if self.is_network_busy() {
+            return;
+        }
+        let (progress, rx) = Progress::new();
+        let handle = rename_remote_branch(&self.path, &new_name, &self.credentials, &progress);
+        self.track_network(format!("renaming {}", tracking), progress, rx, handle, move |app, result| match result {
+            Ok(_) => {
+                if let Some((remote, _)) = tracking.split_once('/') {
+                    let renamed = format!("{}/{}", remote, new_name);
+                    app.rename_visible_branch(&tracking, &renamed);
+                    app.notify(Severity::Info, format!("renamed {} to {}", tracking, renamed));
+                }
+                app.reload();
+            }
+            Err(e) => app.on_network_error("remote rename", e, move |app| app.rename_remote_tracking(new_name, tracking)),
+        });
+    }
+
+    // Keeps the graph filter pointing at the same branch
+    fn rename_visible_branch(&mut self, old_name: &str, new_name: &str) {
+        for branches in self.branches.visible.values_mut() {
+            for branch in branches.iter_mut() {
+                if branch == old_name {
+                    *branch = new_name.to_string();
+                }
+            }
+        }
+    }
+
+    pub fn on_create_tag(&mut self) {
+        match self.viewport {
+            Viewport::Settings | Viewport::Log | Viewport::Viewer | Viewport::Editor => {}
+            _ => {
+                if self.focus != Focus::Viewport || self.oids.get_commit_count() <= 1 {
+                    return;
+                }
+                let oid = *self.oids.get_oid_by_idx(if self.graph_selected == 0 { 1 } else { self.graph_selected });
+                self.tag_target = Some(oid);
+                self.tag_editor = EditorState::default();
+                self.tag_editor.mode = EditorMode::Insert;
+                self.focus = Focus::ModalCreateTag;
+            }
+        }
+    }
+
+    pub fn create_selected_tag(&mut self) {
+        let Some(target) = self.tag_target else {
+            self.focus = Focus::Viewport;
+            return;
+        };
+        let name = editor_state_to_string(&self.tag_editor).trim().to_string();
+        if name.is_empty() {
+            self.notify(Severity::Warning, "a tag needs a name".to_string());
+            return;
+        }
+
+        // Annotated tags ask for their message next
+        if self.tag_annotated {
+            self.tag_name = name;
+            self.open_commit_modal(CommitKind::Tag, "");
+            return;
+        }
+
+        match create_tag(&self.repo, &name, target, None, &self.name, &self.email) {
+            Ok(_) => {
+                self.notify(Severity::Info, format!("tagged #{:.6} as {}", target, name));
+                self.tag_target = None;
+                self.reload();
+                self.focus = Focus::Viewport;
+            }
+            Err(e) => {
+                self.notify(Severity::Error, format!("tag failed: {}", e.message()));
+            }
+        }
+    }
+
+    // Get the tag to act on, from the tags pane or the selected commit
+    pub fn get_selected_tag(&mut self) -> Option<String> {
+        match self.focus {
+            Focus::Tags => self.tags.sorted.get(self.tags_selected).map(|(_, tag)| tag.clone()),
+            Focus::Viewport if self.viewport == Viewport::Graph && self.graph_selected != 0 => {
+                let alias = self.oids.get_alias_by_idx(self.graph_selected);
+                let tags = self.tags.local.get(&alias).cloned().unwrap_or_default();
+                match tags.len() {
+                    0 => {
+                        self.notify(Severity::Warning, "no tag here".to_string());
+                        None
+                    }
+                    1 => Some(tags[0].clone()),
+                    _ => {
+                        self.notify(Severity::Warning, "several tags here, pick one from the tags pane".to_string());
+                        None
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+
+    pub fn on_delete_tag(&mut self) {
+        let Some(tag) = self.get_selected_tag() else {
+            return;
+        };
+        let target = self.repo.revparse_single(&format!("refs/tags/{}", tag)).ok()
+            .and_then(|object| object.peel_to_commit().ok())
+            .map(|commit| format!("on #{:.6} {}", commit.id(), commit.summary().unwrap_or("⊘ no summary")))
+            .unwrap_or_default();
+        let lines = vec![
+            format!("delete tag {}?", tag),
+            target,
+            "copies pushed to remotes are kept".to_string(),
+        ];
+        self.open_confirm(ConfirmAction::DeleteTag(tag), lines);
+    }
+
+    pub fn apply_delete_tag(&mut self, tag: String) {
+        match delete_tag(&self.repo, &tag) {
+            Ok(_) => {
+                self.notify(Severity::Info, format!("deleted tag {}", tag));
+                self.reload();
+            }
+            Err(e) => {
+                self.notify(Severity::Error, format!("delete failed: {}", e.message()));
+            }
+        }
+    }
+
+    pub fn on_push_tag(&mut self) {
+        if let Some(tag) = self.get_selected_tag() {
+            self.push_tags(vec![tag]);
+        }
+    }
+
+    pub fn on_push_all_tags(&mut self) {
+        match self.viewport {
+            Viewport::Settings | Viewport::Log | Viewport::Viewer | Viewport::Editor => {}
+            _ => match get_tag_names(&self.repo) {
+                Ok(tags) if tags.is_empty() => {
+                    self.notify(Severity::Warning, "no tags to push".to_string());
+                }
+                Ok(tags) => self.push_tags(tags),
+                Err(e) => {
+                    self.notify(Severity::Error, format!("push failed: {}", e.message()));
+                }
+            },
+        }
+    }
+
+    // Pushes tags to the selected or configured remote
+    pub fn push_tags(&mut self, tags: Vec<String>) {
+        let remote = match self.get_selected_remote(get_push_remote) {
+            Ok(remote) => remote,
+            Err(e) => {
+                self.notify(Severity::Error, format!("push failed: {}", e.message()));
+                return;
+            }
+        };
+        self.push_tags_to(remote, tags);
+    }
+
+    pub fn push_tags_to(&mut self, remote: String, tags: Vec<String>) {
+        if self.is_network_busy() {
+            return;
+        }
+        let count = tags.len();
+        let (progress, rx) = Progress::new();
+        let handle = push_tags(&self.path, &remote, tags.clone(), &self.credentials, &progress);
+        self.track_network(format!("pushing {} tag{} to {}", count, if count == 1 { "" } else { "s" }, remote), progress, rx, handle, move |app, result| match result {
+            Ok(_) => {
+                app.notify(Severity::Info, format!("pushed {} tag{} to {}", count, if count == 1 { "" } else { "s" }, remote));
+            }
+            Err(e) => app.on_network_error("push", e, move |app| app.push_tags_to(remote, tags)),
+        });
+    }
+
+    // Only one network thread runs at a time
+    pub fn is_network_busy(&mut self) -> bool {
+        if let Some(job) = &self.network {
+            self.notify(Severity::Warning, format!("wait for {} to finish or cancel it", job.label));
+            return true;
+        }
+        false
+    }
+
+    // Follows a network thread in the background, its result is handed to on_done from sync
+    pub fn track_network(
+        &mut self,
+        label: String,
+        progress: Progress,
+        rx: Receiver<String>,
+        handle: thread::JoinHandle<AppResult<()>>,
+        on_done: impl FnOnce(&mut App, AppResult<()>) + 'static,
+    ) {
+        self.hint = format!("{}, (ctrl+x) to cancel", label);
+        self.network = Some(NetworkJob {
+            label,
+            status: String::new(),
+            progress,
+            rx,
+            handle,
+            started: Instant::now(),
+            on_done: Box::new(on_done),
+        });
+    }
+
+    pub fn on_cancel_network(&mut self) {
+        if let Some(job) = &self.network {
+            job.progress.cancel();
+            self.hint = format!("cancelling {}", job.label);
+        }
+    }
+
+    // Asks for what the network action was missing and retries it, otherwise reports the failure
+    pub fn on_network_error(&mut self, action: &str, e: AppError, retry: impl FnOnce(&mut App) + 'static) {
+        match self.credentials.take_prompt() {
+            Some(prompt) => {
+                self.credentials_prompt = Some(prompt);
+                self.credentials_input.clear();
+                self.credentials_retry = Some(Box::new(retry));
+                self.credentials_focus = std::mem::replace(&mut self.focus, Focus::ModalCredentials);
+            }
+            None => {
+                self.notify(Severity::Error, format!("{} failed: {}", action, e.message()));
+            }
+        }
+    }
+
+    pub fn submit_credentials(&mut self) {
+        if let Some(prompt) = self.credentials_prompt.take() {
+            self.credentials.answer(&prompt, &self.credentials_input);
+        }
+        self.credentials_input.clear();
+        self.focus = std::mem::replace(&mut self.credentials_focus, Focus::Viewport);
+        if let Some(retry) = self.credentials_retry.take() {
+            retry(self);
+        }
+    }
+
+    pub fn cancel_credentials(&mut self) {
+
+        // A refused password may just as well have been a wrong username
+        if let Some(CredentialPrompt::Password(_, _)) = self.credentials_prompt.take() {
+            self.credentials.forget();
+        }
+        self.credentials_input.clear();
+        self.credentials_retry = None;
+        self.focus = std::mem::replace(&mut self.credentials_focus, Focus::Viewport);
+        self.notify(Severity::Warning, "authentication cancelled".to_string());
+    }
+
     pub fn on_delete_branch(&mut self) {
         match self.viewport {
-            Viewport::Settings | Viewport::Viewer | Viewport::Editor => {}
+            Viewport::Settings | Viewport::Log | Viewport::Viewer | Viewport::Editor => {}
+            _ => {
+                match self.focus {
+                    Focus::Remotes => {
+                        if let Some(remote) = self.remotes.get(self.remotes_selected) {
+                            let lines = vec![
+                                format!("remove remote {}?", remote.name),
+                                "its remote tracking branches and upstream settings go with it".to_string(),
+                            ];
+                            self.open_confirm(ConfirmAction::RemoveRemote(remote.name.clone()), lines);
+                        }
+                    }
+                    Focus::Branches => {
+                        let Some((_, branch)) = self.branches.sorted.get(self.branches_selected) else {
+                            return;
+                        };
+                        let proceed = if let Some(current) = get_current_branch(&self.repo) {
+                            current != *branch
+                        } else {
+                            true
+                        };
+                        if !proceed {
+                            self.notify(Severity::Warning, "cannot delete the checked out branch".to_string());
+                            return;
+                        }
+                        self.request_delete_branch(branch.clone());
+                    }
+                    Focus::Viewport if self.graph_selected != 0 => {
+                        let alias = self.oids.get_alias_by_idx(self.graph_selected);
+                        let current = get_current_branch(&self.repo);
+
+                        if let Some(branches) = self.branches.visible.get(&alias) {
+                            // Filter out the current branch, if any
+                            let filtered_branches: Vec<_> = branches
+                                .iter()
+                                .filter(|branch| current.as_ref() != Some(*branch))
+                                .collect();
+
+                            match filtered_branches.len() {
+                                0 => {
+                                    self.notify(Severity::Warning, "cannot delete the checked out branch".to_string());
+                                }
+                                1 => {
+                                    let branch = filtered_branches[0].clone();
+                                    self.request_delete_branch(branch);
+                                }
+                                _ => {
+                                    self.focus = Focus::ModalDeleteBranch;
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    // Deletes a branch, asking first when it is set up to
+    pub fn request_delete_branch(&mut self, branch: String) {
+        if !self.confirm_delete_branch {
+            self.apply_delete_branch(branch);
+            return;
+        }
+
+        // Only the local copy of a remote tracking branch goes, the remote still has it
+        let is_local = self.branches.local.values().any(|branches| branches.contains(&branch));
+        let mut lines = vec![format!("delete {}?", branch)];
+        if !is_local {
+            lines.push("only the local copy goes, the next fetch brings it back".to_string());
+        } else {
+            let tip = self.repo.find_branch(&branch, BranchType::Local).ok().and_then(|found| found.get().target());
+            let head = self.repo.head().ok().and_then(|head| head.target());
+            let lost = tip
+                .map(|tip| {
+                    get_branch_tips(&self.repo, Some(&branch))
+                        .and_then(|mut others| {
+                            others.extend(head);
+                            count_unique_commits(&self.repo, tip, &others)
+                        })
+                        .unwrap_or(0)
+                })
+                .unwrap_or(0);
+            if lost > 0 {
+                lines.push(format!("{} unmerged commits are on no other branch and will be lost", lost));
+            } else {
+                lines.push("its commits are all on other branches".to_string());
+            }
+        }
+        self.open_confirm(ConfirmAction::DeleteBranch(branch), lines);
+    }
+
+    pub fn apply_delete_branch(&mut self, branch: String) {
+        match delete_branch(&self.repo, &branch) {
+            Ok(_) => {
+                self.notify(Severity::Info, format!("deleted {}", branch));
+                self.branches.visible.clear();
+                self.reload();
+            }
+            Err(e) => {
+                self.notify(Severity::Error, format!("cannot delete {}: {}", branch, e.message()));
+            }
+        }
+    }
+
+    // Get the stash@{n} index to act on, from the stashes pane or a stash selected in the graph
+    pub fn get_selected_stash(&self) -> Option<usize> {
+        if self.stashes.sorted.is_empty() {
+            return None;
+        }
+        match self.focus {
+            Focus::Stashes => Some(self.stashes_selected.min(self.stashes.sorted.len() - 1)),
+            Focus::Viewport if self.viewport == Viewport::Graph && self.graph_selected != 0 => {
+                let alias = self.oids.get_alias_by_idx(self.graph_selected);
+                self.stashes.get_index_by_alias(&alias)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn on_stash(&mut self) {
+        match self.viewport {
+            Viewport::Settings | Viewport::Log | Viewport::Viewer | Viewport::Editor => {}
+            _ => {
+                if !self.uncommitted.is_clean {
+                    self.focus = Focus::ModalStash;
+                    self.stash_editor.mode = EditorMode::Insert;
+                }
+            }
+        }
+    }
+
+    pub fn on_pop_stash(&mut self) {
+        match self.viewport {
+            Viewport::Settings | Viewport::Log | Viewport::Viewer | Viewport::Editor => {}
+            _ => {
+                let Some(index) = self.get_selected_stash() else {
+                    self.notify(Severity::Warning, "select a stash to pop".to_string());
+                    return;
+                };
+                match stash_pop(&self.path, index) {
+                    Ok(true) => self.notify(Severity::Warning, format!("stash@{{{index}}} applied with conflicts, kept in the stash list")),
+                    Ok(false) => self.notify(Severity::Info, format!("stash@{{{index}}} popped")),
+                    Err(e) => self.notify(Severity::Error, format!("pop failed: {}", e.message())),
+                }
+                self.reload();
+            }
+        }
+    }
+
+    pub fn on_apply_stash(&mut self) {
+        match self.viewport {
+            Viewport::Settings | Viewport::Log | Viewport::Viewer | Viewport::Editor => {}
+            _ => {
+                let Some(index) = self.get_selected_stash() else {
+                    self.notify(Severity::Warning, "select a stash to apply".to_string());
+                    return;
+                };
+                match stash_apply(&self.path, index) {
+                    Ok(true) => self.notify(Severity::Warning, format!("stash@{{{index}}} applied with conflicts")),
+                    Ok(false) => self.notify(Severity::Info, format!("stash@{{{index}}} applied")),
+                    Err(e) => self.notify(Severity::Error, format!("apply failed: {}", e.message())),
+                }
+                self.reload();
+            }
+        }
+    }
+
+    pub fn on_drop_stash(&mut self) {
+        match self.viewport {
+            Viewport::Settings | Viewport::Log | Viewport::Viewer | Viewport::Editor => {}
+            _ => {
+                let Some(index) = self.get_selected_stash() else {
+                    self.notify(Severity::Warning, "select a stash to drop".to_string());
+                    return;
+                };
+
+                // Name the stash about to be lost
+                let message = self.stashes.sorted
+                    .get(index)
+                    .and_then(|(alias, _)| self.repo.find_commit(*self.oids.get_oid_by_alias(*alias)).ok())
+                    .and_then(|commit| commit.summary().map(str::to_string))
+                    .unwrap_or_default();
+                let lines = vec![
+                    format!("drop stash@{{{}}}?", index),
+                    message,
+                    "its changes will be gone from the stash list".to_string(),
+                ];
+                self.open_confirm(ConfirmAction::DropStash(index), lines);
+            }
+        }
+    }
+
+    pub fn apply_drop_stash(&mut self, index: usize) {
+        match stash_drop(&self.path, index) {
+            Ok(_) => self.notify(Severity::Info, format!("stash@{{{index}}} dropped")),
+            Err(e) => self.notify(Severity::Error, format!("drop failed: {}", e.message())),
+        }
+        self.reload();
+    }
+
+    pub fn on_cherry_pick(&mut self) {
+        if self.focus != Focus::Viewport || self.viewport != Viewport::Graph {
+            return;
+        }
+        if self.rebase_session.is_some() || self.repo.state() != RepositoryState::Clean {
+            self.notify(Severity::Warning, "finish or abort the operation in progress first".to_string());
+            return;
+        }
+        if !self.is_worktree_clean() {
+            return;
+        }
+
+        // Pick the marked commits, or the selected one if nothing is marked
+        let mut queue = self.graph_marked.clone();
+        if queue.is_empty() && self.graph_selected != 0 {
+            queue.push(*self.oids.get_oid_by_idx(self.graph_selected));
+        }
+        if queue.is_empty() {
+            return;
+        }
+
+        // Refuse merges before anything is picked, git would need to be told which side to keep
+        if let Some(merge) = queue.iter().find(|oid| self.repo.find_commit(**oid).is_ok_and(|commit| commit.parent_count() > 1)) {
+            self.notify(Severity::Warning, format!("#{:.6} is a merge, pick the commits it brought in instead", merge));
+            return;
+        }
+        self.graph_marked.clear();
+
+        // Apply the oldest commits first, the graph is sorted newest first
+        let positions: HashMap<Oid, usize> = self.oids.get_sorted_aliases().iter().enumerate()
+            .map(|(idx, &alias)| (*self.oids.get_oid_by_alias(alias), idx))
+            .collect();
+        queue.sort_by_key(|oid| std::cmp::Reverse(positions.get(oid).copied().unwrap_or(0)));
+
+        self.cherry_pick_origin = self.repo.head().ok().and_then(|head| head.target());
+        self.cherry_pick_queue = queue;
+        self.run_cherry_pick_queue();
+    }
+
+    // Cherry-picks the queued commits one by one, stopping at the first one with conflicts
+    pub fn run_cherry_pick_queue(&mut self) {
+        while !self.cherry_pick_queue.is_empty() {
+            let oid = self.cherry_pick_queue.remove(0);
+            match cherry_pick(&self.repo, oid, &self.name, &self.email) {
+                Ok(true) => {
+                    self.notify(Severity::Warning, format!("conflicts picking #{:.6}, resolve and continue", oid));
+                    self.reload();
+                    self.open_conflicts();
+                    return;
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    self.notify(Severity::Error, format!("cherry-pick failed: {}", e.message()));
+                    self.cherry_pick_queue.clear();
+                    self.cherry_pick_origin = None;
+                    self.reload();
+                    return;
+                }
+            }
+        }
+        self.notify(Severity::Info, "cherry-pick done".to_string());
+        self.cherry_pick_origin = None;
+        self.branches.visible.clear();
+        self.reload();
+    }
+
+    pub fn on_revert(&mut self) {
+        if self.focus != Focus::Viewport || self.viewport != Viewport::Graph || self.graph_selected == 0 {
+            return;
+        }
+        if self.rebase_session.is_some() || self.repo.state() != RepositoryState::Clean {
+            self.notify(Severity::Warning, "finish or abort the operation in progress first".to_string());
+            return;
+        }
+        if !self.is_worktree_clean() {
+            return;
+        }
+
+        // Merges need a mainline parent to revert against
+        let oid = *self.oids.get_oid_by_idx(self.graph_selected);
+        if self.get_selected_parent_count() > 1 {
+            self.modal_revert_selected = 0;
+            self.focus = Focus::ModalRevert;
+            return;
+        }
+        self.revert(oid, 0);
+    }
+
+    // Reverts a commit and asks for the message, or stops on conflicts
+    pub fn revert(&mut self, oid: Oid, mainline: u32) {
+        match revert(&self.repo, oid, mainline) {
+            Ok(true) => {
+                self.notify(Severity::Warning, format!("conflicts reverting #{:.6}, resolve and continue", oid));
+                self.reload();
+                self.open_conflicts();
+            }
+            Ok(false) => {
+                let message = self.repo.message().unwrap_or_else(|_| format!("Revert #{}", oid));
+                self.open_commit_modal(CommitKind::Revert, message.trim_end());
+            }
+            Err(e) => {
+                self.notify(Severity::Error, format!("revert failed: {}", e.message()));
+            }
+        }
+    }
+
+    pub fn get_selected_parent_count(&self) -> i32 {
+        let oid = *self.oids.get_oid_by_idx(self.graph_selected);
+        self.repo.find_commit(oid).map(|commit| commit.parent_count() as i32).unwrap_or(0)
+    }
+
+    pub fn on_continue(&mut self) {
+        if self.rebase_session.is_some() {
+            self.continue_rebase();
+            return;
+        }
+        match self.repo.state() {
+            RepositoryState::CherryPick | RepositoryState::CherryPickSequence => {
+                let picked = match self.repo.revparse_single("CHERRY_PICK_HEAD").map(|object| object.id()) {
+                    Ok(oid) => oid,
+                    Err(e) => {
+                        self.notify(Severity::Error, format!("continue failed: {}", e.message()));
+                        return;
+                    }
+                };
+                match cherry_pick_commit(&self.repo, picked, &self.name, &self.email) {
+                    Ok(_) => self.run_cherry_pick_queue(),
+                    Err(e) => self.notify(Severity::Error, format!("continue failed: {}", e.message())),
+                }
+            }
+            RepositoryState::Merge => {
+                if self.uncommitted.is_conflicted {
+                    self.notify(Severity::Warning, "resolve and stage the conflicted files first".to_string());
+                    return;
+                }
+                let message = self.repo.message().unwrap_or_default();
+                self.open_commit_modal(CommitKind::Merge, message.trim_end());
+            }
+            RepositoryState::Revert | RepositoryState::RevertSequence => {
+                if self.uncommitted.is_conflicted {
+                    self.notify(Severity::Warning, "resolve and stage the conflicted files first".to_string());
+                    return;
+                }
+                let message = self.repo.message().unwrap_or_default();
+                self.open_commit_modal(CommitKind::Revert, message.trim_end());
+            }
+            _ => {
+                self.notify(Severity::Warning, "nothing to continue".to_string());
+            }
+        }
+    }
+
+    pub fn on_abort(&mut self) {
+        if let Some(session) = self.rebase_session.take() {
+            match rebase_abort(&self.repo, session.branch.as_deref(), session.origin) {
+                Ok(_) => self.notify(Severity::Info, "rebase aborted".to_string()),
+                Err(e) => {
+                    // Keep the session so the abort can be retried
+                    self.notify(Severity::Error, format!("abort failed: {}", e.message()));
+                    self.rebase_session = Some(session);
+                }
+            }
+            self.persist_rebase_session();
+            self.branches.visible.clear();
+            self.reload();
+            return;
+        }
+        match self.repo.state() {
+            RepositoryState::CherryPick | RepositoryState::CherryPickSequence => {
+                // Go back to where the whole sequence started, not just the last pick
+                let target = self.cherry_pick_origin.or_else(|| self.repo.head().ok().and_then(|head| head.target()));
+                if let Some(target) = target {
+                    match abort_operation(&self.repo, target) {
+                        Ok(_) => self.notify(Severity::Info, "cherry-pick aborted".to_string()),
+                        Err(e) => self.notify(Severity::Error, format!("abort failed: {}", e.message())),
+                    }
+                }
+                self.cherry_pick_queue.clear();
+                self.cherry_pick_origin = None;
+                self.branches.visible.clear();
+                self.reload();
+            }
+            RepositoryState::Merge => {
+                if let Some(head) = self.repo.head().ok().and_then(|head| head.target()) {
+                    match abort_operation(&self.repo, head) {
+                        Ok(_) => self.notify(Severity::Info, "merge aborted".to_string()),
+                        Err(e) => self.notify(Severity::Error, format!("abort failed: {}", e.message())),
+                    }
+                }
+                self.reload();
+            }
+            RepositoryState::Revert | RepositoryState::RevertSequence => {
+                if let Some(head) = self.repo.head().ok().and_then(|head| head.target()) {
+                    match abort_operation(&self.repo, head) {
+                        Ok(_) => self.notify(Severity::Info, "revert aborted".to_string()),
+                        Err(e) => self.notify(Severity::Error, format!("abort failed: {}", e.message())),
+                    }
+                }
+                self.reload();
+            }
+            RepositoryState::Rebase | RepositoryState::RebaseInteractive | RepositoryState::RebaseMerge => {
+                // A rebase left behind by another session, let git2 restore its original head
+                match self.repo.open_rebase(None).and_then(|mut rebase| rebase.abort()) {
+                    Ok(_) => self.notify(Severity::Info, "rebase aborted".to_string()),
+                    Err(e) => self.notify(Severity::Error, format!("abort failed: {}", e.message())),
+                }
+                self.branches.visible.clear();
+                self.reload();
+            }
+            _ => {
+                self.notify(Severity::Warning, "nothing to abort".to_string());
+            }
+        }
+    }
+
+    pub fn on_merge(&mut self, no_ff: bool) {
+        match self.viewport {
+            Viewport::Settings | Viewport::Log | Viewport::Viewer | Viewport::Editor | Viewport::Rebase => {}
+            _ => {
+                if self.rebase_session.is_some() || self.repo.state() != RepositoryState::Clean {
+                    self.notify(Severity::Warning, "finish or abort the operation in progress first".to_string());
+                    return;
+                }
+                if !self.is_worktree_clean() {
+                    return;
+                }
+                self.merge_no_ff = no_ff;
+                match self.focus {
+                    Focus::Branches => {
+                        if let Some((_, branch)) = self.branches.sorted.get(self.branches_selected).cloned() {
+                            self.merge(&branch);
+                        }
+                    }
+                    Focus::Viewport if self.graph_selected != 0 => {
+                        let alias = self.oids.get_alias_by_idx(self.graph_selected);
+                        let branches = self.branches.all.get(&alias).cloned().unwrap_or_default();
+                        match branches.len() {
+                            // Not a branch tip, merge the commit itself
+                            0 => {
+                                let oid = self.oids.get_oid_by_alias(alias).to_string();
+                                self.merge(&oid);
+                            }
+                            1 => self.merge(&branches[0]),
+                            _ => {
+                                self.modal_merge_selected = 0;
+                                self.focus = Focus::ModalMerge;
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    pub fn merge(&mut self, revision: &str) {
+        match merge_revision(&self.repo, revision, self.merge_no_ff) {
+            Ok(MergeOutcome::UpToDate) => {
+                self.notify(Severity::Info, "already up to date".to_string());
+            }
+            Ok(MergeOutcome::FastForward) => {
+                self.notify(Severity::Info, format!("fast-forwarded to {}", revision));
+                self.branches.visible.clear();
+                self.reload();
+            }
+            Ok(MergeOutcome::Merged) => {
+                self.reload();
+                let message = self.repo.message().unwrap_or_else(|_| format!("Merge {}", revision));
+                self.open_commit_modal(CommitKind::Merge, message.trim_end());
+            }
+            Ok(MergeOutcome::Conflicted) => {
+                self.notify(Severity::Warning, "merge conflicts, resolve and continue".to_string());
+                self.reload();
+                self.open_conflicts();
+            }
+            Err(e) => {
+                self.notify(Severity::Error, format!("merge failed: {}", e.message()));
+            }
+        }
+    }
+
+    pub fn on_pull(&mut self) {
+        match self.viewport {
+            Viewport::Settings | Viewport::Log | Viewport::Viewer | Viewport::Editor | Viewport::Rebase => {}
+            _ => {
+                if self.rebase_session.is_some() || self.repo.state() != RepositoryState::Clean {
+                    self.notify(Severity::Warning, "finish or abort the operation in progress first".to_string());
+                    return;
+                }
+                let upstream = match get_upstream(&self.repo) {
+                    Ok(upstream) => upstream,
+                    Err(e) => {
+                        self.notify(Severity::Error, format!("pull failed: {}", e.message()));
+                        return;
+                    }
+                };
+
+                // Fetch the remote the current branch tracks, the rest happens once it is done
+                if self.is_network_busy() {
+                    return;
+                }
+                let (progress, rx) = Progress::new();
+                let handle = fetch_over_ssh(&self.path, vec![upstream.remote.clone()], self.fetch_prune, false, &self.credentials, &progress);
+                self.track_network(format!("fetching {}", upstream.remote), progress, rx, handle, move |app, result| match result {
+                    Ok(_) => app.pull_fetched(upstream),
+                    Err(e) => app.on_network_error("fetch", e, |app| app.on_pull()),
+                });
+            }
+        }
+    }
+
+    // Integrates the freshly fetched upstream into the current branch
+    pub fn pull_fetched(&mut self, upstream: Upstream) {
+        self.branches.visible.clear();
+
+        // Something else may have started while fetching
+        if self.rebase_session.is_some() || self.repo.state() != RepositoryState::Clean {
+            self.notify(Severity::Warning, "fetched, finish or abort the operation in progress before pulling".to_string());
+            self.reload();
+            return;
+        }
+
+        // The branch may have been switched or moved while fetching, integrating now would land elsewhere
+        let head = self.repo.head().ok().and_then(|head| head.target());
+        if get_current_branch(&self.repo).as_deref() != Some(upstream.branch.as_str()) || head != Some(upstream.head) {
+            self.notify(Severity::Warning, format!("fetched, but {} changed while fetching, pull again", upstream.branch));
+            self.reload();
+            return;
+        }
+
+        let result = get_upstream_oid(&self.repo, &upstream).and_then(|target| {
+            let annotated = self.repo.find_annotated_commit(target)?;
+            let (analysis, _) = self.repo.merge_analysis(&[&annotated])?;
+            Ok((target, analysis))
+        });
+        let (target, analysis) = match result {
+            Ok(result) => result,
+            Err(e) => {
+                self.notify(Severity::Error, format!("pull failed: {}", e.message()));
+                return;
+            }
+        };
+        let tracking = upstream.tracking.trim_start_matches("refs/remotes/").to_string();
+        let strategy = get_pull_strategy(&self.repo, &upstream.branch);
+
+        if analysis.is_up_to_date() {
+            self.notify(Severity::Info, "already up to date".to_string());
+            self.reload();
+            return;
+        }
+
+        if analysis.is_fast_forward() && strategy != PullStrategy::MergeNoFastForward {
+            match fast_forward(&self.repo, target) {
+                Ok(_) => self.notify(Severity::Info, format!("fast-forwarded to {}", tracking)),
+                Err(e) => self.notify(Severity::Error, format!("pull failed: {}", e.message())),
+            }
+            self.reload();
+            return;
+        }
+
+        match strategy {
+            PullStrategy::FastForwardOnly => {
+                self.notify(Severity::Error, format!("{} has diverged from {}, cannot fast-forward", upstream.branch, tracking));
+                self.reload();
+            }
+            PullStrategy::Rebase => {
+                match get_rebase_steps(&self.repo, target) {
+                    Ok(queue) => {
+                        self.notify(Severity::Info, format!("diverged, rebasing onto {}", tracking));
+                        self.begin_rebase(target, queue);
+                    }
+                    Err(e) => self.notify(Severity::Error, format!("pull failed: {}", e.message())),
+                }
+            }
+            PullStrategy::Merge | PullStrategy::MergeNoFastForward => {
+                if !self.is_worktree_clean() {
+                    self.reload();
+                    return;
+                }
+                self.notify(Severity::Info, format!("diverged, merging {}", tracking));
+                self.merge_no_ff = strategy == PullStrategy::MergeNoFastForward;
+                self.merge(&upstream.tracking);
+            }
+        }
+    }
+
+    pub fn on_conflicts(&mut self) {
+        match self.viewport {
+            Viewport::Conflicts => {
+                self.viewport = Viewport::Graph;
+            }
+            Viewport::Settings | Viewport::Log | Viewport::Editor | Viewport::Rebase => {}
             _ => {
-                match self.focus {
-                    Focus::Branches => {
-                        let branch = &self.branches.sorted.get(self.branches_selected).unwrap().1;
-                        let proceed = if let Some(current) = get_current_branch(&self.repo) {
-                            current != *branch
-                        } else {
-                            true
-                        };
-                        if proceed
-                            && delete_branch(&self.repo, branch).is_ok() {
-                                self.branches.visible.clear();
-                                self.reload();
-                            };
+                self.open_conflicts();
+                if self.viewport != Viewport::Conflicts {
+                    self.notify(Severity::Warning, "no conflicts".to_string());
+                }
+            }
+        }
+    }
+
+    // Shows the conflict view if the index has conflicted entries
+    pub fn open_conflicts(&mut self) {
+        self.conflicts = get_conflicted_paths(&self.repo).unwrap_or_default();
+        if self.conflicts.is_empty() {
+            return;
+        }
+        self.conflicts_selected = 0;
+        self.load_conflict_file();
+        self.viewport = Viewport::Conflicts;
+        self.focus = Focus::Viewport;
+    }
+
+    pub fn load_conflict_file(&mut self) {
+        self.conflict_selected = 0;
+        self.conflict_scroll.set(0);
+        self.conflict_file = match self.conflicts.get(self.conflicts_selected) {
+            Some(path) => match get_conflict_file(&self.repo, path) {
+                Ok(file) => Some(file),
+                Err(e) => {
+                    self.notify(Severity::Error, format!("cannot read conflict: {}", e.message()));
+                    None
+                }
+            },
+            None => None,
+        };
+    }
+
+    // Takes a side for the selected hunk and writes the file
+    pub fn resolve_conflict(&mut self, side: ConflictSide) {
+        let Some(file) = self.conflict_file.as_mut() else {
+            return;
+        };
+
+        // A file deleted on one side can only be taken as a whole
+        if !file.is_ours || !file.is_theirs {
+            self.take_conflict_file(side);
+            return;
+        }
+
+        file.resolve(self.conflict_selected, side);
+        if let Err(e) = write_conflict_file(&self.repo, file) {
+            let message = format!("cannot write {}: {}", file.path, e.message());
+            self.notify(Severity::Error, message);
+            return;
+        }
+
+        if file.is_resolved() {
+            let message = format!("{} has no conflicts left, press s to mark it resolved", file.path);
+            self.notify(Severity::Info, message);
+        } else if self.conflict_selected + 1 < file.get_conflict_count() {
+            self.conflict_selected += 1;
+        }
+    }
+
+    pub fn take_conflict_file(&mut self, side: ConflictSide) {
+        let Some(file) = self.conflict_file.as_mut() else {
+            return;
+        };
+        let path = file.path.clone();
+        match take_conflict_side(&self.repo, file, side) {
+            Ok(_) => self.notify(Severity::Info, format!("took {} for {}, press s to mark it resolved", if side == ConflictSide::Ours { "ours" } else { "theirs" }, path)),
+            Err(e) => self.notify(Severity::Error, format!("cannot write {}: {}", path, e.message())),
+        }
+    }
+
+    // Stages the selected file, which clears its conflict entries, asking first if markers are left
+    pub fn stage_conflict_file(&mut self) {
+        let Some(path) = self.conflicts.get(self.conflicts_selected).cloned() else {
+            return;
+        };
+        let unresolved = self.conflict_file.as_ref().map(|file| file.get_unresolved_count()).unwrap_or(0);
+        if unresolved > 0 {
+            let lines = vec![
+                format!("stage {}?", path),
+                format!("{} conflict{} still unresolved", unresolved, if unresolved == 1 { " is" } else { "s are" }),
+                "their markers would be committed".to_string(),
+            ];
+            self.open_confirm(ConfirmAction::StageConflicted(path), lines);
+            return;
+        }
+        self.apply_stage_conflict_file(path);
+    }
+
+    pub fn apply_stage_conflict_file(&mut self, path: String) {
+        if let Err(e) = mark_resolved(&self.repo, &path) {
+            self.notify(Severity::Error, format!("cannot stage {}: {}", path, e.message()));
+            return;
+        }
+
+        self.conflicts = get_conflicted_paths(&self.repo).unwrap_or_default();
+        self.conflicts_selected = self.conflicts_selected.min(self.conflicts.len().saturating_sub(1));
+        self.load_conflict_file();
+        let message = if self.conflicts.is_empty() {
+            "all conflicts resolved, continue with shift+c".to_string()
+        } else {
+            format!("{} marked as resolved", path)
+        };
+        self.notify(Severity::Info, message);
+        self.reload();
+    }
+
+    pub fn on_interactive_rebase(&mut self) {
+        if self.focus != Focus::Viewport || self.viewport != Viewport::Graph || self.graph_selected == 0 {
+            return;
+        }
+        if self.rebase_session.is_some() || self.repo.state() != RepositoryState::Clean {
+            self.notify(Severity::Warning, "finish or abort the operation in progress first".to_string());
+            return;
+        }
+        if !self.is_worktree_clean() {
+            return;
+        }
+
+        // Everything after the selected commit goes into the todo list
+        let base = *self.oids.get_oid_by_idx(self.graph_selected);
+        match get_rebase_steps(&self.repo, base) {
+            Ok(steps) if steps.is_empty() => {
+                self.notify(Severity::Warning, "nothing to rebase".to_string());
+            }
+            Ok(steps) => {
+                self.rebase_base = Some(base);
+                self.rebase_steps = steps;
+                self.rebase_selected = 0;
+                self.viewport = Viewport::Rebase;
+                self.hint = "(p)ick (r)eword (e)dit (s)quash (f)ixup (d)rop, shift+j/k to move".to_string();
+            }
+            Err(e) => {
+                self.notify(Severity::Error, format!("rebase failed: {}", e.message()));
+            }
+        }
+    }
+
+    pub fn start_rebase(&mut self) {
+        let Some(base) = self.rebase_base else {
+            return;
+        };
+        let queue: Vec<_> = self.rebase_steps.iter().filter(|step| step.action != RebaseAction::Drop).cloned().collect();
+
+        // Squashing needs a commit to fold into
+        if let Some(first) = queue.first()
+            && matches!(first.action, RebaseAction::Squash | RebaseAction::Fixup) {
+                self.notify(Severity::Error, format!("cannot {} without a previous commit", first.action.as_str()));
+                return;
+            }
+
+        self.rebase_steps.clear();
+        self.rebase_base = None;
+        self.viewport = Viewport::Graph;
+        self.begin_rebase(base, queue);
+    }
+
+    // Detaches HEAD onto the base and replays the queue on top of it
+    pub fn begin_rebase(&mut self, base: Oid, queue: Vec<RebaseStep>) {
+        let origin = match self.repo.head().ok().and_then(|head| head.target()) {
+            Some(origin) => origin,
+            None => return,
+        };
+
+        // The todo may have been edited for a while, check again right before HEAD moves
+        if !self.is_worktree_clean() {
+            return;
+        }
+        match rebase_start(&self.repo, base) {
+            Ok(branch) => {
+                self.rebase_session = Some(RebaseSession {
+                    total: queue.len(),
+                    queue,
+                    current: None,
+                    branch,
+                    origin,
+                });
+                self.run_rebase_queue();
+            }
+            Err(e) => {
+                self.notify(Severity::Error, format!("rebase failed: {}", e.message()));
+            }
+        }
+    }
+
+    // Applies the remaining todo steps, stopping on conflicts and at steps that need the user
+    pub fn run_rebase_queue(&mut self) {
+        loop {
+            let Some(session) = self.rebase_session.as_mut() else {
+                return;
+            };
+            if session.queue.is_empty() {
+                break;
+            }
+            let step = session.queue.remove(0);
+            session.current = Some(step.clone());
+            let (done, total) = session.get_progress();
+            self.persist_rebase_session();
+
+            match rebase_step(&self.repo, step.oid, &self.name, &self.email) {
+                Ok(true) => {
+                    self.notify(Severity::Warning, format!("rebasing {}/{}: conflicts at #{:.6}, resolve and continue", done, total, step.oid));
+                    self.reload();
+                    self.open_conflicts();
+                    return;
+                }
+                Ok(false) => {
+                    if !self.finish_rebase_step() {
+                        self.reload();
+                        return;
                     }
-                    Focus::Viewport => {
-                        if self.graph_selected != 0 {
-                            
-                            let alias = self.oids.get_alias_by_idx(if self.graph_selected == 0 { 1 } else { self.graph_selected });
-                            let current = get_current_branch(&self.repo);
-
-                            if let Some(branches) = self.branches.visible.get(&alias) {
-                                // Filter out the current branch, if any
-                                let filtered_branches: Vec<_> = branches
-                                    .iter()
-                                    .filter(|branch| current.as_ref() != Some(*branch))
-                                    .collect();
-
-                                match filtered_branches.len() {
-                                    0 => {
-                                    }
-                                    1 => {
-                                        if delete_branch(&self.repo, filtered_branches[0]).is_ok() {
-                                            self.branches.visible.clear();
-                                            self.reload();
-                                        };
-                                    }
-                                    _ => {
-                                        self.focus = Focus::ModalDeleteBranch;
-                                    }
-                                }
-                            }
-                        }
+                }
+                Err(e) => {
+                    self.notify(Severity::Warning, format!("rebasing {}/{}: {}, continue or abort", done, total, e.message()));
+                    self.reload();
+                    return;
+                }
+            }
+        }
+
+        // Point the branch at the result
+        if let Some(session) = self.rebase_session.take() {
+            match rebase_finish(&self.repo, session.branch.as_deref()) {
+                Ok(_) => self.notify(Severity::Info, "rebase done".to_string()),
+                Err(e) => {
+                    self.notify(Severity::Error, format!("rebase failed: {}", e.message()));
+                    self.rebase_session = Some(session);
+                }
+            }
+            self.persist_rebase_session();
+        }
+        self.branches.visible.clear();
+        self.reload();
+    }
+
+    // Keeps the todo under .git so a paused rebase can still be continued or aborted after a restart
+    pub fn persist_rebase_session(&mut self) {
+        let result = match self.rebase_session.as_ref() {
+            Some(session) => save_rebase_session(&self.repo, session),
+            None => clear_rebase_session(&self.repo),
+        };
+        if let Err(e) = result {
+            self.notify(Severity::Error, format!("cannot save the rebase: {}", e.message()));
+        }
+    }
+
+    // Runs what the current step needs after it was applied, returns false if the rebase has to pause
+    pub fn finish_rebase_step(&mut self) -> bool {
+        let Some(step) = self.rebase_session.as_ref().and_then(|session| session.current.clone()) else {
+            return true;
+        };
+        let is_done = match step.action {
+            RebaseAction::Pick | RebaseAction::Drop => true,
+            RebaseAction::Fixup => {
+                if let Err(e) = squash_head(&self.repo, None) {
+                    self.notify(Severity::Error, format!("fixup failed: {}, continue or abort", e.message()));
+                    return false;
+                }
+                true
+            }
+            RebaseAction::Squash => {
+                // Offer both messages for editing, same as git
+                let message = self.repo.find_commit(step.oid).ok()
+                    .and_then(|commit| commit.message().map(|message| message.to_string()))
+                    .unwrap_or_default();
+                match squash_head(&self.repo, None) {
+                    Ok(squashed) => {
+                        let previous = self.repo.find_commit(squashed).ok()
+                            .and_then(|commit| commit.message().map(|message| message.to_string()))
+                            .unwrap_or_default();
+                        self.open_commit_modal(CommitKind::Rebase, &format!("{}\n\n{}", previous.trim_end(), message.trim_end()));
+                    }
+                    Err(e) => {
+                        self.notify(Severity::Error, format!("squash failed: {}, continue or abort", e.message()));
+                    }
+                }
+                false
+            }
+            RebaseAction::Reword => {
+                let message = self.repo.find_commit(step.oid).ok()
+                    .and_then(|commit| commit.message().map(|message| message.to_string()))
+                    .unwrap_or_default();
+                self.open_commit_modal(CommitKind::Rebase, message.trim_end());
+                false
+            }
+            RebaseAction::Edit => {
+                self.notify(Severity::Warning, format!("stopped at #{:.6}, stage your edits and continue", step.oid));
+                false
+            }
+        };
+        if is_done && let Some(session) = self.rebase_session.as_mut() {
+            session.current = None;
+        }
+        is_done
+    }
+
+    // Opens the commit message modal prefilled with a message
+    pub fn open_commit_modal(&mut self, kind: CommitKind, message: &str) {
+        self.commit_editor = EditorState::new(Lines::from(message));
+        self.commit_editor.mode = EditorMode::Insert;
+        self.commit_kind = kind;
+        self.focus = Focus::ModalCommit;
+    }
+
+    pub fn continue_rebase(&mut self) {
+        let Some(step) = self.rebase_session.as_ref().and_then(|session| session.current.clone()) else {
+            self.run_rebase_queue();
+            return;
+        };
+
+        // The step stopped on conflicts, commit the resolution first
+        if matches!(self.repo.state(), RepositoryState::Rebase | RepositoryState::RebaseInteractive | RepositoryState::RebaseMerge) {
+            if let Err(e) = rebase_continue(&self.repo, &self.name, &self.email) {
+                self.notify(Severity::Error, format!("continue failed: {}", e.message()));
+                return;
+            }
+            if !self.finish_rebase_step() {
+                self.reload();
+                return;
+            }
+        } else {
+            match step.action {
+                RebaseAction::Reword | RebaseAction::Squash => {
+                    // The message modal was dismissed, bring it back
+                    let message = self.repo.head().ok()
+                        .and_then(|head| head.peel_to_commit().ok())
+                        .and_then(|commit| commit.message().map(|message| message.to_string()))
+                        .unwrap_or_default();
+                    self.open_commit_modal(CommitKind::Rebase, message.trim_end());
+                    return;
+                }
+                RebaseAction::Edit if self.uncommitted.is_staged => {
+                    if let Err(e) = amend_head_with_index(&self.repo) {
+                        self.notify(Severity::Error, format!("continue failed: {}", e.message()));
+                        return;
                     }
-                    _ => {}
                 }
+                _ => {}
+            }
+            if let Some(session) = self.rebase_session.as_mut() {
+                session.current = None;
             }
         }
+        self.run_rebase_queue();
     }
 
     pub fn on_go_back(&mut self) {
@@ -1211,6 +4017,21 @@ impl App {
                 self.modal_checkout_selected = 0;
                 self.focus = Focus::Viewport;
             }
+            Focus::ModalStash => {
+                self.focus = Focus::Viewport;
+            }
+            Focus::ModalMerge => {
+                self.modal_merge_selected = 0;
+                self.focus = Focus::Viewport;
+            }
+            Focus::ModalRevert => {
+                self.modal_revert_selected = 0;
+                self.focus = Focus::Viewport;
+            }
+            // Drop the marked lines before leaving the viewer
+            Focus::Viewport if self.viewport == Viewport::Viewer && self.viewer_anchor.is_some() => {
+                self.viewer_anchor = None;
+            }
             _ => {
                 self.viewer_selected = 0;
                 self.viewport = Viewport::Graph;
@@ -1223,7 +4044,7 @@ impl App {
     pub fn on_reload(&mut self) {
         self.reload();
         match self.focus {
-            Focus::ModalCheckout | Focus::ModalCommit => {
+            Focus::ModalCheckout | Focus::ModalCommit | Focus::ModalStash | Focus::ModalMerge | Focus::ModalRevert => {
                 self.focus = Focus::Viewport;
             }
             _ => {}
@@ -1246,6 +4067,114 @@ impl App {
         }
     }
 
+    pub fn on_toggle_stashes(&mut self) {
+        self.is_stashes = !self.is_stashes;
+        if self.viewport == Viewport::Editor || self.viewport == Viewport::Settings {
+            return;
+        }
+        if self.is_stashes {
+            self.focus = Focus::Stashes;
+        } else {
+            self.focus = Focus::Viewport;
+        }
+    }
+
+    pub fn on_toggle_tags(&mut self) {
+        self.is_tags = !self.is_tags;
+        if self.viewport == Viewport::Editor || self.viewport == Viewport::Settings {
+            return;
+        }
+        if self.is_tags {
+            self.focus = Focus::Tags;
+        } else {
+            self.focus = Focus::Viewport;
+        }
+    }
+
+    // Lists the files a commit changed, reporting when it cannot be read
+    pub fn load_current_diff(&mut self, oid: Oid) {
+        self.current_diff = match get_filenames_diff_at_oid(&self.repo, oid) {
+            Ok(changes) => changes,
+            Err(e) => {
+                self.notify(Severity::Error, format!("cannot read #{:.6}: {}", oid, e.message()));
+                Vec::new()
+            }
+        };
+    }
+
+    // The remote selected in the remotes pane, or the one git would pick otherwise
+    pub fn get_selected_remote(&self, fallback: fn(&Repository) -> AppResult<String>) -> AppResult<String> {
+        match self.remotes.get(self.remotes_selected) {
+            Some(remote) if self.focus == Focus::Remotes => Ok(remote.name.clone()),
+            _ => fallback(&self.repo),
+        }
+    }
+
+    pub fn open_remote_modal(&mut self, action: RemoteAction, value: &str) {
+        self.remote_editor = EditorState::new(Lines::from(value));
+        self.remote_editor.mode = EditorMode::Insert;
+        self.remote_action = Some(action);
+        self.focus = Focus::ModalRemote;
+    }
+
+    pub fn apply_remote_action(&mut self) {
+        let Some(action) = self.remote_action.take() else {
+            self.focus = Focus::Remotes;
+            return;
+        };
+        let value = editor_state_to_string(&self.remote_editor).trim().to_string();
+
+        let result = match &action {
+            // Adding takes a name first, then asks for the url
+            RemoteAction::Add => {
+                if value.is_empty() {
+                    self.notify(Severity::Warning, "a remote needs a name".to_string());
+                    self.remote_action = Some(action);
+                    return;
+                }
+                self.open_remote_modal(RemoteAction::AddUrl(value), "");
+                return;
+            }
+            RemoteAction::AddUrl(name) => add_remote(&self.repo, name, &value).map(|_| format!("added remote {}", name)),
+            RemoteAction::Rename(name) => rename_remote(&self.repo, name, &value).map(|problems| {
+                if problems.is_empty() {
+                    format!("renamed remote {} to {}", name, value)
+                } else {
+                    format!("renamed remote {} to {}, update these refspecs by hand: {}", name, value, problems.join(", "))
+                }
+            }),
+            RemoteAction::SetUrl(name) => set_remote_url(&self.repo, name, &value, false).map(|_| format!("updated the fetch url of {}", name)),
+            RemoteAction::SetPushUrl(name) => set_remote_url(&self.repo, name, &value, true).map(|_| format!("updated the push url of {}", name)),
+        };
+
+        match result {
+            Ok(hint) => {
+                self.notify(Severity::Info, hint);
+                self.remote_editor = EditorState::default();
+                self.branches.visible.clear();
+                self.reload();
+                self.focus = Focus::Remotes;
+            }
+            Err(e) => {
+                self.notify(Severity::Error, format!("remote failed: {}", e.message()));
+                self.remote_action = Some(action);
+            }
+        }
+    }
+
+    pub fn on_toggle_remotes(&mut self) {
+        self.is_remotes = !self.is_remotes;
+        if self.viewport == Viewport::Editor || self.viewport == Viewport::Settings {
+            return;
+        }
+        if self.is_remotes {
+            self.focus = Focus::Remotes;
+            self.hint = "(a) add (n) rename (e) url (E) push url (d) remove (f) fetch (F) fetch all (p) push".to_string();
+        } else {
+            self.focus = Focus::Viewport;
+        }
+    }
+
     pub fn on_toggle_status(&mut self) {
         self.is_status = !self.is_status;
         if !self.is_status && (self.focus == Focus::StatusTop || self.focus == Focus::StatusBottom)
@@ -1278,6 +4207,122 @@ impl App {
         };
     }
 
+    pub fn on_toggle_log(&mut self) {
+        match self.viewport {
+            Viewport::Log => {
+                self.viewport = Viewport::Graph;
+                self.focus = Focus::Viewport;
+            }
+            _ => {
+                self.viewport = Viewport::Log;
+                self.focus = Focus::Viewport;
+                self.log_selected = 0;
+            }
+        };
+    }
+
+    pub fn on_toggle_first_parent(&mut self) {
+        self.first_parent = !self.first_parent;
+
+        // Rows shift once merged branches collapse, start over from the top
+        self.graph_selected = 0;
+        self.graph_scroll.set(0);
+        self.reload();
+
+        let label = if self.first_parent { "showing first parents only" } else { "showing all parents" };
+        self.notify(Severity::Info, label.to_string());
+    }
+
+    pub fn on_filter_range(&mut self) {
+        // Only from the graph or the panes beside it
+        if !matches!(self.viewport, Viewport::Graph | Viewport::Viewer) || self.focus == Focus::Viewport && self.viewport != Viewport::Graph {
+            return;
+        }
+
+        self.range_editor = EditorState::new(Lines::from(self.range.as_deref().unwrap_or_default()));
+        self.range_editor.mode = EditorMode::Insert;
+        self.range_focus = std::mem::replace(&mut self.focus, Focus::ModalRange);
+    }
+
+    pub fn apply_range(&mut self) {
+        let expression = editor_state_to_string(&self.range_editor).trim().to_string();
+
+        // An empty expression goes back to all branches
+        let range = if expression.is_empty() {
+            None
+        } else {
+            // Resolve it here so a typo keeps the prompt open instead of emptying the graph
+            let resolved = self.repo.revwalk().and_then(|mut revwalk| push_revisions(&self.repo, &mut revwalk, &expression));
+            if let Err(e) = resolved {
+                self.notify(Severity::Warning, format!("cannot resolve {}: {}", expression, e.message()));
+                return;
+            }
+            Some(expression)
+        };
+
+        self.focus = std::mem::replace(&mut self.range_focus, Focus::Viewport);
+        if range == self.range {
+            return;
+        }
+
+        self.notify(Severity::Info, match &range {
+            Some(expression) => format!("showing {}", expression),
+            None => "showing all branches".to_string(),
+        });
+        self.range = range;
+        self.graph_selected = 0;
+        self.graph_scroll.set(0);
+        self.reload();
+    }
+
+    pub fn on_filter_path(&mut self) {
+        // Only from the graph or the panes beside it
+        if !matches!(self.viewport, Viewport::Graph | Viewport::Viewer) || self.focus == Focus::Viewport && self.viewport != Viewport::Graph {
+            return;
+        }
+
+        // Follow the selected file right away, otherwise ask for a path
+        let selected = match self.focus {
+            Focus::StatusTop if self.graph_selected == 0 => self.uncommitted.get_staged_file(self.status_top_selected).map(|file| file.filename),
+            Focus::StatusTop => self.current_diff.get(self.status_top_selected).map(|file| file.filename.clone()),
+            Focus::StatusBottom if self.graph_selected == 0 => {
+                let conflicted_len = self.uncommitted.conflicted.len();
+                if self.status_bottom_selected < conflicted_len {
+                    self.uncommitted.conflicted.get(self.status_bottom_selected).cloned()
+                } else {
+                    self.uncommitted.get_unstaged_file(self.status_bottom_selected - conflicted_len).map(|file| file.filename)
+                }
+            }
+            _ => None,
+        };
+
+        match selected {
+            // Pressing it again on the same file goes back to the whole history
+            Some(pathspec) if self.pathspec.as_ref() == Some(&pathspec) => self.apply_pathspec(None),
+            Some(pathspec) => self.apply_pathspec(Some(pathspec)),
+            None => {
+                self.path_editor = EditorState::new(Lines::from(self.pathspec.as_deref().unwrap_or_default()));
+                self.path_editor.mode = EditorMode::Insert;
+                self.path_focus = std::mem::replace(&mut self.focus, Focus::ModalPath);
+            }
+        }
+    }
+
+    pub fn apply_pathspec(&mut self, pathspec: Option<String>) {
+        if pathspec == self.pathspec {
+            return;
+        }
+
+        self.notify(Severity::Info, match &pathspec {
+            Some(pathspec) => format!("showing commits touching {}", pathspec),
+            None => "showing all commits".to_string(),
+        });
+        self.pathspec = pathspec;
+        self.graph_selected = 0;
+        self.graph_scroll.set(0);
+        self.reload();
+    }
+
     pub fn on_exit(&mut self) {
         self.exit();
     }
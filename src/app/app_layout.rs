@@ -18,6 +18,12 @@ pub struct Layout {
     pub app: Rect,
     pub branches: Rect,
     pub branches_scrollbar: Rect,
+    pub stashes: Rect,
+    pub stashes_scrollbar: Rect,
+    pub tags: Rect,
+    pub tags_scrollbar: Rect,
+    pub remotes: Rect,
+    pub remotes_scrollbar: Rect,
     pub graph: Rect,
     pub graph_scrollbar: Rect,
     pub inspector: Rect,
@@ -38,6 +44,8 @@ impl App {
         let is_inspector = !is_settings && self.is_inspector && self.graph_selected != 0;
         let is_status = !is_settings && self.is_status;
         let is_right_pane = is_inspector || is_status;
+        let is_left_pane = !is_settings && (self.is_branches || self.is_stashes || self.is_tags || self.is_remotes);
+        let left_panes = [self.is_branches, self.is_stashes, self.is_tags, self.is_remotes].iter().filter(|&&is_pane| is_pane).count().max(1) as u32;
 
         let chunks_vertical = ratatui::layout::Layout::default()
             .direction(ratatui::layout::Direction::Vertical)
@@ -59,12 +67,22 @@ impl App {
         let chunks_horizontal = ratatui::layout::Layout::default()
             .direction(ratatui::layout::Direction::Horizontal)
             .constraints([
-                ratatui::layout::Constraint::Length(if self.is_branches && !is_settings { 45 } else { 0 }),
+                ratatui::layout::Constraint::Length(if is_left_pane { 45 } else { 0 }),
                 ratatui::layout::Constraint::Max(500),
                 ratatui::layout::Constraint::Length(if is_right_pane { 46 } else { 0 }),
             ])
             .split(chunks_vertical[1]);
 
+        let chunks_left = ratatui::layout::Layout::default()
+            .direction(ratatui::layout::Direction::Vertical)
+            .constraints([
+                ratatui::layout::Constraint::Ratio(if self.is_branches { 1 } else { 0 }, left_panes),
+                ratatui::layout::Constraint::Ratio(if self.is_stashes { 1 } else { 0 }, left_panes),
+                ratatui::layout::Constraint::Ratio(if self.is_tags { 1 } else { 0 }, left_panes),
+                ratatui::layout::Constraint::Ratio(if self.is_remotes { 1 } else { 0 }, left_panes),
+            ])
+            .split(chunks_horizontal[0]);
+
         let chunks_pane = ratatui::layout::Layout::default()
             .direction(ratatui::layout::Direction::Vertical)
             .constraints([
@@ -90,11 +108,50 @@ impl App {
             .split(chunks_vertical[2]);
 
         // Branches
-        let mut branches_scrollbar = chunks_horizontal[0];
+        let mut branches_scrollbar = chunks_left[0];
         branches_scrollbar.width += 1;
-        let mut branches = chunks_horizontal[0];
+        let mut branches = chunks_left[0];
         branches.y += 1; 
 
+        // Stashes
+        let mut stashes_scrollbar = chunks_left[1];
+        stashes_scrollbar.width += 1;
+        let mut stashes = chunks_left[1];
+        if self.is_branches {
+            stashes_scrollbar.y = stashes_scrollbar.y.saturating_sub(1);
+            stashes_scrollbar.height += 1;
+            stashes.y = stashes.y.saturating_sub(1);
+            stashes.height += 1;
+        } else {
+            stashes.y += 1;
+        }
+
+        // Tags
+        let mut tags_scrollbar = chunks_left[2];
+        tags_scrollbar.width += 1;
+        let mut tags = chunks_left[2];
+        if self.is_branches || self.is_stashes {
+            tags_scrollbar.y = tags_scrollbar.y.saturating_sub(1);
+            tags_scrollbar.height += 1;
+            tags.y = tags.y.saturating_sub(1);
+            tags.height += 1;
+        } else {
+            tags.y += 1;
+        }
+
+        // Remotes
+        let mut remotes_scrollbar = chunks_left[3];
+        remotes_scrollbar.width += 1;
+        let mut remotes = chunks_left[3];
+        if self.is_branches || self.is_stashes || self.is_tags {
+            remotes_scrollbar.y = remotes_scrollbar.y.saturating_sub(1);
+            remotes_scrollbar.height += 1;
+            remotes.y = remotes.y.saturating_sub(1);
+            remotes.height += 1;
+        } else {
+            remotes.y += 1;
+        }
+
         // Graph
         let graph_scrollbar = chunks_horizontal[1];
         let mut graph = chunks_horizontal[1];
@@ -136,6 +193,12 @@ impl App {
             app: chunks_vertical[1],
             branches,
             branches_scrollbar,
+            stashes,
+            stashes_scrollbar,
+            tags,
+            tags_scrollbar,
+            remotes,
+            remotes_scrollbar,
             graph,
             graph_scrollbar,
             inspector,
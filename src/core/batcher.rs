@@ -1,11 +1,13 @@
 #[rustfmt::skip]
 use std::{
     rc::Rc,
+    path::PathBuf,
     sync::{
         Mutex
     },
     collections::{
         HashMap,
+        HashSet
     }
 };
 #[rustfmt::skip]
@@ -13,7 +15,9 @@ use git2::{
     BranchType,
     Oid,
     Repository,
+    RevparseMode,
     Revwalk,
+    Tree,
 };
 #[rustfmt::skip]
 use crate::{
@@ -27,6 +31,7 @@ use crate::{
 // Encapsulate a revwalk over the git repository, allowing incremental fetching of commits
 pub struct Batcher {
     revwalk: Mutex<Revwalk<'static>>,
+    filter: Mutex<Option<PathFilter>>,
 }
 
 impl Batcher {
@@ -34,11 +39,15 @@ impl Batcher {
     pub fn new(
         repo: Rc<Repository>,
         visible: HashMap<u32, Vec<String>>,
-        oids: &mut Oids
+        oids: &mut Oids,
+        first_parent: bool,
+        range: Option<String>,
+        pathspec: Option<String>
     ) -> Result<Self, git2::Error> {
-        let revwalk = Self::build(&repo, visible, oids)?;
+        let revwalk = Self::build(&repo, visible, oids, first_parent, range.as_deref())?;
         Ok(Self {
             revwalk: Mutex::new(revwalk),
+            filter: Mutex::new(pathspec.map(|pathspec| PathFilter::new(repo, &pathspec))),
         })
     }
 
@@ -47,29 +56,59 @@ impl Batcher {
         &self,
         repo: Rc<Repository>,
         visible: HashMap<u32, Vec<String>>,
-        oids: &mut Oids
+        oids: &mut Oids,
+        first_parent: bool,
+        range: Option<String>,
+        pathspec: Option<String>
     ) -> Result<(), git2::Error> {
-        let revwalk = Self::build(&repo, visible, oids)?;
+        let revwalk = Self::build(&repo, visible, oids, first_parent, range.as_deref())?;
         let mut guard = self.revwalk.lock().unwrap();
         *guard = revwalk;
+        *self.filter.lock().unwrap() = pathspec.map(|pathspec| PathFilter::new(repo, &pathspec));
         Ok(())
     }
 
-    // Get up to "count" commits from the global revwalk
+    // Get up to "count" commits from the global revwalk, only the ones touching the path when filtered
     pub fn next(&self, count: usize) -> Vec<Oid> {
         let mut revwalk = self.revwalk.lock().unwrap();
-        revwalk
-            .by_ref()
-            .take(count)
-            .filter_map(Result::ok)
-            .collect()
+        let mut filter = self.filter.lock().unwrap();
+        match filter.as_mut() {
+            Some(filter) => revwalk
+                .by_ref()
+                .filter_map(Result::ok)
+                .filter(|oid| filter.is_shown(*oid))
+                .take(count)
+                .collect(),
+            None => revwalk
+                .by_ref()
+                .take(count)
+                .filter_map(Result::ok)
+                .collect()
+        }
+    }
+
+    // Replace parents left out by the path filter with their nearest ancestors that are shown
+    pub fn rewrite_parents(&self, parents: Vec<Oid>) -> Vec<Oid> {
+        let mut filter = self.filter.lock().unwrap();
+        let Some(filter) = filter.as_mut() else {
+            return parents;
+        };
+        let mut rewritten: Vec<Oid> = Vec::new();
+        for parent in parents {
+            if let Some(oid) = filter.get_nearest(parent) && !rewritten.contains(&oid) {
+                rewritten.push(oid);
+            }
+        }
+        rewritten
     }
 
     // Internal helper to build a revwalk for all branch tips
     fn build(
         repo: &Repository,
         visible: HashMap<u32, Vec<String>>,
-        oids: &mut Oids
+        oids: &mut Oids,
+        first_parent: bool,
+        range: Option<&str>
     ) -> Result<Revwalk<'static>, git2::Error> {
 
         // Safe: we keep repo alive in Rc, so transmute to 'static is safe
@@ -77,25 +116,207 @@ impl Batcher {
             unsafe { std::mem::transmute::<&Repository, &'static Repository>(repo) };
         let mut revwalk = repo_ref.revwalk()?;
 
-        // TODO: Steal faster implementation from get_tip_oids function!
-        // Push all branches_local and branches_remote branch tips
-        for branch_type in [BranchType::Local, BranchType::Remote] {
-            for branch_result in repo.branches(Some(branch_type))? {
-                let (branch, _) = branch_result?;
-                if let Some(oid) = branch.get().target() {
+        // A revision range replaces the branch tips and their visibility
+        if let Some(expression) = range {
+            push_revisions(repo, &mut revwalk, expression)?;
+        } else {
+            // TODO: Steal faster implementation from get_tip_oids function!
+            // Push all branches_local and branches_remote branch tips
+            let mut pushed: Vec<Oid> = Vec::new();
+            let mut is_filtered = false;
+            for branch_type in [BranchType::Local, BranchType::Remote] {
+                for branch_result in repo.branches(Some(branch_type))? {
+                    let (branch, _) = branch_result?;
+                    if let Some(oid) = branch.get().target() {
 
-                    // Get the oidi
-                    let alias = oids.get_alias_by_oid(oid);
+                        // Get the oidi
+                        let alias = oids.get_alias_by_oid(oid);
 
-                    if visible.is_empty() || visible.contains_key(&alias) {
+                        if visible.is_empty() || visible.contains_key(&alias) {
+                            revwalk.push(oid)?;
+                            pushed.push(oid);
+                        } else {
+                            is_filtered = true;
+                        }
+                    }
+                }
+            }
+
+            // Push stashes, but only the ones made on top of the visible history
+            if let Ok(reflog) = repo.reflog("refs/stash") {
+                for entry in reflog.iter() {
+                    let oid = entry.id_new();
+                    let Ok(base) = repo.find_commit(oid).and_then(|stash| stash.parent_id(0)) else {
+                        continue;
+                    };
+                    if !is_filtered || pushed.iter().any(|&tip| tip == base || repo.graph_descendant_of(tip, base).unwrap_or(false)) {
                         revwalk.push(oid)?;
                     }
                 }
             }
         }
 
+        // Only follow the mainline of each merge, like git log --first-parent
+        if first_parent {
+            revwalk.simplify_first_parent()?;
+        }
+
         // Topological and chronological sorting
         revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
         Ok(revwalk)
     }
 }
+
+// Push a revision expression such as "main..feature", "v1.2...HEAD" or "^origin/main topic" onto a revwalk
+pub fn push_revisions(repo: &Repository, revwalk: &mut Revwalk, expression: &str) -> Result<(), git2::Error> {
+    if expression.trim().is_empty() {
+        return Err(git2::Error::from_str("the revision range is empty"));
+    }
+
+    for token in expression.split_whitespace() {
+
+        // A leading caret hides everything reachable from the revision
+        if let Some(revision) = token.strip_prefix('^') {
+            let oid = repo.revparse_single(revision)?.peel_to_commit()?.id();
+            revwalk.hide(oid)?;
+            continue;
+        }
+
+        let spec = repo.revparse(token)?;
+        let from = spec.from().map(|object| object.peel_to_commit()).transpose()?.map(|commit| commit.id());
+        let to = spec.to().map(|object| object.peel_to_commit()).transpose()?.map(|commit| commit.id());
+
+        match (from, to) {
+            // Symmetric difference, commits on either side but not on both
+            (Some(from), Some(to)) if spec.mode().contains(RevparseMode::MERGE_BASE) => {
+                revwalk.push(from)?;
+                revwalk.push(to)?;
+                for base in repo.merge_bases(from, to)?.iter() {
+                    revwalk.hide(*base)?;
+                }
+            }
+            (Some(from), Some(to)) => {
+                revwalk.hide(from)?;
+                revwalk.push(to)?;
+            }
+            (Some(oid), None) | (None, Some(oid)) => revwalk.push(oid)?,
+            (None, None) => return Err(git2::Error::from_str(&format!("cannot resolve {}", token))),
+        }
+    }
+    Ok(())
+}
+
+// How a commit relates to the filtered path
+#[derive(Clone, Copy)]
+enum PathChange {
+    Touched,   // Changes the path compared to every parent
+    Same(Oid), // Leaves the path as it is in this parent
+    Absent,    // A root commit without the path
+}
+
+// Decides which commits touch a file or directory, like git log -- <path>
+pub struct PathFilter {
+    repo: Rc<Repository>,
+    path: PathBuf,
+    changes: HashMap<Oid, PathChange>,
+    nearest: HashMap<Oid, Option<Oid>>,
+    followed: HashSet<Oid>,
+    pruned: HashSet<Oid>,
+}
+
+impl PathFilter {
+    pub fn new(repo: Rc<Repository>, path: &str) -> Self {
+        Self {
+            repo,
+            path: PathBuf::from(path.trim_matches('/')),
+            changes: HashMap::new(),
+            nearest: HashMap::new(),
+            followed: HashSet::new(),
+            pruned: HashSet::new(),
+        }
+    }
+
+    // Decide on the next commit of the walk, which always yields children before their parents
+    pub fn is_shown(&mut self, oid: Oid) -> bool {
+        // A commit nothing led to yet is a tip, one only reached through the dropped side of a merge is hidden
+        let is_reachable = self.followed.contains(&oid) || !self.pruned.contains(&oid);
+        let change = self.get_change(oid);
+        let parents: Vec<Oid> = self.repo.find_commit(oid).map(|commit| commit.parent_ids().collect()).unwrap_or_default();
+
+        // Like git, a merge leaving the path as one parent has it only follows that parent
+        for parent in parents {
+            let is_followed = match change {
+                PathChange::Same(same) => is_reachable && parent == same,
+                _ => is_reachable,
+            };
+            if is_followed {
+                self.followed.insert(parent);
+            } else {
+                self.pruned.insert(parent);
+            }
+        }
+
+        is_reachable && matches!(change, PathChange::Touched)
+    }
+
+    // Follow the history down to the first commit touching the path, remembering the way for later lookups
+    pub fn get_nearest(&mut self, oid: Oid) -> Option<Oid> {
+        let mut visited: Vec<Oid> = Vec::new();
+        let mut current = oid;
+        let nearest = loop {
+            if let Some(nearest) = self.nearest.get(&current) {
+                break *nearest;
+            }
+            match self.get_change(current) {
+                PathChange::Touched => break Some(current),
+                PathChange::Same(parent) => {
+                    visited.push(current);
+                    current = parent;
+                }
+                PathChange::Absent => {
+                    visited.push(current);
+                    break None;
+                }
+            }
+        };
+        for oid in visited {
+            self.nearest.insert(oid, nearest);
+        }
+        nearest
+    }
+
+    fn get_change(&mut self, oid: Oid) -> PathChange {
+        if let Some(change) = self.changes.get(&oid) {
+            return *change;
+        }
+
+        // A commit that cannot be read is shown rather than silently dropped
+        let change = self.inspect(oid).unwrap_or(PathChange::Touched);
+        self.changes.insert(oid, change);
+        change
+    }
+
+    fn inspect(&self, oid: Oid) -> Result<PathChange, git2::Error> {
+        let commit = self.repo.find_commit(oid)?;
+        let entry = self.get_entry(&commit.tree()?);
+        if commit.parent_count() == 0 {
+            return Ok(if entry.is_some() { PathChange::Touched } else { PathChange::Absent });
+        }
+
+        // Same as any parent means the change, if any, came in from there
+        for parent in commit.parents() {
+            if self.get_entry(&parent.tree()?) == entry {
+                return Ok(PathChange::Same(parent.id()));
+            }
+        }
+        Ok(PathChange::Touched)
+    }
+
+    // Blob or tree id at the path, comparing ids is enough to tell whether anything below changed
+    fn get_entry(&self, tree: &Tree) -> Option<Oid> {
+        if self.path.as_os_str().is_empty() {
+            return Some(tree.id());
+        }
+        tree.get_path(&self.path).ok().map(|entry| entry.id())
+    }
+}
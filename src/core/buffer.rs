@@ -56,21 +56,37 @@ impl Buffer {
                 self.mergers.remove(merger_pos);
             }
 
-            let mut clone = self.curr[merger_idx].clone();
-            clone.parent_a = clone.parent_b;
-            clone.parent_b = NONE;
+            // Merged parents already awaited on a lane of their own stay put, the rest fan out to new lanes
+            let merger = self.curr[merger_idx].clone();
+            let (awaited, fanned): (Vec<u32>, Vec<u32>) = merger.merged_parents().partition(|&parent| {
+                self.curr.iter().enumerate().any(|(idx, inner)| {
+                    idx != merger_idx && inner.is_single_parent() && inner.parent_a == parent
+                })
+            });
+
             self.curr[merger_idx].parent_b = NONE;
-            self.curr.push_back(clone.clone());
+            self.curr[merger_idx].extra_parents = awaited;
+            self.curr[merger_idx].compact();
 
             self.delta.ops.push_back(DeltaOp::Replace {
                 index: merger_idx,
                 new: self.curr[merger_idx].clone(),
             });
 
-            self.delta.ops.push_back(DeltaOp::Insert {
-                index: self.curr.len() - 1,
-                item: clone,
-            });
+            for parent in fanned {
+                let clone = Chunk {
+                    parent_a: parent,
+                    parent_b: NONE,
+                    extra_parents: Vec::new(),
+                    ..merger.clone()
+                };
+                self.curr.push_back(clone.clone());
+
+                self.delta.ops.push_back(DeltaOp::Insert {
+                    index: self.curr.len() - 1,
+                    item: clone,
+                });
+            }
         }
 
         // Replace or append buffer chunk
@@ -104,6 +120,12 @@ impl Buffer {
                     parents_changed = true;
                 }
 
+                if inner.extra_parents.contains(&old_alias) {
+                    inner.extra_parents.retain(|parent| *parent != old_alias);
+                    parents_changed = true;
+                }
+                inner.compact();
+
                 if parents_changed && inner.parent_a == NONE && inner.parent_b== NONE {
                     *inner = Chunk::dummy();
                     self.delta.ops.push_back(DeltaOp::Replace {
@@ -172,3 +194,94 @@ impl Buffer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Alias and the parents a lane waits on
+    type Lane = (u32, u32, u32, Vec<u32>);
+
+    // Feeds commits in topological order like the walker, planning a fan out for merges with parents no lane waits on
+    fn walk(commits: &[(u32, &[u32])]) -> Vec<Vec<Lane>> {
+        let mut buffer = Buffer::default();
+        let mut snapshots = Vec::new();
+        for &(alias, parents) in commits {
+            buffer.update(Chunk::commit(alias, parents));
+            let chunk = buffer.curr.iter().find(|chunk| chunk.alias == alias).cloned().unwrap();
+            if chunk.parent_a != NONE && chunk.parent_b != NONE {
+                let is_awaited = chunk.merged_parents().all(|parent| {
+                    buffer.curr.iter().any(|nested| nested.is_single_parent() && nested.parent_a == parent)
+                });
+                if !is_awaited {
+                    buffer.merger(alias);
+                }
+            }
+            snapshots.push(buffer.curr.iter().map(|chunk| (chunk.alias, chunk.parent_a, chunk.parent_b, chunk.extra_parents.clone())).collect());
+        }
+        snapshots
+    }
+
+    #[test]
+    fn octopus_of_three_fans_out() {
+        let snapshots = walk(&[(0, &[1, 2, 3]), (1, &[4]), (2, &[4]), (3, &[4]), (4, &[])]);
+
+        // The merge waits on all three parents in a single lane
+        assert_eq!(snapshots[0], vec![(0, 1, 2, vec![3])]);
+
+        // Reaching the first parent opens a lane for each of the others
+        assert_eq!(snapshots[1], vec![(1, 4, NONE, vec![]), (0, 2, NONE, vec![]), (0, 3, NONE, vec![])]);
+        assert_eq!(snapshots[2], vec![(1, 4, NONE, vec![]), (2, 4, NONE, vec![]), (0, 3, NONE, vec![])]);
+        assert_eq!(snapshots[3], vec![(1, 4, NONE, vec![]), (2, 4, NONE, vec![]), (3, 4, NONE, vec![])]);
+
+        // The common base joins them back into the first lane
+        assert_eq!(snapshots[4], vec![(4, NONE, NONE, vec![]), (NONE, NONE, NONE, vec![]), (NONE, NONE, NONE, vec![])]);
+    }
+
+    #[test]
+    fn octopus_of_five_fans_out() {
+        let snapshots = walk(&[(9, &[0]), (0, &[1, 2, 3, 4, 5]), (1, &[6]), (2, &[6]), (3, &[6]), (4, &[6]), (5, &[6]), (6, &[])]);
+
+        assert_eq!(snapshots[1], vec![(0, 1, 2, vec![3, 4, 5])]);
+        assert_eq!(snapshots[2], vec![
+            (1, 6, NONE, vec![]),
+            (0, 2, NONE, vec![]),
+            (0, 3, NONE, vec![]),
+            (0, 4, NONE, vec![]),
+            (0, 5, NONE, vec![]),
+        ]);
+
+        // Every parent takes over the lane opened for it
+        assert_eq!(snapshots[6], vec![
+            (1, 6, NONE, vec![]),
+            (2, 6, NONE, vec![]),
+            (3, 6, NONE, vec![]),
+            (4, 6, NONE, vec![]),
+            (5, 6, NONE, vec![]),
+        ]);
+        assert_eq!(snapshots[7].len(), 5);
+        assert!(snapshots[7][1..].iter().all(|lane| lane.0 == NONE));
+    }
+
+    #[test]
+    fn octopus_keeps_awaited_parents_on_their_lane() {
+        let snapshots = walk(&[(8, &[2]), (0, &[1, 2, 3]), (1, &[4]), (2, &[4]), (3, &[4]), (4, &[])]);
+
+        // Parent 2 is already awaited by the branch on the first lane, only parent 3 gets a new one
+        assert_eq!(snapshots[1], vec![(8, 2, NONE, vec![]), (0, 1, 2, vec![3])]);
+        assert_eq!(snapshots[2], vec![(8, 2, NONE, vec![]), (1, 4, NONE, vec![]), (0, 3, NONE, vec![])]);
+        assert_eq!(snapshots[3], vec![(2, 4, NONE, vec![]), (1, 4, NONE, vec![]), (0, 3, NONE, vec![])]);
+        assert_eq!(snapshots[4], vec![(2, 4, NONE, vec![]), (1, 4, NONE, vec![]), (3, 4, NONE, vec![])]);
+    }
+
+    #[test]
+    fn compact_moves_extra_parents_up() {
+        let mut chunk = Chunk::commit(0, &[1, 2, 3, 4]);
+        assert_eq!((chunk.parent_a, chunk.parent_b, chunk.extra_parents.clone()), (1, 2, vec![3, 4]));
+
+        chunk.parent_b = NONE;
+        chunk.compact();
+        assert_eq!((chunk.parent_a, chunk.parent_b, chunk.extra_parents.clone()), (1, 3, vec![4]));
+        assert_eq!(chunk.merged_parents().collect::<Vec<_>>(), vec![3, 4]);
+    }
+}
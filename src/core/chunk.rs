@@ -2,6 +2,7 @@
 pub enum Markers {
     Uncommitted,
     Commit,
+    Merge, // A merge walked along its first parent only
     Dummy,
 }
 
@@ -12,6 +13,7 @@ pub struct Chunk {
     pub alias: u32,
     pub parent_a: u32,
     pub parent_b: u32,
+    pub extra_parents: Vec<u32>, // Parents beyond the second, for octopus merges
     pub marker: Markers,
 }
 
@@ -21,6 +23,7 @@ impl Default for Chunk {
             alias: NONE,
             parent_a: NONE,
             parent_b: NONE,
+            extra_parents: Vec::new(),
             marker: Markers::Dummy
         }
     }
@@ -32,15 +35,17 @@ impl Chunk {
             alias: NONE,
             parent_a,
             parent_b,
+            extra_parents: Vec::new(),
             marker: Markers::Uncommitted,
         }
     }
 
-    pub fn commit(alias: u32, parent_a: u32, parent_b: u32) -> Self {
+    pub fn commit(alias: u32, parents: &[u32]) -> Self {
         Chunk {
             alias,
-            parent_a,
-            parent_b,
+            parent_a: parents.first().copied().unwrap_or(NONE),
+            parent_b: parents.get(1).copied().unwrap_or(NONE),
+            extra_parents: parents.iter().skip(2).copied().collect(),
             marker: Markers::Commit,
         }
     }
@@ -50,6 +55,7 @@ impl Chunk {
             alias: NONE,
             parent_a: NONE,
             parent_b: NONE,
+            extra_parents: Vec::new(),
             marker: Markers::Dummy,
         }
     }
@@ -57,4 +63,29 @@ impl Chunk {
     pub fn is_dummy(&self) -> bool {
         self.marker == Markers::Dummy
     }
+
+    pub fn is_collapsed_merge(&self) -> bool {
+        self.marker == Markers::Merge
+    }
+
+    pub fn is_octopus(&self) -> bool {
+        !self.extra_parents.is_empty()
+    }
+
+    // Waits on a single parent, the shape of a plain lane
+    pub fn is_single_parent(&self) -> bool {
+        (self.parent_a != NONE && self.parent_b == NONE) || (self.parent_a == NONE && self.parent_b != NONE)
+    }
+
+    // Parents beyond the first that still have to be reached
+    pub fn merged_parents(&self) -> impl Iterator<Item = u32> + '_ {
+        std::iter::once(self.parent_b).filter(|&parent| parent != NONE).chain(self.extra_parents.iter().copied())
+    }
+
+    // Moves a waiting extra parent up once the second one is reached, so the lane never looks empty
+    pub fn compact(&mut self) {
+        if self.parent_b == NONE && !self.extra_parents.is_empty() {
+            self.parent_b = self.extra_parents.remove(0);
+        }
+    }
 }
@@ -0,0 +1,146 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictSide {
+    Ours,
+    Theirs,
+}
+
+// A run of lines in a conflicted file, lines keep their line endings
+#[derive(Debug, Clone)]
+pub enum ConflictChunk {
+    Clean(Vec<String>),
+    Conflict {
+        base: Vec<String>,
+        ours: Vec<String>,
+        theirs: Vec<String>,
+        resolution: Option<ConflictSide>,
+    },
+}
+
+// A conflicted file split into clean runs and conflicting hunks
+#[derive(Debug, Clone)]
+pub struct ConflictFile {
+    pub path: String,
+    pub chunks: Vec<ConflictChunk>,
+    pub is_ours: bool,   // False if the file was deleted on our side
+    pub is_theirs: bool, // False if the file was deleted on their side
+}
+
+impl ConflictFile {
+
+    // Splits merged content with diff3 style markers into chunks
+    pub fn from_merged(path: &str, content: &str) -> Self {
+        let mut chunks = Vec::new();
+        let mut clean = Vec::new();
+        let mut sections: Option<[Vec<String>; 3]> = None;
+        let mut target = 0;
+
+        for line in content.split_inclusive('\n') {
+            match &mut sections {
+                None if is_marker(line, "<<<<<<<") => {
+                    if !clean.is_empty() {
+                        chunks.push(ConflictChunk::Clean(std::mem::take(&mut clean)));
+                    }
+                    sections = Some([Vec::new(), Vec::new(), Vec::new()]);
+                    target = 0;
+                }
+                None => clean.push(line.to_string()),
+                Some(_) if is_marker(line, "|||||||") => target = 1,
+                Some(_) if is_marker(line, "=======") => target = 2,
+                Some(_) if is_marker(line, ">>>>>>>") => {
+                    let [ours, base, theirs] = sections.take().unwrap_or_default();
+                    chunks.push(ConflictChunk::Conflict { base, ours, theirs, resolution: None });
+                }
+                Some(parts) => parts[target].push(line.to_string()),
+            }
+        }
+
+        if !clean.is_empty() {
+            chunks.push(ConflictChunk::Clean(clean));
+        }
+
+        ConflictFile { path: path.to_string(), chunks, is_ours: true, is_theirs: true }
+    }
+
+    // Markers written without the diff3 style have no base, take it from the same hunk merged from the index
+    pub fn fill_bases(&mut self, merged: &ConflictFile) {
+        for chunk in self.chunks.iter_mut() {
+            let ConflictChunk::Conflict { base, ours, theirs, .. } = chunk else {
+                continue;
+            };
+            if !base.is_empty() {
+                continue;
+            }
+            let source = merged.chunks.iter().find_map(|other| match other {
+                ConflictChunk::Conflict { base, ours: other_ours, theirs: other_theirs, .. }
+                    if other_ours == ours && other_theirs == theirs => Some(base),
+                _ => None,
+            });
+            if let Some(source) = source {
+                base.clone_from(source);
+            }
+        }
+    }
+
+    pub fn get_conflict_count(&self) -> usize {
+        self.chunks.iter().filter(|chunk| matches!(chunk, ConflictChunk::Conflict { .. })).count()
+    }
+
+    pub fn get_unresolved_count(&self) -> usize {
+        self.chunks.iter().filter(|chunk| matches!(chunk, ConflictChunk::Conflict { resolution: None, .. })).count()
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        self.get_unresolved_count() == 0
+    }
+
+    // Picks a side for the nth conflicting hunk
+    pub fn resolve(&mut self, conflict: usize, side: ConflictSide) {
+        if let Some(ConflictChunk::Conflict { resolution, .. }) = self.chunks
+            .iter_mut()
+            .filter(|chunk| matches!(chunk, ConflictChunk::Conflict { .. }))
+            .nth(conflict) {
+                *resolution = Some(side);
+            }
+    }
+
+    // Renders the file, keeping conflict markers around the hunks that are not resolved yet
+    pub fn to_content(&self) -> String {
+        let mut content = String::new();
+        for chunk in self.chunks.iter() {
+            match chunk {
+                ConflictChunk::Clean(lines) => content.extend(lines.iter().map(|line| line.as_str())),
+                ConflictChunk::Conflict { ours, resolution: Some(ConflictSide::Ours), .. } => {
+                    content.extend(ours.iter().map(|line| line.as_str()));
+                }
+                ConflictChunk::Conflict { theirs, resolution: Some(ConflictSide::Theirs), .. } => {
+                    content.extend(theirs.iter().map(|line| line.as_str()));
+                }
+                ConflictChunk::Conflict { base, ours, theirs, resolution: None } => {
+                    content.push_str("<<<<<<< ours\n");
+                    push_lines(&mut content, ours);
+                    content.push_str("||||||| base\n");
+                    push_lines(&mut content, base);
+                    content.push_str("=======\n");
+                    push_lines(&mut content, theirs);
+                    content.push_str(">>>>>>> theirs\n");
+                }
+            }
+        }
+        content
+    }
+}
+
+// A marker line is the marker alone or followed by a space and a label, so content like ======== is kept
+fn is_marker(line: &str, marker: &str) -> bool {
+    line.trim_end_matches(['\n', '\r'])
+        .strip_prefix(marker)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+}
+
+// Appends lines, making sure a marker that follows starts on its own line
+fn push_lines(content: &mut String, lines: &[String]) {
+    content.extend(lines.iter().map(|line| line.as_str()));
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+}
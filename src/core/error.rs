@@ -0,0 +1,76 @@
+#[rustfmt::skip]
+use std::fmt;
+#[rustfmt::skip]
+use git2::{
+    Error,
+    ErrorClass,
+    ErrorCode
+};
+
+pub type AppResult<T> = Result<T, AppError>;
+
+// A git error together with what the app was doing when it happened
+#[derive(Debug)]
+pub struct AppError {
+    pub context: String, // What was being done, e.g. checking out main
+    pub source: Error,   // What libgit2 reported
+}
+
+impl AppError {
+
+    pub fn new(context: impl Into<String>, source: Error) -> Self {
+        Self { context: context.into(), source }
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        self.source.code()
+    }
+
+    pub fn class(&self) -> ErrorClass {
+        self.source.class()
+    }
+
+    pub fn message(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.context.is_empty() {
+            write!(f, "{}", self.source.message())
+        } else {
+            write!(f, "{}: {}", self.context, self.source.message())
+        }
+    }
+}
+
+impl std::error::Error for AppError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl From<Error> for AppError {
+    fn from(source: Error) -> Self {
+        Self { context: String::new(), source }
+    }
+}
+
+// Says what was being done when a git call fails
+pub trait Context<T> {
+    fn context(self, context: impl Into<String>) -> AppResult<T>;
+}
+
+impl<T> Context<T> for Result<T, Error> {
+    fn context(self, context: impl Into<String>) -> AppResult<T> {
+        self.map_err(|source| AppError::new(context, source))
+    }
+}
+
+// Missing values, like an unborn HEAD without a target
+impl<T> Context<T> for Option<T> {
+    fn context(self, context: impl Into<String>) -> AppResult<T> {
+        self.ok_or_else(|| AppError::new(context, Error::from_str("not found")))
+    }
+}
@@ -0,0 +1,116 @@
+#[rustfmt::skip]
+use std::time::{
+    Duration,
+    Instant
+};
+#[rustfmt::skip]
+use chrono::{
+    DateTime,
+    Local
+};
+
+// Entries kept before the oldest ones are dropped
+const CAPACITY: usize = 1000;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+pub struct LogEntry {
+    pub time: DateTime<Local>,
+    pub severity: Severity,
+    pub action: String,     // Command that ran, e.g. push
+    pub target: String,     // What it ran on, e.g. a commit or a branch
+    pub duration: Duration, // From the key press until the result
+    pub message: String,    // Result or error message
+}
+
+// The action currently running, entries recorded meanwhile belong to it
+pub struct Current {
+    action: String,
+    target: String,
+    started: Instant,
+    is_recorded: bool,
+}
+
+#[derive(Default)]
+pub struct Log {
+    pub entries: Vec<LogEntry>,
+    current: Option<Current>,
+}
+
+impl Log {
+
+    pub fn begin(&mut self, action: &str, target: &str) {
+        self.begin_at(action, target, Instant::now());
+    }
+
+    // Starts an action that began earlier, like a network thread finishing
+    pub fn begin_at(&mut self, action: &str, target: &str, started: Instant) {
+        self.current = Some(Current {
+            action: action.to_string(),
+            target: target.to_string(),
+            started,
+            is_recorded: false,
+        });
+    }
+
+    // Records a result for the current action, or on its own outside of one
+    pub fn record(&mut self, severity: Severity, message: &str) {
+        let (action, target, duration) = match self.current.as_mut() {
+            Some(current) => {
+                current.is_recorded = true;
+                (current.action.clone(), current.target.clone(), current.started.elapsed())
+            }
+            None => (String::new(), String::new(), Duration::ZERO),
+        };
+
+        if self.entries.len() >= CAPACITY {
+            self.entries.remove(0);
+        }
+        self.entries.push(LogEntry {
+            time: Local::now(),
+            severity,
+            action,
+            target,
+            duration,
+            message: message.to_string(),
+        });
+    }
+
+    // Closes the current action, noting that it ran when it reported nothing
+    pub fn end(&mut self) {
+        if self.current.as_ref().is_some_and(|current| !current.is_recorded) {
+            self.record(Severity::Info, "done");
+        }
+        self.current = None;
+    }
+
+    // Closes the current action without noting anything, for keys that did no git work
+    pub fn discard(&mut self) {
+        self.current = None;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.current.is_some()
+    }
+
+    // Times the current action from now on, e.g. from the key that submits a modal
+    pub fn restart(&mut self) {
+        if let Some(current) = self.current.as_mut() {
+            current.started = Instant::now();
+        }
+    }
+
+    // Puts the current action aside while another one is logged
+    pub fn suspend(&mut self) -> Option<Current> {
+        self.current.take()
+    }
+
+    pub fn resume(&mut self, current: Option<Current>) {
+        self.current = current;
+    }
+}
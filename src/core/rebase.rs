@@ -0,0 +1,65 @@
+#[rustfmt::skip]
+use git2::{
+    Oid
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RebaseAction {
+    Pick,
+    Reword,
+    Edit,
+    Squash,
+    Fixup,
+    Drop,
+}
+
+impl RebaseAction {
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RebaseAction::Pick => "pick",
+            RebaseAction::Reword => "reword",
+            RebaseAction::Edit => "edit",
+            RebaseAction::Squash => "squash",
+            RebaseAction::Fixup => "fixup",
+            RebaseAction::Drop => "drop",
+        }
+    }
+
+    pub fn parse(action: &str) -> Option<Self> {
+        match action {
+            "pick" => Some(RebaseAction::Pick),
+            "reword" => Some(RebaseAction::Reword),
+            "edit" => Some(RebaseAction::Edit),
+            "squash" => Some(RebaseAction::Squash),
+            "fixup" => Some(RebaseAction::Fixup),
+            "drop" => Some(RebaseAction::Drop),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RebaseStep {
+    pub action: RebaseAction,
+    pub oid: Oid,
+    pub summary: String,
+}
+
+// A rebase that is being executed, one todo step at a time
+#[derive(Debug, Clone)]
+pub struct RebaseSession {
+    pub queue: Vec<RebaseStep>,      // Steps that are not applied yet
+    pub current: Option<RebaseStep>, // Step the rebase stopped at, if any
+    pub branch: Option<String>,      // Branch to move to the result, None if HEAD was detached
+    pub origin: Oid,                 // Where HEAD was before the rebase started
+    pub total: usize,                // Number of steps in the todo list
+}
+
+impl RebaseSession {
+
+    pub fn get_progress(&self) -> (usize, usize) {
+        let done = self.total - self.queue.len();
+        (done, self.total)
+    }
+}
@@ -19,7 +19,8 @@ use git2::{
 use ratatui::{
     style::{
         Style,
-        Color
+        Color,
+        Modifier
     },
     text::{
         Line,
@@ -49,6 +50,12 @@ use crate::{
         chunk::{
             Chunk,
             NONE
+        },
+        layers::{
+            LayersContext
+        },
+        search::{
+            Search
         }
     },
     helpers::{
@@ -65,10 +72,12 @@ use crate::{
     }
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn render_graph_range(
     theme: &Theme,
     oids: &Oids,
     all: &HashMap<u32, Vec<String>>,
+    stashes: &HashMap<u32, Vec<String>>,
     history: &Vector<Vector<Chunk>>,
     head_alias: u32,
     start: usize,
@@ -148,7 +157,9 @@ pub fn render_graph_range(
             } else if *alias == chunk.alias {
                 is_commit_found = true;
                 let is_two_parents = chunk.parent_a != NONE && chunk.parent_b != NONE;
-                if is_two_parents && !(all.contains_key(alias)) {
+                if stashes.contains_key(alias) {
+                    layers.commit(SYM_STASH, lane_idx);
+                } else if (is_two_parents || chunk.is_collapsed_merge()) && !(all.contains_key(alias)) {
                     layers.commit(SYM_MERGE, lane_idx);
                 } else if all.contains_key(alias) {
                     layers.commit(SYM_COMMIT_BRANCH, lane_idx);
@@ -162,7 +173,9 @@ pub fn render_graph_range(
                 // Check if commit is being merged into
                 let mut is_mergee_found = false;
                 let mut is_drawing = false;
-                if is_two_parents {
+                if chunk.is_octopus() {
+                    render_octopus(&mut layers, chunk, last);
+                } else if is_two_parents {
                     let mut is_merger_found = false;
                     let mut merger_idx: usize = 0;
                     for chunk_nested in last {
@@ -311,7 +324,9 @@ pub fn render_graph_range(
         }
 
         if !is_commit_found {
-            if all.contains_key(alias) {
+            if stashes.contains_key(alias) {
+                layers.commit(SYM_STASH, lane_idx);
+            } else if all.contains_key(alias) {
                 layers.commit(SYM_COMMIT_BRANCH, lane_idx);
             } else {
                 layers.commit(SYM_COMMIT, lane_idx);
@@ -333,6 +348,54 @@ pub fn render_graph_range(
 
 }
 
+// Fans an octopus merge out to the lanes of all its merged parents, new lanes open past the last one
+fn render_octopus(layers: &mut LayersContext, chunk: &Chunk, last: &Vector<Chunk>) {
+    let mergee_idx = last.iter().position(|nested| nested.alias == chunk.alias).unwrap_or(0);
+    let mut next_idx = last.iter().rposition(|nested| !nested.is_dummy()).map_or(0, |idx| idx + 1);
+
+    // Lane of each merged parent, and whether it is opened by this merge
+    let targets: Vec<(usize, bool)> = chunk.merged_parents().map(|parent| {
+        match last.iter().position(|nested| nested.is_single_parent() && nested.parent_a == parent) {
+            Some(idx) => (idx, false),
+            None => {
+                next_idx += 1;
+                (next_idx - 1, true)
+            }
+        }
+    }).collect();
+
+    let leftmost = targets.iter().map(|&(idx, _)| idx).min().unwrap_or(mergee_idx).min(mergee_idx);
+    let rightmost = targets.iter().map(|&(idx, _)| idx).max().unwrap_or(mergee_idx).max(mergee_idx);
+
+    // Segments take the color of the next lane they lead to
+    let color_of = |idx: usize, is_gap: bool| {
+        let (lo, hi) = if is_gap { (idx + 1, idx) } else { (idx, idx) };
+        if idx < mergee_idx {
+            targets.iter().map(|&(target, _)| target).filter(|&target| target <= hi).max().unwrap_or(idx)
+        } else {
+            targets.iter().map(|&(target, _)| target).filter(|&target| target >= lo).min().unwrap_or(idx)
+        }
+    };
+
+    for idx in 0..=rightmost {
+        let target = targets.iter().find(|&&(target, _)| target == idx);
+        let symbol = match target {
+            _ if idx == mergee_idx => SYM_EMPTY,
+            Some(&(_, true)) if idx == rightmost => SYM_BRANCH_DOWN,
+            Some(&(_, false)) if idx == rightmost => SYM_MERGE_LEFT_FROM,
+            Some(_) if idx == leftmost => SYM_MERGE_RIGHT_FROM,
+            Some(&(_, true)) => SYM_MERGE_FAN,
+            Some(&(_, false)) => SYM_MERGE_CROSS,
+            None if idx > leftmost && idx < rightmost => SYM_HORIZONTAL,
+            None => SYM_EMPTY,
+        };
+        layers.merge(symbol, color_of(idx, false));
+
+        let gap = if idx >= leftmost && idx < rightmost { SYM_HORIZONTAL } else { SYM_EMPTY };
+        layers.merge(gap, color_of(idx, true));
+    }
+}
+
 pub fn remove_empty_columns(lines: &mut Vec<Line<'_>>) {
     let mut non_empty_counts: HashMap<usize, usize> = HashMap::new();
 
@@ -385,11 +448,10 @@ pub fn render_buffer_range(
     end: usize,
 ) -> Vec<Line<'static>> {
     
-    let mut idx = start;
     let mut lines_buffer: Vec<Line> = Vec::new();
 
     // Iterate over the selected snapshots
-    for snapshot in history.iter().skip(start + 1).take(end + 1 - start - 1) {
+    for (idx, snapshot) in (start..).zip(history.iter().skip(start + 1).take(end + 1 - start - 1)) {
         
         // Get the oid of the commit
         let oid = oids.get_oid_by_idx(idx);
@@ -409,12 +471,15 @@ pub fn render_buffer_range(
                     format!("{}", chunk.alias)
                 };
 
-                let parents_formatted = match (chunk.parent_a, chunk.parent_b) {
+                let mut parents_formatted = match (chunk.parent_a, chunk.parent_b) {
                     (NONE, NONE) => "--,--".to_string(),
                     (a, NONE) => format!("{:.2},--", a),
                     (NONE, b) => format!("--,{:.2}", b),
                     (a, b) => format!("{:.2},{:.2}", a, b),
                 };
+                for parent in &chunk.extra_parents {
+                    parents_formatted.push_str(&format!(",{:.2}", parent));
+                }
 
                 format!("{}({:<5})", &oid_str, parents_formatted)
             })
@@ -429,8 +494,6 @@ pub fn render_buffer_range(
 
         // Push back the line for the current snapshot
         lines_buffer.push(Line::from(spans));
-
-        idx += 1;
     }
 
     lines_buffer
@@ -444,12 +507,15 @@ pub fn render_message_range(
     local: &HashMap<u32, Vec<String>>,
     visible: &HashMap<u32, Vec<String>>,
     tags: &HashMap<u32, Vec<String>>,
+    stashes: &HashMap<u32, Vec<String>>,
     branch_colors: &mut HashMap<u32, Color>,
     tag_colors: &mut HashMap<u32, Color>,
+    stash_colors: &mut HashMap<u32, Color>,
     start: usize,
     end: usize,
     selected: usize,
     uncommitted: &UncommittedChanges,
+    search: Option<&Search>,
 ) -> Vec<Line<'static>> {
     let mut lines: Vec<Line> = Vec::new();
 
@@ -460,7 +526,7 @@ pub fn render_message_range(
 
         if alias != NONE {
             let oid = oids.get_oid_by_alias(alias);
-            let commit = repo.find_commit(*oid).unwrap();
+            let summary = repo.find_commit(*oid).ok().and_then(|commit| commit.summary().map(str::to_string));
 
             if let Some(visible) = visible.get(&alias) {
                 for branch in visible {
@@ -507,14 +573,32 @@ pub fn render_message_range(
                 }
             }
 
-            spans.push(Span::styled(
-                commit.summary().unwrap_or("⊘ no message").to_string(),
-                Style::default().fg(if global_idx == selected {
-                    theme.COLOR_GREY_500
-                } else {
-                    theme.COLOR_TEXT
-                }),
-            ));
+            if let Some(stashes) = stashes.get(&alias) {
+                for stash in stashes {
+                    // Render stashes
+                    spans.push(Span::styled(
+                        format!("{} {} ", SYM_STASH, stash),
+                        Style::default().fg(if let Some(color) = stash_colors.get(&alias) {
+                            *color
+                        } else {
+                            theme.COLOR_TEXT
+                        }),
+                    ));
+                }
+            }
+
+            let summary = summary.unwrap_or("⊘ no message".to_string());
+            let style = Style::default().fg(if global_idx == selected {
+                theme.COLOR_GREY_500
+            } else {
+                theme.COLOR_TEXT
+            });
+
+            // Search matches stand out, with the query underlined when it is in the summary
+            match search.filter(|search| search.matches.contains(&alias)) {
+                Some(search) => spans.extend(highlight_query(summary, &search.query, style.fg(theme.COLOR_YELLOW))),
+                None => spans.push(Span::styled(summary, style)),
+            }
 
             lines.push(Line::from(spans));
         } else {
@@ -551,6 +635,25 @@ pub fn render_message_range(
     lines
 }
 
+// Split a text around the first case insensitive occurrence of a query
+fn highlight_query(text: String, query: &str, style: Style) -> Vec<Span<'static>> {
+    let lowercase = text.to_lowercase();
+    let needle = query.to_lowercase();
+
+    // Lowercasing can shift byte offsets outside of ascii, leave those texts whole
+    let found = if lowercase.len() == text.len() { lowercase.find(&needle) } else { None };
+    match found.map(|start| (start, start + needle.len())) {
+        Some((start, end)) if text.is_char_boundary(start) && text.is_char_boundary(end) => {
+            vec![
+                Span::styled(text[..start].to_string(), style),
+                Span::styled(text[start..end].to_string(), style.add_modifier(Modifier::UNDERLINED)),
+                Span::styled(text[end..].to_string(), style),
+            ]
+        }
+        _ => vec![Span::styled(text, style)],
+    }
+}
+
 pub fn render_keybindings(theme: &Theme, keymap: &IndexMap<KeyBinding, Command>, width: usize) -> Vec<Line<'static>> {
     keymap.iter().map(|(kb, cmd)| {
         // Build key string
@@ -582,3 +685,47 @@ pub fn render_keybindings(theme: &Theme, keymap: &IndexMap<KeyBinding, Command>,
         ]).alignment(ratatui::layout::Alignment::Center)
     }).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::buffer::Buffer;
+
+    // Draws the fan out of the octopus on top of the lanes the buffer holds once it is reached
+    fn draw_octopus(commits: &[(u32, &[u32])]) -> String {
+        let mut buffer = Buffer::default();
+        for &(alias, parents) in commits {
+            buffer.update(Chunk::commit(alias, parents));
+        }
+        let (alias, _) = commits.last().unwrap();
+        let chunk = buffer.curr.iter().find(|chunk| chunk.alias == *alias).cloned().unwrap();
+
+        let mut layers = layers!(Rc::new(RefCell::new(ColorPicker::from_theme(&Theme::default()))));
+        render_octopus(&mut layers, &chunk, &buffer.curr);
+        let mut spans = Vec::new();
+        layers.bake(&mut spans);
+        spans.iter().map(|span| span.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn octopus_of_three_opens_two_lanes() {
+        assert_eq!(draw_octopus(&[(0, &[1, 2, 3])]), " ─┬─╮");
+    }
+
+    #[test]
+    fn octopus_of_five_opens_four_lanes() {
+        assert_eq!(draw_octopus(&[(9, &[0]), (0, &[1, 2, 3, 4, 5])]), " ─┬─┬─┬─╮");
+    }
+
+    #[test]
+    fn octopus_joins_an_awaited_lane() {
+        // The first lane already waits on parent 2, so the merge reaches left to it and opens a lane for 3
+        assert_eq!(draw_octopus(&[(8, &[2]), (0, &[1, 2, 3])]), "╭─ ─╮");
+    }
+
+    #[test]
+    fn octopus_crosses_an_awaited_lane() {
+        // Parent 2 waits on the second lane, the line to the new lane for 3 passes through it
+        assert_eq!(draw_octopus(&[(9, &[0]), (7, &[2]), (0, &[1, 2, 3])]), " ─┼─╮");
+    }
+}
@@ -0,0 +1,147 @@
+#[rustfmt::skip]
+use std::{
+    thread,
+    collections::{
+        HashSet
+    },
+    sync::{
+        Arc,
+        atomic::{
+            AtomicBool,
+            Ordering
+        },
+        mpsc::{
+            channel,
+            Receiver
+        }
+    }
+};
+#[rustfmt::skip]
+use git2::{
+    Commit,
+    DiffFormat,
+    Oid,
+    Repository
+};
+
+// How many commits to look at before handing the matches over
+const BATCH_SIZE: usize = 256;
+
+// A search running in the background over the walked commits
+pub struct Search {
+    pub query: String,
+    pub matches: HashSet<u32>,
+    cancel: Arc<AtomicBool>,
+    rx: Receiver<Vec<u32>>,
+    handle: thread::JoinHandle<()>,
+}
+
+impl Search {
+    // Look through the candidates in the given order, diffs only when asked since they are slow
+    pub fn start(path: String, candidates: Vec<(u32, Oid)>, query: String, is_pickaxe: bool) -> Self {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let cancel_clone = cancel.clone();
+        let (tx, rx) = channel();
+        let needle = query.to_lowercase();
+
+        let handle = thread::spawn(move || {
+            let Ok(repo) = Repository::open(&path) else {
+                return;
+            };
+
+            for batch in candidates.chunks(BATCH_SIZE) {
+                if cancel_clone.load(Ordering::SeqCst) {
+                    return;
+                }
+                let found: Vec<u32> = batch
+                    .iter()
+                    .filter(|(_, oid)| is_match(&repo, *oid, &needle, is_pickaxe))
+                    .map(|(alias, _)| *alias)
+                    .collect();
+                if !found.is_empty() && tx.send(found).is_err() {
+                    return;
+                }
+            }
+        });
+
+        Self {
+            query,
+            matches: HashSet::new(),
+            cancel,
+            rx,
+            handle,
+        }
+    }
+
+    // Collect what the thread found so far, telling whether anything new came in
+    pub fn sync(&mut self) -> bool {
+        let mut is_updated = false;
+        while let Ok(found) = self.rx.try_recv() {
+            self.matches.extend(found);
+            is_updated = true;
+        }
+        is_updated
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.handle.is_finished()
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::SeqCst);
+    }
+}
+
+impl Drop for Search {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+// Match the sha prefix, the message and both signatures, then optionally the changed lines
+fn is_match(repo: &Repository, oid: Oid, needle: &str, is_pickaxe: bool) -> bool {
+    if oid.to_string().starts_with(needle) {
+        return true;
+    }
+    let Ok(commit) = repo.find_commit(oid) else {
+        return false;
+    };
+    let author = commit.author();
+    let committer = commit.committer();
+    let fields = [
+        commit.message(),
+        author.name(),
+        author.email(),
+        committer.name(),
+        committer.email(),
+    ];
+    if fields.iter().flatten().any(|field| field.to_lowercase().contains(needle)) {
+        return true;
+    }
+    is_pickaxe && is_diff_match(repo, &commit, needle)
+}
+
+// Whether a line added or removed by the commit contains the needle, like git log -G which skips merges too
+fn is_diff_match(repo: &Repository, commit: &Commit, needle: &str) -> bool {
+    if commit.parent_count() > 1 {
+        return false;
+    }
+    let Ok(tree) = commit.tree() else {
+        return false;
+    };
+    let parent_tree = commit.parent(0).ok().and_then(|parent| parent.tree().ok());
+    let Ok(diff) = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None) else {
+        return false;
+    };
+
+    let mut is_found = false;
+    let _ = diff.print(DiffFormat::Patch, |_, _, line| {
+        if matches!(line.origin(), '+' | '-') && String::from_utf8_lossy(line.content()).to_lowercase().contains(needle) {
+            is_found = true;
+        }
+
+        // Stop printing once found
+        !is_found
+    });
+    is_found
+}
@@ -0,0 +1,101 @@
+#[rustfmt::skip]
+use std::{
+    cell::{
+        RefCell
+    },
+    rc::Rc,
+    collections::{
+        HashMap
+    },
+};
+#[rustfmt::skip]
+use ratatui::{
+    style::{
+        Color
+    }
+};
+#[rustfmt::skip]
+use crate::{
+    core::{
+        oids::{
+            Oids
+        }
+    },
+    helpers::{
+        palette::{
+            Theme
+        },
+        colors::{
+            ColorPicker
+        }
+    }
+};
+
+#[derive(Default)]
+pub struct Stashes {
+    pub local: HashMap<u32, Vec<String>>,
+    pub colors: HashMap<u32, Color>,
+    pub sorted: Vec<(u32, String)>,
+    pub indices: Vec<usize>,
+}
+
+impl Stashes {
+
+    pub fn feed(
+        &mut self,
+        oids: &Oids,
+        color: &Rc<RefCell<ColorPicker>>,
+        stashes_lanes: &HashMap<u32, usize>,
+        stashes_local: HashMap<u32, Vec<String>>,
+    ) {
+
+        // Initialize
+        self.local = stashes_local;
+        self.colors = HashMap::new();
+        self.indices = Vec::new();
+
+        // Stash tuple vectors
+        let mut sorted: Vec<(u32, String)> = self.local.iter().flat_map(|(&alias, stashes)| {
+                stashes.iter().map(move |stash| (alias, stash.clone()))
+            }).collect();
+
+        // Sorting tuples by their position in the stash list, so that the position matches stash@{n}
+        sorted.sort_by_key(|(_, stash)| Self::parse_index(stash));
+        self.sorted = sorted;
+
+        // Set stash colors
+        for (oidi, &lane_idx) in stashes_lanes.iter() {
+            self.colors.insert(*oidi, color.borrow().get_lane(lane_idx));
+        }
+
+        // Build a lookup of stash aliases to positions in sorted aliases
+        let index_map: HashMap<u32, usize> = oids.get_sorted_aliases().iter().enumerate().map(|(i, &oidi)| (oidi, i)).collect();
+        self.sorted.iter().for_each(|(oidi, _)| {
+            self.indices.push(index_map.get(oidi).copied().unwrap_or(usize::MAX));
+        });
+    }
+
+    pub fn get_sorted_aliases(&self) -> &Vec<(u32, String)> {
+        &self.sorted
+    }
+
+    pub fn get_color(&self, theme: &Theme, stash_alias: &u32) -> Color {
+        *self.colors.get(stash_alias).unwrap_or(&theme.COLOR_TEXT)
+    }
+
+    // Get the stash@{n} index of a stash commit, if the alias is one
+    pub fn get_index_by_alias(&self, stash_alias: &u32) -> Option<usize> {
+        self.local
+            .get(stash_alias)
+            .and_then(|stashes| stashes.first())
+            .map(|stash| Self::parse_index(stash))
+    }
+
+    fn parse_index(stash: &str) -> usize {
+        stash
+            .strip_prefix("stash@{")
+            .and_then(|s| s.strip_suffix('}'))
+            .and_then(|s| s.parse::<usize>().ok())
+            .unwrap_or(usize::MAX)
+    }
+}
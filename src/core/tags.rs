@@ -31,25 +31,12 @@ use crate::{
     }
 };
 
+#[derive(Default)]
 pub struct Tags {
     pub local: HashMap<u32, Vec<String>>,
     pub colors: HashMap<u32, Color>,
     pub sorted: Vec<(u32, String)>,
     pub indices: Vec<usize>,
-    pub visible: bool
-}
-
-impl Default for Tags {
-
-    fn default() -> Self {
-        Self {
-            local: Default::default(),
-            colors: Default::default(),
-            sorted: Default::default(),
-            indices: Default::default(),
-            visible: Default::default(),
-        }
-    }
 }
 
 impl Tags {
@@ -68,13 +55,14 @@ impl Tags {
         self.sorted = Vec::new();
         self.indices = Vec::new();
         
-        // Branch tuple vectors
+        // Tag tuple vectors
         let mut sorted: Vec<(u32, String)> = self.local.iter().flat_map(|(&alias, tags)| {
                 tags.iter().map(move |tag| (alias, tag.clone()))
             }).collect();
 
         // Sorting tuples
         sorted.sort_by(|a, b| a.1.cmp(&b.1));
+        self.sorted = sorted;
 
         // Set tag colors
         for (oidi, &lane_idx) in tags_lanes.iter() {
@@ -101,10 +89,4 @@ impl Tags {
         *self.colors.get(tag_alias).unwrap_or(&theme.COLOR_TEXT)
     }
 
-    pub fn is_local(&self, tag_name: &String) -> bool {
-        self.local
-            .values()
-            .any(|tags| tags.iter().any(|current_tag| current_tag.as_str() == tag_name))
-    }
-
 }
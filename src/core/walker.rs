@@ -6,6 +6,7 @@ use std::{
     },
     collections::{
         HashMap,
+        HashSet
     }
 };
 #[rustfmt::skip]
@@ -24,10 +25,15 @@ use crate::{
         },
         chunk::{
             Chunk,
+            Markers,
             NONE
         },
         batcher::{
             Batcher
+        },
+        error::{
+            AppResult,
+            Context
         }
     },
     git::{
@@ -35,7 +41,9 @@ use crate::{
             commits::{
                 get_sorted_oids,
                 get_tip_oids,
-                get_tag_oids
+                get_tag_oids,
+                get_stash_oids,
+                get_stash_internal_oids
             }
         }
     },
@@ -62,8 +70,13 @@ pub struct Walker {
     pub tags_lanes: HashMap<u32, usize>,
     pub tags_local: HashMap<u32, Vec<String>>,
 
+    pub stashes_lanes: HashMap<u32, usize>,
+    pub stashes_local: HashMap<u32, Vec<String>>,
+    pub stashes_internal: HashSet<u32>,
+
     // Batching
-    pub amount: usize
+    pub amount: usize,
+    pub first_parent: bool
 }
 
 // Output structure for walk results
@@ -82,6 +95,9 @@ pub struct WalkerOutput {
     pub tags_lanes: HashMap<u32, usize>,
     pub tags_local: HashMap<u32, Vec<String>>,
 
+    pub stashes_lanes: HashMap<u32, usize>,
+    pub stashes_local: HashMap<u32, Vec<String>>,
+
     // Batching
     pub is_again: bool,
     pub is_first: bool
@@ -93,9 +109,11 @@ impl Walker {
         path: String,
         amount: usize,
         visible: HashMap<u32, Vec<String>>,
-    ) -> Result<Self, git2::Error> {
-        let path = path.clone();
-        let repo = Rc::new(Repository::open(path).expect("Failed to open repo"));
+        first_parent: bool,
+        range: Option<String>,
+        pathspec: Option<String>,
+    ) -> AppResult<Self> {
+        let repo = Rc::new(Repository::open(&path).context(format!("opening {}", path))?);
         
         // Walker utilities
         let buffer = RefCell::new(Buffer::default());
@@ -103,13 +121,17 @@ impl Walker {
         // Walker data
         let mut oids = Oids::default();
         let branches_lanes = HashMap::new();
-        let (branches_local, branches_remote) = get_tip_oids(&repo, &mut oids);
+        let (branches_local, branches_remote) = get_tip_oids(&repo, &mut oids)?;
 
         let tags_lanes = HashMap::new();
-        let tags_local = get_tag_oids(&repo, &mut oids);
+        let tags_local = get_tag_oids(&repo, &mut oids)?;
+
+        let stashes_lanes = HashMap::new();
+        let stashes_local = get_stash_oids(&repo, &mut oids);
+        let stashes_internal = get_stash_internal_oids(&repo, &mut oids);
         
         // Batcher
-        let batcher = Batcher::new(repo.clone(), visible, &mut oids).expect("Error");
+        let batcher = Batcher::new(repo.clone(), visible, &mut oids, first_parent, range, pathspec).context("walking the history")?;
 
         Ok(Self {
             repo,
@@ -127,20 +149,24 @@ impl Walker {
             branches_remote,
             tags_lanes,
             tags_local,
+            stashes_lanes,
+            stashes_local,
+            stashes_internal,
 
             // Pagination
-            amount
+            amount,
+            first_parent
         })
     }
 
     // Walk through "amount" commits, update buffers and render lines
     pub fn walk(&mut self) -> bool {
 
-        // Determine current HEAD oid
-        let head_oid = self.repo.head().unwrap().target().unwrap();
+        // Determine current HEAD oid, an unborn branch has none yet
+        let head_oid = self.repo.head().ok().and_then(|head| head.target());
 
         // Get the alias
-        let head_alias = self.oids.get_alias_by_oid(head_oid);
+        let head_alias = head_oid.map_or(NONE, |oid| self.oids.get_alias_by_oid(oid));
 
         // Sort commits
         let mut sorted_batch: Vec<u32> = Vec::new();
@@ -160,20 +186,34 @@ impl Walker {
 
         // Go through the commits, inferring the graph
         for &alias in sorted_batch.iter() {
-            let mut merger_alias: u32 = NONE;
-            let oid = self.oids.get_oid_by_alias(alias);
-            let commit = self.repo.find_commit(*oid).unwrap();
-            let parents: Vec<Oid> = commit.parent_ids().collect();
 
-            // Gat parent aliases
-            let parent_a = if let Some(parent) = parents.first() {
-                self.oids.get_alias_by_oid(*parent)
-            } else { NONE };
-            let parent_b = if let Some(parent) = parents.get(1) {
-                self.oids.get_alias_by_oid(*parent)
-            } else { NONE };
+            // Skip the helper commits stashes are made of
+            if self.stashes_internal.contains(&alias) {
+                continue;
+            }
 
-            let chunk = Chunk::commit(alias, parent_a, parent_b);
+            let mut merger_alias: u32 = NONE;
+            let oid = self.oids.get_oid_by_alias(alias);
+            let Ok(commit) = self.repo.find_commit(*oid) else {
+                continue;
+            };
+            let parents: Vec<Oid> = self.batcher.rewrite_parents(commit.parent_ids().collect());
+
+            // Gat parent aliases, a stash only follows the commit it was made on
+            let is_stash = self.stashes_local.contains_key(&alias);
+            let parents_count = if self.first_parent || is_stash { 1 } else { parents.len() };
+            let parent_aliases: Vec<u32> = parents
+                .iter()
+                .take(parents_count)
+                .map(|parent| self.oids.get_alias_by_oid(*parent))
+                .collect();
+
+            let mut chunk = Chunk::commit(alias, &parent_aliases);
+
+            // Merges collapse into a single node in first parent mode
+            if self.first_parent && !is_stash && parents.len() > 1 {
+                chunk.marker = Markers::Merge;
+            }
 
             let mut is_commit_found = false;
             let mut lane_idx = 0;
@@ -193,16 +233,18 @@ impl Walker {
                         self.tags_lanes.insert(alias, lane_idx);
                     }
 
+                    if self.stashes_local.contains_key(&alias) {
+                        self.stashes_lanes.insert(alias, lane_idx);
+                    }
+
+                    // Every merged parent not awaited on another lane needs one of its own
                     if chunk.parent_a != NONE && chunk.parent_b != NONE {
-                        let mut is_merger_found = false;
-                        for chunk_nested in &self.buffer.borrow().curr {
-                            if chunk_nested.parent_a != NONE && chunk_nested.parent_b == NONE
-                                && chunk.parent_b == chunk_nested.parent_a
-                            {
-                                is_merger_found = true;
-                                break;
-                            }
-                        }
+                        let is_merger_found = chunk.merged_parents().all(|parent| {
+                            self.buffer.borrow().curr.iter().any(|chunk_nested| {
+                                chunk_nested.parent_a != NONE && chunk_nested.parent_b == NONE
+                                    && parent == chunk_nested.parent_a
+                            })
+                        });
                         if !is_merger_found {
                             merger_alias = chunk.alias;
                         }
@@ -218,6 +260,10 @@ impl Walker {
                 if self.tags_local.contains_key(&alias) {
                     self.tags_lanes.insert(alias, lane_idx);
                 }
+
+                if self.stashes_local.contains_key(&alias) {
+                    self.stashes_lanes.insert(alias, lane_idx);
+                }
             }
 
             // Now we can borrow mutably
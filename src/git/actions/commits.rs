@@ -8,8 +8,6 @@ use std::{
 #[rustfmt::skip]
 use git2::{
     Oid,
-    Cred,
-    RemoteCallbacks,
     Error,
     ErrorCode,
     Signature,
@@ -17,23 +15,44 @@ use git2::{
     BranchType,
     ResetType,
     Repository,
+    Remote,
+    Direction,
     FetchOptions,
+    FetchPrune,
+    AutotagOption,
     PushOptions,
+    CherrypickOptions,
+    RevertOptions,
     build::CheckoutBuilder
 };
+#[rustfmt::skip]
+use crate::{
+    core::error::{
+        AppError,
+        AppResult,
+        Context
+    },
+    git::{
+        credentials::Credentials,
+        network::{
+            Progress,
+            cancelled
+        }
+    }
+};
 
-pub fn checkout_head(repo: &Repository, oid: Oid) {
+pub fn checkout_head(repo: &Repository, oid: Oid) -> AppResult<()> {
     // Find the commit object
-    let commit = repo.find_commit(oid).unwrap();
+    let commit = repo.find_commit(oid).context(format!("finding #{:.6}", oid))?;
 
     // Set HEAD to the commit (detached)
-    repo.set_head_detached(commit.id()).unwrap();
+    repo.set_head_detached(commit.id()).context(format!("detaching HEAD at #{:.6}", oid))?;
 
     // Checkout the commit
     repo.checkout_head(Some(
         CheckoutBuilder::default().allow_conflicts(true).force(), // optional: force overwrite local changes
     ))
-    .expect("Error checking out");
+    .context(format!("checking out #{:.6}", oid))
 }
 
 pub fn checkout_branch(
@@ -42,12 +61,14 @@ pub fn checkout_branch(
     local: &mut HashMap<u32, Vec<String>>,
     alias: u32,
     branch_name: &str,
-) -> Result<(), git2::Error> {
+) -> AppResult<()> {
     // Helper to checkout a local branch
-    fn checkout(repo: &Repository, branch_name: &str) -> Result<(), git2::Error> {
+    fn checkout(repo: &Repository, branch_name: &str) -> AppResult<()> {
         let branch = repo.find_branch(branch_name, BranchType::Local)?;
-        repo.set_head(branch.get().name().unwrap())?;
+        let refname = branch.get().name().context(format!("{} is not valid utf-8", branch_name))?;
+        repo.set_head(refname)?;
         repo.checkout_head(Some(CheckoutBuilder::default().allow_conflicts(true).force()))
+            .context(format!("checking out {}", branch_name))
     }
 
     // If branch_name already exists as a local branch, checkout directly
@@ -81,10 +102,10 @@ pub fn checkout_branch(
 
     Err(git2::Error::from_str(
         "No matching local or remote branch found for the given Oid",
-    ))
+    ).into())
 }
 
-pub fn git_add_all(repo: &Repository) -> Result<(), Error> {
+pub fn git_add_all(repo: &Repository) -> AppResult<()> {
     let mut index = repo.index()?;
 
     let mut opts = StatusOptions::new();
@@ -123,7 +144,7 @@ pub fn commit_staged(
     message: &str,
     name: &str,
     email: &str,
-) -> Result<Oid, Error> {
+) -> AppResult<Oid> {
     let mut index = repo.index()?;
     let tree_oid = index.write_tree()?;
     let tree = repo.find_tree(tree_oid)?;
@@ -138,7 +159,7 @@ pub fn commit_staged(
             if e.code() == ErrorCode::UnbornBranch {
                 None // empty repo, initial commit
             } else {
-                return Err(e);
+                return Err(AppError::new("reading HEAD", e));
             }
         }
     };
@@ -162,7 +183,73 @@ pub fn commit_staged(
     Ok(commit_oid)
 }
 
-pub fn reset_to_commit(repo: &Repository, target: Oid, reset_type: ResetType) -> Result<(), Error> {
+// Rewrites HEAD with the staged changes and a new message, keeping its author
+pub fn amend_head(repo: &Repository, message: &str, name: &str, email: &str) -> AppResult<Oid> {
+    let mut index = repo.index()?;
+    index.read(false)?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+
+    let head = repo.head()?.peel_to_commit()?;
+    let committer = Signature::now(name, email)?;
+    head.amend(Some("HEAD"), None, Some(&committer), None, Some(message), Some(&tree))
+        .context(format!("amending #{:.6}", head.id()))
+}
+
+// Rewrites the message of a commit on HEAD and recreates every commit after it
+pub fn reword_commit(repo: &Repository, oid: Oid, message: &str, name: &str, email: &str) -> AppResult<Oid> {
+    let head = repo.head()?;
+    let head_oid = head.peel_to_commit()?.id();
+    if head_oid != oid && !repo.graph_descendant_of(head_oid, oid)? {
+        return Err(Error::from_str("Only commits on the current branch can be reworded").into());
+    }
+
+    let committer = Signature::now(name, email)?;
+    let commit = repo.find_commit(oid)?;
+    let parents: Vec<_> = commit.parents().collect();
+    let parents: Vec<_> = parents.iter().collect();
+    let reworded = repo.commit(None, &commit.author(), &committer, message, &commit.tree()?, &parents)?;
+
+    // Trees stay the same, so descendants are recreated without touching the working directory
+    let mut rewritten: HashMap<Oid, Oid> = HashMap::from([(oid, reworded)]);
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(head_oid)?;
+    revwalk.hide(oid)?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+    for descendant in revwalk {
+        let descendant = repo.find_commit(descendant?)?;
+
+        // Commits merged in from elsewhere keep their identity
+        if !descendant.parent_ids().any(|parent| rewritten.contains_key(&parent)) {
+            continue;
+        }
+        let parents = descendant
+            .parent_ids()
+            .map(|parent| repo.find_commit(*rewritten.get(&parent).unwrap_or(&parent)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let parents: Vec<_> = parents.iter().collect();
+        let recreated = repo.commit(
+            None,
+            &descendant.author(),
+            &committer,
+            descendant.message_raw().unwrap_or(""),
+            &descendant.tree()?,
+            &parents,
+        )?;
+        rewritten.insert(descendant.id(), recreated);
+    }
+
+    let result = *rewritten.get(&head_oid).unwrap_or(&reworded);
+    if head.is_branch() {
+        let name = head.name().ok_or_else(|| Error::from_str("Invalid branch reference name"))?;
+        repo.find_reference(name)?.set_target(result, "reword")?;
+    } else {
+        repo.set_head_detached(result)?;
+    }
+
+    Ok(result)
+}
+
+pub fn reset_to_commit(repo: &Repository, target: Oid, reset_type: ResetType) -> AppResult<()> {
     // Resolve the target commit object
     let target_commit = repo.find_commit(target)?;
 
@@ -189,7 +276,110 @@ pub fn reset_to_commit(repo: &Repository, target: Oid, reset_type: ResetType) ->
     Ok(())
 }
 
-pub fn unstage_all(repo: &Repository) -> Result<(), git2::Error> {
+// Cherry-picks a commit onto HEAD, returns true if it stopped on conflicts
+pub fn cherry_pick(repo: &Repository, oid: Oid, name: &str, email: &str) -> AppResult<bool> {
+    let commit = repo.find_commit(oid)?;
+    if commit.parent_count() > 1 {
+        return Err(Error::from_str(&format!("#{:.6} is a merge, pick the commits it brought in instead", oid)).into());
+    }
+
+    let mut options = CherrypickOptions::new();
+
+    // Leave conflict markers in the working directory for the user to resolve
+    let mut checkout = CheckoutBuilder::default();
+    checkout.allow_conflicts(true).conflict_style_merge(true);
+    options.checkout_builder(checkout);
+
+    repo.cherrypick(&commit, Some(&mut options))?;
+
+    if repo.index()?.has_conflicts() {
+        return Ok(true);
+    }
+
+    cherry_pick_commit(repo, oid, name, email)?;
+    Ok(false)
+}
+
+// Commits the result of a cherry-pick, keeping the original author and message
+pub fn cherry_pick_commit(repo: &Repository, oid: Oid, name: &str, email: &str) -> AppResult<Oid> {
+    let commit = repo.find_commit(oid)?;
+
+    // Pick up conflicts resolved outside of the app
+    let mut index = repo.index()?;
+    index.read(false)?;
+    if index.has_conflicts() {
+        return Err(Error::from_str("Resolve and stage the conflicted files first").into());
+    }
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let head = repo.head()?.peel_to_commit()?;
+
+    // Nothing left to commit if the changes are already on HEAD
+    if tree.id() == head.tree_id() {
+        repo.cleanup_state()?;
+        return Ok(head.id());
+    }
+
+    let committer = Signature::now(name, email)?;
+    let commit_oid = repo.commit(
+        Some("HEAD"),
+        &commit.author(),
+        &committer,
+        commit.message().unwrap_or(""),
+        &tree,
+        &[&head],
+    )?;
+    repo.cleanup_state()?;
+
+    Ok(commit_oid)
+}
+
+// Reverts a commit on top of HEAD, merges need the parent to revert against, returns true if it stopped on conflicts
+pub fn revert(repo: &Repository, oid: Oid, mainline: u32) -> AppResult<bool> {
+    let commit = repo.find_commit(oid)?;
+
+    let mut options = RevertOptions::new();
+    if commit.parent_count() > 1 {
+        if mainline == 0 {
+            return Err(Error::from_str("Pick a mainline parent to revert a merge against").into());
+        }
+        options.mainline(mainline);
+    }
+
+    // Leave conflict markers in the working directory for the user to resolve
+    let mut checkout = CheckoutBuilder::default();
+    checkout.allow_conflicts(true).conflict_style_merge(true);
+    options.checkout_builder(checkout);
+
+    repo.revert(&commit, Some(&mut options))?;
+
+    Ok(repo.index()?.has_conflicts())
+}
+
+// Commits the result of a revert with the given message
+pub fn commit_revert(repo: &Repository, message: &str, name: &str, email: &str) -> AppResult<Oid> {
+    // Pick up conflicts resolved outside of the app
+    let mut index = repo.index()?;
+    index.read(false)?;
+    if index.has_conflicts() {
+        return Err(Error::from_str("Resolve and stage the conflicted files first").into());
+    }
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let head = repo.head()?.peel_to_commit()?;
+
+    let signature = Signature::now(name, email)?;
+    let commit_oid = repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &[&head])?;
+    repo.cleanup_state()?;
+
+    Ok(commit_oid)
+}
+
+// Throws away an operation in progress and resets back to the given commit
+pub fn abort_operation(repo: &Repository, target: Oid) -> AppResult<()> {
+    reset_to_commit(repo, target, ResetType::Hard)?;
+    repo.cleanup_state().context("cleaning up the repository state")
+}
+
+pub fn unstage_all(repo: &Repository) -> AppResult<()> {
     // Get HEAD commit
     let head = match repo.head() {
         Ok(head) => head.peel_to_commit()?,
@@ -205,90 +395,208 @@ pub fn unstage_all(repo: &Repository) -> Result<(), git2::Error> {
     Ok(())
 }
 
+// Stages a single file, or its deletion
+pub fn stage_file(repo: &Repository, path: &str) -> AppResult<()> {
+    let mut index = repo.index()?;
+    index.read(false)?;
+
+    let is_present = repo.workdir().is_some_and(|root| root.join(path).exists());
+    if is_present {
+        index.add_all([path], git2::IndexAddOption::DEFAULT, None)?;
+    } else {
+        index.remove_all([path], None)?;
+    }
+    index.write().context("writing the index")
+}
+
+// Resets a single file in the index back to HEAD, keeping the working directory
+pub fn unstage_file(repo: &Repository, path: &str) -> AppResult<()> {
+    match repo.head().and_then(|head| head.peel(git2::ObjectType::Commit)) {
+        Ok(head) => repo.reset_default(Some(&head), [path]).context(format!("resetting {}", path)),
+        Err(_) => {
+            // No HEAD yet (fresh repo), unstaging means dropping it from the index
+            let mut index = repo.index()?;
+            index.remove_path(std::path::Path::new(path))?;
+            index.write().context("writing the index")
+        }
+    }
+}
+
+// Throws away the working directory changes of a file, restoring it from the index
+pub fn discard_file(repo: &Repository, path: &str) -> AppResult<()> {
+    let mut checkout = CheckoutBuilder::new();
+    checkout.force().path(path);
+    repo.checkout_index(None, Some(&mut checkout)).context(format!("restoring {}", path))
+}
+
+// Removes an untracked file or directory from the working directory
+pub fn delete_untracked(repo: &Repository, path: &str) -> AppResult<()> {
+    let full_path = repo.workdir()
+        .map(|root| root.join(path))
+        .ok_or_else(|| Error::from_str("Bare repositories have no working directory"))?;
+    let result = if full_path.is_dir() {
+        std::fs::remove_dir_all(full_path)
+    } else {
+        std::fs::remove_file(full_path)
+    };
+    result.map_err(|e| Error::from_str(&e.to_string())).context(format!("removing {}", path))
+}
+
+// Fetches remotes one after another using their configured refspecs
 pub fn fetch_over_ssh(
     repo_path: &str,
-    remote_name: &str,
-) -> thread::JoinHandle<Result<(), git2::Error>> {
+    remote_names: Vec<String>,
+    prune: bool,
+    all_tags: bool,
+    credentials: &Credentials,
+    progress: &Progress,
+) -> thread::JoinHandle<AppResult<()>> {
     // Clone the strings so the thread owns them
     let repo_path = repo_path.to_string();
-    let remote_name = remote_name.to_string();
+    let credentials = credentials.clone();
+    let progress = progress.clone();
 
     thread::spawn(move || {
         let repo = Repository::open(repo_path)?;
-        let mut remote = repo.find_remote(&remote_name)?;
-
-        let mut callbacks = RemoteCallbacks::new();
-        callbacks.credentials(|_url, username_from_url, _| {
-            Cred::ssh_key_from_agent(username_from_url.unwrap())
-        });
-
-        callbacks.transfer_progress(|_stats| {
-            // println!("Received {}/{} objects", stats.received_objects(), stats.total_objects());
-            true
-        });
 
-        let mut fetch_options = FetchOptions::new();
-        fetch_options.remote_callbacks(callbacks);
-
-        remote.fetch(
-            &["refs/heads/*:refs/remotes/origin/*"],
-            Some(&mut fetch_options),
-            None,
-        )?;
+        for remote_name in remote_names {
+            if progress.is_cancelled() {
+                return Err(cancelled().into());
+            }
+            let mut remote = repo.find_remote(&remote_name)?;
+
+            let mut callbacks = credentials.callbacks();
+            progress.attach(&mut callbacks);
+
+            // Unspecified falls back to fetch.prune, remote.<name>.prune and remote.<name>.tagOpt
+            let mut fetch_options = FetchOptions::new();
+            fetch_options.remote_callbacks(callbacks);
+            fetch_options.prune(if prune { FetchPrune::On } else { FetchPrune::Unspecified });
+            fetch_options.download_tags(if all_tags { AutotagOption::All } else { AutotagOption::Unspecified });
+
+            // Use the refspecs configured for the remote
+            remote.fetch(
+                &[] as &[&str],
+                Some(&mut fetch_options),
+                None,
+            )?;
+        }
         Ok(())
     })
 }
 
+// How a branch is pushed
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PushMode {
+    Normal,         // Fast-forward only, the remote refuses anything else
+    ForceWithLease, // Overwrite, but only while the remote still matches our tracking ref
+}
+
 pub fn push_over_ssh(
     repo_path: &str,
     remote_name: &str,
     branch: &str,
-    force: bool,
-) -> thread::JoinHandle<Result<(), git2::Error>> {
+    mode: PushMode,
+    credentials: &Credentials,
+    progress: &Progress,
+) -> thread::JoinHandle<AppResult<()>> {
     // Clone inputs so they can move into the thread safely
     let repo_path = repo_path.to_string();
     let remote_name = remote_name.to_string();
     let branch = branch.to_string();
+    let credentials = credentials.clone();
+    let progress = progress.clone();
 
     thread::spawn(move || {
         // Open the repository
         let repo = Repository::open(&repo_path)?;
         let mut remote = repo.find_remote(&remote_name)?;
+        let refname = format!("refs/heads/{}", branch);
 
-        // Configure SSH authentication
-        let mut callbacks = RemoteCallbacks::new();
-        callbacks.credentials(|_url, _username_from_url, _| Cred::ssh_key_from_agent("git"));
+        // Push to the tracked branch when it lives on this remote, under the same name otherwise
+        let upstream_remote = repo.branch_upstream_remote(&refname).ok().and_then(|name| name.as_str().map(|name| name.to_string()));
+        let is_tracked = upstream_remote.as_deref() == Some(remote_name.as_str());
+        let destination = if is_tracked {
+            repo.config()?.get_string(&format!("branch.{}.merge", branch))?
+        } else {
+            refname.clone()
+        };
 
-        // Track progress
-        callbacks.push_update_reference(|_refname, status| {
-            if let Some(_err) = status {
-                // eprintln!("Failed to update {refname}: {err}");
-            } else {
-                // println!("Updated {refname}");
+        // What we last saw of the destination, a missing tracking ref means it should not exist yet
+        let tracking = get_tracking_ref(&remote, &destination);
+        let lease = tracking.as_ref()
+            .and_then(|tracking| repo.refname_to_id(tracking).ok())
+            .unwrap_or_else(Oid::zero);
+
+        // Collect the refs the remote refused to update
+        let rejected = std::cell::RefCell::new(Vec::new());
+
+        // Authenticate with the chosen method
+        let mut callbacks = credentials.callbacks();
+        progress.attach(&mut callbacks);
+        callbacks.push_update_reference(|refname, status| {
+            if let Some(err) = status {
+                rejected.borrow_mut().push(format!("{}: {}", refname.trim_start_matches("refs/heads/"), err));
             }
             Ok(())
         });
 
+        // Refuse to overwrite work pushed by someone else since our last fetch
+        if mode == PushMode::ForceWithLease {
+            let progress = progress.clone();
+            let destination = destination.clone();
+            callbacks.push_negotiation(move |updates| {
+                if progress.is_cancelled() {
+                    return Err(cancelled());
+                }
+                for update in updates {
+                    if update.dst_refname() == Some(destination.as_str()) && update.src() != lease {
+                        return Err(Error::from_str(&format!(
+                            "stale info, {} moved on the remote since the last fetch",
+                            destination.trim_start_matches("refs/heads/")
+                        )));
+                    }
+                }
+                Ok(())
+            });
+        }
+
         // Configure push options
         let mut push_options = PushOptions::new();
         push_options.remote_callbacks(callbacks);
 
-        // The refspec tells Git what to push
-        let refspec = if force {
-            format!("+refs/heads/{0}:refs/heads/{0}", branch) // '+' means force
-        } else {
-            format!("refs/heads/{0}:refs/heads/{0}", branch)
+        // The refspec tells Git what to push, '+' means force
+        let refspec = match mode {
+            PushMode::Normal => format!("{}:{}", refname, destination),
+            PushMode::ForceWithLease => format!("+{}:{}", refname, destination),
         };
 
         // Perform the push
         remote.push(&[&refspec], Some(&mut push_options))?;
+        drop(push_options);
 
-        // println!("Push complete for branch '{}'", branch);
+        let rejected = rejected.into_inner();
+        if !rejected.is_empty() {
+            return Err(Error::from_str(&format!("rejected {}", rejected.join(", "))).into());
+        }
+
+        // Track the pushed branch when it did not track anything yet
+        if upstream_remote.is_none() && let Some(tracking) = tracking {
+            let mut local = repo.find_branch(&branch, BranchType::Local)?;
+            local.set_upstream(Some(tracking.trim_start_matches("refs/remotes/")))?;
+        }
         Ok(())
     })
 }
 
-pub fn create_branch(repo: &Repository, branch_name: &str, target_oid: Oid) -> Result<(), Error> {
+// Remote tracking ref a remote branch is fetched into, following the fetch refspecs
+fn get_tracking_ref(remote: &Remote, destination: &str) -> Option<String> {
+    remote.refspecs()
+        .filter(|refspec| refspec.direction() == Direction::Fetch && refspec.src_matches(destination))
+        .find_map(|refspec| refspec.transform(destination).ok().and_then(|name| name.as_str().map(|name| name.to_string())))
+}
+
+pub fn create_branch(repo: &Repository, branch_name: &str, target_oid: Oid) -> AppResult<()> {
     // Find the commit you want the branch to point to
     let target_commit = repo.find_commit(target_oid)?;
 
@@ -298,7 +606,70 @@ pub fn create_branch(repo: &Repository, branch_name: &str, target_oid: Oid) -> R
     Ok(())
 }
 
-pub fn delete_branch(repo: &Repository, branch: &str) -> Result<(), Error> {
+// Renames a local branch, its upstream configuration moves along with it
+pub fn rename_branch(repo: &Repository, old_name: &str, new_name: &str) -> AppResult<()> {
+    let mut branch = repo.find_branch(old_name, BranchType::Local)?;
+    branch.rename(new_name, false)?;
+
+    Ok(())
+}
+
+// Pushes a renamed branch under its new name, deletes the old one and tracks the new one
+pub fn rename_remote_branch(
+    repo_path: &str,
+    branch: &str,
+    credentials: &Credentials,
+    progress: &Progress,
+) -> thread::JoinHandle<AppResult<()>> {
+    let repo_path = repo_path.to_string();
+    let branch = branch.to_string();
+    let credentials = credentials.clone();
+    let progress = progress.clone();
+
+    thread::spawn(move || {
+        let repo = Repository::open(&repo_path)?;
+        let refname = format!("refs/heads/{}", branch);
+
+        // The old name lives on in the upstream configuration
+        let remote_name = repo.branch_upstream_remote(&refname)
+            .map_err(|_| Error::from_str(&format!("No upstream configured for '{}'", branch)))?;
+        let remote_name = remote_name.as_str().unwrap_or("origin").to_string();
+        let mut config = repo.config()?;
+        let merge = config.get_string(&format!("branch.{}.merge", branch))?;
+        let mut remote = repo.find_remote(&remote_name)?;
+
+        // Collect the refs the remote refused to update
+        let rejected = std::cell::RefCell::new(Vec::new());
+
+        // Authenticate with the chosen method
+        let mut callbacks = credentials.callbacks();
+        progress.attach(&mut callbacks);
+        callbacks.push_update_reference(|refname, status| {
+            if let Some(err) = status {
+                rejected.borrow_mut().push(format!("{}: {}", refname, err));
+            }
+            Ok(())
+        });
+
+        let mut push_options = PushOptions::new();
+        push_options.remote_callbacks(callbacks);
+
+        // Push the new name and delete the old one in one go
+        let refspecs = [format!("{0}:{0}", refname), format!(":{}", merge)];
+        remote.push(&refspecs, Some(&mut push_options))?;
+        drop(push_options);
+
+        let rejected = rejected.into_inner();
+        if !rejected.is_empty() {
+            return Err(Error::from_str(&rejected.join(", ")).into());
+        }
+
+        config.set_str(&format!("branch.{}.merge", branch), &refname)?;
+        Ok(())
+    })
+}
+
+pub fn delete_branch(repo: &Repository, branch: &str) -> AppResult<()> {
 
     // Try deleting as a local branch first
     if let Ok(mut local_branch) = repo.find_branch(branch, BranchType::Local) {
@@ -307,14 +678,12 @@ pub fn delete_branch(repo: &Repository, branch: &str) -> Result<(), Error> {
         local_branch.delete()?;
     } else {
 
-        // Delete remote-tracking branch (assume "origin" remote for now)
-        let ref_name = format!("refs/remotes/origin/{}", branch);
-
-        if let Ok(mut reference) = repo.find_reference(&ref_name) {
-            reference.delete()?;
+        // Delete the remote-tracking branch, named after its remote like origin/main
+        if let Ok(mut remote_branch) = repo.find_branch(branch, BranchType::Remote) {
+            remote_branch.delete()?;
         } else {
             // Branch not found locally or remotely
-            return Err(Error::from_str(&format!("Branch '{}' not found", branch)));
+            return Err(Error::from_str(&format!("Branch '{}' not found", branch)).into());
         }
     }
 
@@ -0,0 +1,76 @@
+#[rustfmt::skip]
+use std::{
+    path::{
+        Path,
+        PathBuf
+    }
+};
+#[rustfmt::skip]
+use git2::{
+    Error,
+    Repository
+};
+#[rustfmt::skip]
+use crate::{
+    core::{
+        error::{
+            AppResult,
+            Context
+        },
+        conflicts::{
+            ConflictChunk,
+            ConflictFile,
+            ConflictSide
+        }
+    }
+};
+
+// Writes the partially or fully resolved file to the working directory
+pub fn write_conflict_file(repo: &Repository, file: &ConflictFile) -> AppResult<()> {
+    std::fs::write(get_workdir_path(repo, &file.path)?, file.to_content())
+        .map_err(|e| Error::from_str(&e.to_string()))
+        .context(format!("writing {}", file.path))
+}
+
+// Takes one side for the whole file, removing it if that side deleted it
+pub fn take_conflict_side(repo: &Repository, file: &mut ConflictFile, side: ConflictSide) -> AppResult<()> {
+    let is_present = match side {
+        ConflictSide::Ours => file.is_ours,
+        ConflictSide::Theirs => file.is_theirs,
+    };
+
+    if !is_present {
+        let path = get_workdir_path(repo, &file.path)?;
+        if path.exists() {
+            std::fs::remove_file(path).map_err(|e| Error::from_str(&e.to_string())).context(format!("removing {}", file.path))?;
+        }
+        file.chunks.clear();
+        return Ok(());
+    }
+
+    for chunk in file.chunks.iter_mut() {
+        if let ConflictChunk::Conflict { resolution, .. } = chunk {
+            *resolution = Some(side);
+        }
+    }
+    write_conflict_file(repo, file)
+}
+
+// Marks a file as resolved by staging whatever is in the working directory
+pub fn mark_resolved(repo: &Repository, path: &str) -> AppResult<()> {
+    let mut index = repo.index()?;
+    index.read(false)?;
+
+    if get_workdir_path(repo, path)?.exists() {
+        index.add_path(Path::new(path))?;
+    } else {
+        index.remove_path(Path::new(path))?;
+    }
+    index.write().context("writing the index")
+}
+
+fn get_workdir_path(repo: &Repository, path: &str) -> AppResult<PathBuf> {
+    repo.workdir()
+        .map(|root| root.join(path))
+        .ok_or_else(|| Error::from_str("Bare repositories have no working directory").into())
+}
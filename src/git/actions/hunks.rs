@@ -0,0 +1,160 @@
+#[rustfmt::skip]
+use std::{
+    collections::{
+        HashSet
+    }
+};
+#[rustfmt::skip]
+use git2::{
+    ApplyLocation,
+    Delta,
+    Diff,
+    DiffOptions,
+    Error,
+    Patch,
+    Repository
+};
+#[rustfmt::skip]
+use crate::core::error::{
+    AppResult,
+    Context
+};
+
+// Signature shared by the line actions, so the viewer can pick one
+pub type LineAction = fn(&Repository, &str, &HashSet<(usize, usize)>) -> AppResult<()>;
+
+// Stages the selected lines of the unstaged diff, lines are (hunk, line) pairs
+pub fn stage_lines(repo: &Repository, filename: &str, selection: &HashSet<(usize, usize)>) -> AppResult<()> {
+    let diff = get_unstaged_diff(repo, filename)?;
+    let patch = build_patch(&diff, selection, false)?;
+    repo.apply(&patch, ApplyLocation::Index, None).context(format!("staging lines of {}", filename))
+}
+
+// Takes the selected lines of the staged diff back out of the index
+pub fn unstage_lines(repo: &Repository, filename: &str, selection: &HashSet<(usize, usize)>) -> AppResult<()> {
+    let diff = get_staged_diff(repo, filename)?;
+    let patch = build_patch(&diff, selection, true)?;
+    repo.apply(&patch, ApplyLocation::Index, None).context(format!("unstaging lines of {}", filename))
+}
+
+// Reverts the selected lines of the unstaged diff in the working directory
+pub fn discard_lines(repo: &Repository, filename: &str, selection: &HashSet<(usize, usize)>) -> AppResult<()> {
+    let diff = get_unstaged_diff(repo, filename)?;
+    let patch = build_patch(&diff, selection, true)?;
+    repo.apply(&patch, ApplyLocation::WorkDir, None).context(format!("discarding lines of {}", filename))
+}
+
+fn get_unstaged_diff<'a>(repo: &'a Repository, filename: &str) -> AppResult<Diff<'a>> {
+    let mut options = DiffOptions::new();
+    options.pathspec(filename).include_untracked(true).show_untracked_content(true);
+
+    // Pick up changes staged outside of the app
+    let mut index = repo.index()?;
+    index.read(false)?;
+    repo.diff_index_to_workdir(Some(&index), Some(&mut options)).context(format!("diffing {}", filename))
+}
+
+fn get_staged_diff<'a>(repo: &'a Repository, filename: &str) -> AppResult<Diff<'a>> {
+    let mut options = DiffOptions::new();
+    options.pathspec(filename);
+
+    let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+    let mut index = repo.index()?;
+    index.read(false)?;
+    repo.diff_tree_to_index(head_tree.as_ref(), Some(&index), Some(&mut options)).context(format!("diffing {}", filename))
+}
+
+// Builds a patch with only the selected lines, reversed patches undo them instead
+fn build_patch(diff: &Diff, selection: &HashSet<(usize, usize)>, reverse: bool) -> AppResult<Diff<'static>> {
+    let patch = Patch::from_diff(diff, 0)?.ok_or_else(|| Error::from_str("Nothing to apply"))?;
+    let delta = patch.delta();
+    let path = delta.new_file().path().or(delta.old_file().path())
+        .and_then(|path| path.to_str())
+        .ok_or_else(|| Error::from_str("Invalid file path"))?
+        .to_string();
+
+    // Files that appear or disappear can only be taken as a whole, unless new lines are staged
+    let is_new = matches!(delta.status(), Delta::Added | Delta::Untracked);
+    if (is_new && reverse) || delta.status() == Delta::Deleted {
+        return Err(Error::from_str("Stage or unstage new and deleted files as a whole").into());
+    }
+
+    let mut text = format!("diff --git a/{0} b/{0}\n", path);
+    if is_new {
+        text.push_str(&format!("new file mode {:o}\n--- /dev/null\n", u32::from(delta.new_file().mode())));
+    } else {
+        text.push_str(&format!("--- a/{}\n", path));
+    }
+    text.push_str(&format!("+++ b/{}\n", path));
+
+    let mut offset: i64 = 0;
+    let mut is_empty = true;
+    for hunk_idx in 0..patch.num_hunks() {
+        let (hunk, line_count) = patch.hunk(hunk_idx)?;
+        let mut body = String::new();
+        let (mut old_len, mut new_len) = (0, 0);
+        let mut is_changed = false;
+        let mut is_last_emitted = false;
+
+        for line_idx in 0..line_count {
+            let line = patch.line_in_hunk(hunk_idx, line_idx)?;
+            let content = String::from_utf8_lossy(line.content());
+            let is_selected = selection.contains(&(hunk_idx, line_idx));
+
+            // Flip the side of a line when the patch goes backwards
+            let origin = match (line.origin(), reverse) {
+                ('+', true) => '-',
+                ('-', true) => '+',
+                (origin, _) => origin,
+            };
+
+            let prefix = match origin {
+                ' ' => Some(' '),
+                '+' if is_selected => Some('+'),
+                '-' if is_selected => Some('-'),
+                // An unselected removal stays in the file, an unselected addition never gets there
+                '-' => Some(' '),
+                '+' => None,
+                // No newline at end of file, belongs to the line before it
+                '=' | '>' | '<' => {
+                    if is_last_emitted {
+                        body.push_str("\\ No newline at end of file\n");
+                    }
+                    continue;
+                }
+                _ => None,
+            };
+
+            is_last_emitted = prefix.is_some();
+            if let Some(prefix) = prefix {
+                body.push(prefix);
+                body.push_str(content.trim_end_matches('\n'));
+                body.push('\n');
+                match prefix {
+                    ' ' => { old_len += 1; new_len += 1; }
+                    '-' => { old_len += 1; is_changed = true; }
+                    _ => { new_len += 1; is_changed = true; }
+                }
+            }
+        }
+
+        if !is_changed {
+            continue;
+        }
+
+        // The old side of a reversed patch is what the diff calls new
+        let old_start = if reverse { hunk.new_start() } else { hunk.old_start() } as i64;
+        let old_start = if old_len == 0 { old_start } else { old_start.max(1) };
+        let new_start = (old_start + offset).max(if new_len == 0 { 0 } else { 1 });
+        text.push_str(&format!("@@ -{},{} +{},{} @@\n", old_start, old_len, new_start, new_len));
+        text.push_str(&body);
+        offset += new_len as i64 - old_len as i64;
+        is_empty = false;
+    }
+
+    if is_empty {
+        return Err(Error::from_str("Nothing selected to apply").into());
+    }
+
+    Diff::from_buffer(text.as_bytes()).context(format!("building a patch for {}", path))
+}
@@ -0,0 +1,94 @@
+#[rustfmt::skip]
+use git2::{
+    Oid,
+    Error,
+    Signature,
+    Repository,
+    AnnotatedCommit,
+    build::CheckoutBuilder
+};
+#[rustfmt::skip]
+use crate::core::error::AppResult;
+
+pub enum MergeOutcome {
+    UpToDate,    // Nothing to merge
+    FastForward, // HEAD was moved to the merged commit
+    Merged,      // The merge is staged and waits for a commit
+    Conflicted,  // The merge stopped on conflicts
+}
+
+// Merges a branch, or any revision git can resolve, into HEAD
+pub fn merge_revision(repo: &Repository, revision: &str, no_ff: bool) -> AppResult<MergeOutcome> {
+    let annotated = match repo.resolve_reference_from_short_name(revision) {
+        Ok(reference) => repo.reference_to_annotated_commit(&reference)?,
+        Err(_) => repo.find_annotated_commit(repo.revparse_single(revision)?.peel_to_commit()?.id())?,
+    };
+    merge_annotated(repo, &annotated, no_ff)
+}
+
+pub fn merge_annotated(repo: &Repository, annotated: &AnnotatedCommit, no_ff: bool) -> AppResult<MergeOutcome> {
+    let (analysis, _) = repo.merge_analysis(&[annotated])?;
+
+    if analysis.is_up_to_date() {
+        return Ok(MergeOutcome::UpToDate);
+    }
+
+    if analysis.is_fast_forward() && !no_ff {
+        fast_forward(repo, annotated.id())?;
+        return Ok(MergeOutcome::FastForward);
+    }
+
+    // Leave conflict markers in the working directory for the user to resolve
+    let mut checkout = CheckoutBuilder::default();
+    checkout.allow_conflicts(true).conflict_style_merge(true);
+    repo.merge(&[annotated], None, Some(&mut checkout))?;
+
+    if repo.index()?.has_conflicts() {
+        Ok(MergeOutcome::Conflicted)
+    } else {
+        Ok(MergeOutcome::Merged)
+    }
+}
+
+// Moves HEAD forward to the target, keeping local changes that do not collide
+pub fn fast_forward(repo: &Repository, target: Oid) -> AppResult<()> {
+    let commit = repo.find_commit(target)?;
+    repo.checkout_tree(commit.as_object(), Some(CheckoutBuilder::default().safe()))?;
+
+    let head = repo.head()?;
+    if head.is_branch() {
+        let name = head.name().ok_or_else(|| Error::from_str("Invalid branch reference name"))?;
+        repo.find_reference(name)?.set_target(target, "merge: fast-forward")?;
+    } else {
+        repo.set_head_detached(target)?;
+    }
+
+    Ok(())
+}
+
+// Commits a merge in progress, with HEAD and every merged head as parents
+pub fn commit_merge(repo: &Repository, message: &str, name: &str, email: &str) -> AppResult<Oid> {
+    // Pick up conflicts resolved outside of the app
+    let mut index = repo.index()?;
+    index.read(false)?;
+    if index.has_conflicts() {
+        return Err(Error::from_str("Resolve and stage the conflicted files first").into());
+    }
+    let tree = repo.find_tree(index.write_tree()?)?;
+
+    // The merged heads are read from MERGE_HEAD, since walking them needs a mutable repository
+    let mut parent_oids = vec![repo.head()?.peel_to_commit()?.id()];
+    let merge_head = std::fs::read_to_string(repo.path().join("MERGE_HEAD"))
+        .map_err(|_| Error::from_str("No merge in progress"))?;
+    for line in merge_head.lines().filter(|line| !line.trim().is_empty()) {
+        parent_oids.push(Oid::from_str(line.trim())?);
+    }
+    let parents = parent_oids.iter().map(|oid| repo.find_commit(*oid)).collect::<Result<Vec<_>, _>>()?;
+    let parents: Vec<_> = parents.iter().collect();
+
+    let signature = Signature::now(name, email)?;
+    let commit_oid = repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents)?;
+    repo.cleanup_state()?;
+
+    Ok(commit_oid)
+}
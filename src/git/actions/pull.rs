@@ -0,0 +1,72 @@
+#[rustfmt::skip]
+use git2::{
+    Oid,
+    Error,
+    Repository
+};
+#[rustfmt::skip]
+use crate::core::error::AppResult;
+
+// Where the current branch pulls from, as configured by branch.<name>.remote and branch.<name>.merge
+pub struct Upstream {
+    pub branch: String,   // Short name of the current branch
+    pub remote: String,   // Remote to fetch from
+    pub tracking: String, // Remote tracking reference, e.g. refs/remotes/origin/main
+    pub head: Oid,        // Where the branch was when the pull started
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PullStrategy {
+    FastForwardOnly,    // pull.ff = only
+    Merge,              // Fast-forward when possible, merge otherwise
+    MergeNoFastForward, // pull.ff = false
+    Rebase,             // pull.rebase or branch.<name>.rebase
+}
+
+pub fn get_upstream(repo: &Repository) -> AppResult<Upstream> {
+    let head = repo.head()?;
+    if !head.is_branch() {
+        return Err(Error::from_str("HEAD is detached, nothing to pull").into());
+    }
+    let refname = head.name().ok_or_else(|| Error::from_str("Invalid branch reference name"))?;
+    let branch = head.shorthand().unwrap_or(refname).to_string();
+    let target = head.peel_to_commit()?.id();
+
+    let remote = repo.branch_upstream_remote(refname)
+        .map_err(|_| Error::from_str(&format!("No upstream configured for '{}'", branch)))?;
+    let tracking = repo.branch_upstream_name(refname)
+        .map_err(|_| Error::from_str(&format!("No upstream configured for '{}'", branch)))?;
+
+    Ok(Upstream {
+        branch,
+        remote: remote.as_str().unwrap_or("origin").to_string(),
+        tracking: tracking.as_str().unwrap_or_default().to_string(),
+        head: target,
+    })
+}
+
+// Reads pull.rebase and pull.ff the same way git does, the branch setting wins over the global one
+pub fn get_pull_strategy(repo: &Repository, branch: &str) -> PullStrategy {
+    let Ok(config) = repo.config() else {
+        return PullStrategy::Merge;
+    };
+
+    let rebase = config.get_string(&format!("branch.{}.rebase", branch))
+        .or_else(|_| config.get_string("pull.rebase"))
+        .map(|value| matches!(value.to_lowercase().as_str(), "true" | "yes" | "on" | "1" | "merges" | "interactive"))
+        .unwrap_or(false);
+    if rebase {
+        return PullStrategy::Rebase;
+    }
+
+    match config.get_string("pull.ff").map(|value| value.to_lowercase()).as_deref() {
+        Ok("only") => PullStrategy::FastForwardOnly,
+        Ok("false") | Ok("no") | Ok("off") | Ok("0") => PullStrategy::MergeNoFastForward,
+        _ => PullStrategy::Merge,
+    }
+}
+
+// Resolves the fetched upstream commit
+pub fn get_upstream_oid(repo: &Repository, upstream: &Upstream) -> AppResult<Oid> {
+    Ok(repo.find_reference(&upstream.tracking)?.peel_to_commit()?.id())
+}
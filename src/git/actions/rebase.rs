@@ -0,0 +1,267 @@
+#[rustfmt::skip]
+use std::{
+    fs,
+    io,
+    path::PathBuf
+};
+#[rustfmt::skip]
+use git2::{
+    Oid,
+    Error,
+    ErrorCode,
+    Signature,
+    Sort,
+    Repository,
+    RepositoryState,
+    RebaseOptions,
+    build::CheckoutBuilder
+};
+#[rustfmt::skip]
+use crate::{
+    core::{
+        error::{
+            AppResult,
+            Context
+        },
+        rebase::{
+            RebaseAction,
+            RebaseSession,
+            RebaseStep
+        }
+    }
+};
+
+// Lists the commits between the base and HEAD as a todo list, oldest first, refusing merges
+pub fn get_rebase_steps(repo: &Repository, base: Oid) -> AppResult<Vec<RebaseStep>> {
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push_head()?;
+    revwalk.hide(base)?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+
+    let mut steps = Vec::new();
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+
+        // Replaying one parent at a time would flatten the merge and lose the other side
+        if commit.parent_count() > 1 {
+            return Err(Error::from_str(&format!("#{:.6} is a merge, rebasing would flatten it", commit.id())).into());
+        }
+
+        steps.push(RebaseStep {
+            action: RebaseAction::Pick,
+            oid: commit.id(),
+            summary: commit.summary().unwrap_or("⊘ no summary").to_string(),
+        });
+    }
+
+    Ok(steps)
+}
+
+// Detaches HEAD at the base commit, returns the branch that was checked out, if any
+pub fn rebase_start(repo: &Repository, base: Oid) -> AppResult<Option<String>> {
+    let head = repo.head()?;
+    let branch = if head.is_branch() { head.name().map(|name| name.to_string()) } else { None };
+
+    // Move the files before HEAD so the safe checkout compares against where they came from
+    let target = repo.find_commit(base)?;
+    repo.checkout_tree(target.as_object(), Some(CheckoutBuilder::default().safe()))
+        .context(format!("checking out #{:.6}", base))?;
+    repo.set_head_detached(base)?;
+
+    Ok(branch)
+}
+
+// Where the session lives while a rebase is in progress, so a quit or a crash does not lose it
+fn get_session_path(repo: &Repository) -> PathBuf {
+    repo.path().join("guitar-rebase")
+}
+
+// Writes the session as plain lines, the todo in the same form as git's
+pub fn save_rebase_session(repo: &Repository, session: &RebaseSession) -> AppResult<()> {
+    let format_step = |step: &RebaseStep| format!("{} {} {}", step.action.as_str(), step.oid, step.summary);
+    let mut lines = vec![
+        format!("origin {}", session.origin),
+        format!("branch {}", session.branch.as_deref().unwrap_or("-")),
+        format!("total {}", session.total),
+    ];
+    if let Some(step) = session.current.as_ref() {
+        lines.push(format!("current {}", format_step(step)));
+    }
+    lines.extend(session.queue.iter().map(format_step));
+
+    fs::write(get_session_path(repo), lines.join("\n") + "\n")
+        .map_err(|e| Error::from_str(&e.to_string()))
+        .context("saving the rebase todo")
+}
+
+// Reads back the session of a rebase left paused by a previous run
+pub fn get_rebase_session(repo: &Repository) -> Option<RebaseSession> {
+    // Ignore leftovers once HEAD is back on a branch
+    if !repo.head_detached().unwrap_or(false) {
+        return None;
+    }
+    let text = fs::read_to_string(get_session_path(repo)).ok()?;
+
+    let mut origin = None;
+    let mut branch = None;
+    let mut total = 0;
+    let mut current = None;
+    let mut queue = Vec::new();
+    for line in text.lines() {
+        let (key, value) = line.split_once(' ')?;
+        match key {
+            "origin" => origin = Some(Oid::from_str(value).ok()?),
+            "branch" => branch = (value != "-").then(|| value.to_string()),
+            "total" => total = value.parse().ok()?,
+            "current" => current = Some(parse_step(value)?),
+            _ => queue.push(parse_step(line)?),
+        }
+    }
+
+    Some(RebaseSession {
+        queue,
+        current,
+        branch,
+        origin: origin?,
+        total,
+    })
+}
+
+fn parse_step(line: &str) -> Option<RebaseStep> {
+    let mut parts = line.splitn(3, ' ');
+    let action = RebaseAction::parse(parts.next()?)?;
+    let oid = Oid::from_str(parts.next()?).ok()?;
+    let summary = parts.next().unwrap_or_default().to_string();
+    Some(RebaseStep { action, oid, summary })
+}
+
+// Removes the saved session once the rebase is finished or aborted
+pub fn clear_rebase_session(repo: &Repository) -> AppResult<()> {
+    match fs::remove_file(get_session_path(repo)) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => {
+            Err(Error::from_str(&e.to_string())).context("removing the rebase todo")
+        }
+        _ => Ok(()),
+    }
+}
+
+// Replays a single commit on top of HEAD, returns true if it stopped on conflicts
+pub fn rebase_step(repo: &Repository, oid: Oid, name: &str, email: &str) -> AppResult<bool> {
+    let commit = repo.find_commit(oid)?;
+    let parent = commit.parent(0)?;
+    let head = repo.head()?.peel_to_commit()?;
+
+    let branch = repo.find_annotated_commit(commit.id())?;
+    let upstream = repo.find_annotated_commit(parent.id())?;
+    let onto = repo.find_annotated_commit(head.id())?;
+
+    // Leave conflict markers in the working directory for the user to resolve
+    let mut checkout = CheckoutBuilder::default();
+    checkout.allow_conflicts(true).conflict_style_merge(true);
+    let mut options = RebaseOptions::new();
+    options.checkout_options(checkout);
+
+    let mut rebase = repo.rebase(Some(&branch), Some(&upstream), Some(&onto), Some(&mut options))?;
+    if let Some(operation) = rebase.next() {
+        operation?;
+    }
+
+    if repo.index()?.has_conflicts() {
+        return Ok(true);
+    }
+
+    let committer = Signature::now(name, email)?;
+    rebase_commit(&mut rebase, &committer)?;
+    rebase.finish(Some(&committer))?;
+
+    Ok(false)
+}
+
+// Commits the resolved step of a rebase that stopped on conflicts
+pub fn rebase_continue(repo: &Repository, name: &str, email: &str) -> AppResult<()> {
+    // Pick up conflicts resolved outside of the app
+    let mut index = repo.index()?;
+    index.read(false)?;
+    if index.has_conflicts() {
+        return Err(Error::from_str("Resolve and stage the conflicted files first").into());
+    }
+
+    let committer = Signature::now(name, email)?;
+    let mut rebase = repo.open_rebase(None)?;
+    rebase_commit(&mut rebase, &committer)?;
+    rebase.finish(Some(&committer)).context("finishing the rebase")
+}
+
+// Commits the current operation, keeping the original author and message
+fn rebase_commit(rebase: &mut git2::Rebase, committer: &Signature) -> AppResult<()> {
+    match rebase.commit(None, committer, None) {
+        Ok(_) => Ok(()),
+        // The changes are already upstream, nothing to commit
+        Err(e) if e.code() == ErrorCode::Applied => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+// Folds HEAD into its parent, keeps the parent message if none is given
+pub fn squash_head(repo: &Repository, message: Option<&str>) -> AppResult<Oid> {
+    let head = repo.head()?.peel_to_commit()?;
+    let parent = head.parent(0)?;
+    let grandparents: Vec<_> = parent.parents().collect();
+    let grandparents: Vec<_> = grandparents.iter().collect();
+
+    let message = message.or(parent.message()).unwrap_or("");
+    let squashed = repo.commit(
+        None,
+        &parent.author(),
+        &parent.committer(),
+        message,
+        &head.tree()?,
+        &grandparents,
+    )?;
+    repo.set_head_detached(squashed)?;
+
+    Ok(squashed)
+}
+
+// Replaces the message of the HEAD commit
+pub fn reword_head(repo: &Repository, message: &str) -> AppResult<Oid> {
+    let head = repo.head()?.peel_to_commit()?;
+    head.amend(Some("HEAD"), None, None, None, Some(message), None)
+        .context(format!("rewording #{:.6}", head.id()))
+}
+
+// Folds the staged changes into the HEAD commit
+pub fn amend_head_with_index(repo: &Repository) -> AppResult<Oid> {
+    let head = repo.head()?.peel_to_commit()?;
+    let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+    head.amend(Some("HEAD"), None, None, None, None, Some(&tree))
+        .context(format!("amending #{:.6}", head.id()))
+}
+
+// Moves the rebased branch to the result and checks it out again
+pub fn rebase_finish(repo: &Repository, branch: Option<&str>) -> AppResult<()> {
+    if let Some(branch) = branch {
+        let result = repo.head()?.peel_to_commit()?.id();
+        repo.find_reference(branch)?.set_target(result, "rebase: finished")?;
+        repo.set_head(branch)?;
+    }
+    Ok(())
+}
+
+// Throws away the rebase and returns to where it started
+pub fn rebase_abort(repo: &Repository, branch: Option<&str>, origin: Oid) -> AppResult<()> {
+    // Drop a step that stopped on conflicts
+    if matches!(repo.state(), RepositoryState::Rebase | RepositoryState::RebaseInteractive | RepositoryState::RebaseMerge) {
+        repo.open_rebase(None)?.abort()?;
+    }
+
+    // The branch was never moved, so checking out the origin restores everything, local edits block it
+    let target = repo.find_commit(origin)?;
+    repo.checkout_tree(target.as_object(), Some(CheckoutBuilder::default().safe()))
+        .context(format!("checking out #{:.6}", origin))?;
+    match branch {
+        Some(branch) => repo.set_head(branch)?,
+        None => repo.set_head_detached(origin)?,
+    }
+    repo.cleanup_state().context("cleaning up the repository state")
+}
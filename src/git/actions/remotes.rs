@@ -0,0 +1,65 @@
+#[rustfmt::skip]
+use git2::{
+    ConfigLevel,
+    Repository
+};
+#[rustfmt::skip]
+use crate::core::error::{
+    AppResult,
+    Context
+};
+
+pub fn add_remote(repo: &Repository, name: &str, url: &str) -> AppResult<()> {
+    repo.remote(name, url)?;
+    Ok(())
+}
+
+// Removes a remote along with its remote tracking branches and upstream configuration
+pub fn remove_remote(repo: &Repository, name: &str) -> AppResult<()> {
+    repo.remote_delete(name)?;
+
+    // Forget it as the default push remote too, like git does
+    for level in get_push_default_levels(repo, name)? {
+        repo.config()?.open_level(level)?.remove("remote.pushDefault").context("forgetting remote.pushDefault")?;
+    }
+    Ok(())
+}
+
+// Renames a remote, returns the refspecs git2 could not rewrite on its own
+pub fn rename_remote(repo: &Repository, name: &str, new_name: &str) -> AppResult<Vec<String>> {
+    let problems = repo.remote_rename(name, new_name)?;
+
+    for level in get_push_default_levels(repo, name)? {
+        repo.config()?.open_level(level)?.set_str("remote.pushDefault", new_name).context("updating remote.pushDefault")?;
+    }
+    Ok(problems.iter().flatten().map(|refspec| refspec.to_string()).collect())
+}
+
+// The config levels whose remote.pushDefault names the remote, edits have to go to the file that defines it
+fn get_push_default_levels(repo: &Repository, name: &str) -> AppResult<Vec<ConfigLevel>> {
+    let config = repo.config()?;
+    let mut levels = Vec::new();
+    let mut entries = config.entries(Some("remote.pushdefault"))?;
+    while let Some(entry) = entries.next() {
+        let entry = entry?;
+        if entry.value() == Some(name) && !levels.contains(&entry.level()) {
+            levels.push(entry.level());
+        }
+    }
+    Ok(levels)
+}
+
+// Points a remote at a new url, either the one it fetches from or the one it pushes to
+pub fn set_remote_url(repo: &Repository, name: &str, url: &str, is_push: bool) -> AppResult<()> {
+    if is_push {
+        repo.remote_set_pushurl(name, if url.is_empty() { None } else { Some(url) }).context(format!("setting the push url of {}", name))
+    } else {
+        repo.remote_set_url(name, url).context(format!("setting the url of {}", name))
+    }
+}
+
+// Saves a preference in the repository's own config, the global one is left to git
+pub fn set_local_bool(repo: &Repository, key: &str, value: bool) -> AppResult<()> {
+    let mut config = repo.config()?.open_level(ConfigLevel::Local)?;
+    config.set_bool(key, value).context(format!("saving {}", key))
+}
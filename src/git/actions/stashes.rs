@@ -0,0 +1,74 @@
+#[rustfmt::skip]
+use git2::{
+    Oid,
+    Signature,
+    StashFlags,
+    StashApplyOptions,
+    Repository,
+    build::CheckoutBuilder
+};
+#[rustfmt::skip]
+use crate::core::error::{
+    AppResult,
+    Context
+};
+
+pub fn stash_save(
+    repo_path: &str,
+    message: &str,
+    name: &str,
+    email: &str,
+    include_untracked: bool,
+    keep_index: bool,
+) -> AppResult<Oid> {
+    // Stashing needs a mutable repository handle
+    let mut repo = Repository::open(repo_path)?;
+    let signature = Signature::now(name, email)?;
+
+    // Translate the options into stash flags
+    let mut flags = StashFlags::DEFAULT;
+    if include_untracked {
+        flags |= StashFlags::INCLUDE_UNTRACKED;
+    }
+    if keep_index {
+        flags |= StashFlags::KEEP_INDEX;
+    }
+
+    // Let git generate the "WIP on <branch>" message when none is given
+    let message = message.trim();
+    let message = if message.is_empty() { None } else { Some(message) };
+
+    repo.stash_save2(&signature, message, Some(flags)).context("saving the stash")
+}
+
+// Applies a stash on top of the working directory, returns true if it left conflicts behind
+pub fn stash_apply(repo_path: &str, index: usize) -> AppResult<bool> {
+    let mut repo = Repository::open(repo_path)?;
+
+    // Write conflicts into the working directory instead of refusing to apply
+    let mut checkout = CheckoutBuilder::default();
+    checkout.allow_conflicts(true);
+    let mut options = StashApplyOptions::new();
+    options.checkout_options(checkout);
+
+    repo.stash_apply(index, Some(&mut options))?;
+
+    Ok(repo.index()?.has_conflicts())
+}
+
+// Applies a stash and drops it, unless applying it produced conflicts
+pub fn stash_pop(repo_path: &str, index: usize) -> AppResult<bool> {
+    let is_conflicted = stash_apply(repo_path, index)?;
+
+    // Same as git, keep the stash around if it needs to be applied again
+    if !is_conflicted {
+        stash_drop(repo_path, index)?;
+    }
+
+    Ok(is_conflicted)
+}
+
+pub fn stash_drop(repo_path: &str, index: usize) -> AppResult<()> {
+    let mut repo = Repository::open(repo_path)?;
+    repo.stash_drop(index).context(format!("dropping stash@{{{}}}", index))
+}
@@ -0,0 +1,91 @@
+#[rustfmt::skip]
+use std::{
+    cell::RefCell,
+    thread
+};
+#[rustfmt::skip]
+use git2::{
+    Oid,
+    Error,
+    PushOptions,
+    Repository,
+    Signature
+};
+#[rustfmt::skip]
+use crate::{
+    core::error::{
+        AppResult,
+        Context
+    },
+    git::{
+        credentials::Credentials,
+        network::Progress
+    }
+};
+
+// Creates a tag on a commit, annotated when a message is given
+pub fn create_tag(repo: &Repository, name: &str, target: Oid, message: Option<&str>, user_name: &str, email: &str) -> AppResult<Oid> {
+    let object = repo.find_object(target, None)?;
+    match message {
+        Some(message) => {
+            let tagger = Signature::now(user_name, email)?;
+            repo.tag(name, &object, &tagger, message, false).context(format!("creating tag {}", name))
+        }
+        None => repo.tag_lightweight(name, &object, false).context(format!("creating tag {}", name)),
+    }
+}
+
+pub fn delete_tag(repo: &Repository, name: &str) -> AppResult<()> {
+    repo.tag_delete(name).context(format!("deleting tag {}", name))
+}
+
+// Lists every tag name in the repository
+pub fn get_tag_names(repo: &Repository) -> AppResult<Vec<String>> {
+    Ok(repo.tag_names(None)?.iter().flatten().map(|name| name.to_string()).collect())
+}
+
+// Pushes the given tags to a remote, reporting the ones it refused
+pub fn push_tags(
+    repo_path: &str,
+    remote_name: &str,
+    tags: Vec<String>,
+    credentials: &Credentials,
+    progress: &Progress,
+) -> thread::JoinHandle<AppResult<()>> {
+    let repo_path = repo_path.to_string();
+    let remote_name = remote_name.to_string();
+    let credentials = credentials.clone();
+    let progress = progress.clone();
+
+    thread::spawn(move || {
+        let repo = Repository::open(&repo_path)?;
+        let mut remote = repo.find_remote(&remote_name)?;
+
+        // Collect the refs the remote refused to update
+        let rejected = RefCell::new(Vec::new());
+
+        // Authenticate with the chosen method
+        let mut callbacks = credentials.callbacks();
+        progress.attach(&mut callbacks);
+        callbacks.push_update_reference(|refname, status| {
+            if let Some(err) = status {
+                rejected.borrow_mut().push(format!("{}: {}", refname.trim_start_matches("refs/tags/"), err));
+            }
+            Ok(())
+        });
+
+        let mut push_options = PushOptions::new();
+        push_options.remote_callbacks(callbacks);
+
+        // Tags are never forced, an existing tag on the remote has to be deleted first
+        let refspecs: Vec<String> = tags.iter().map(|tag| format!("refs/tags/{0}:refs/tags/{0}", tag)).collect();
+        remote.push(&refspecs, Some(&mut push_options))?;
+        drop(push_options);
+
+        let rejected = rejected.into_inner();
+        if !rejected.is_empty() {
+            return Err(Error::from_str(&rejected.join(", ")).into());
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,408 @@
+#[rustfmt::skip]
+use std::{
+    collections::HashMap,
+    env,
+    fs,
+    path::{
+        Path,
+        PathBuf
+    },
+    sync::{
+        Arc,
+        Mutex
+    }
+};
+#[rustfmt::skip]
+use git2::{
+    Config,
+    ConfigLevel,
+    Cred,
+    CredentialType,
+    Error,
+    ErrorClass,
+    ErrorCode,
+    RemoteCallbacks,
+    Repository
+};
+
+// Git config key the chosen method is saved under
+const CONFIG_KEY: &str = "guitar.authorization";
+
+// Git config key of extra private keys to try first, can be given several times
+const KEY_FILE_KEY: &str = "guitar.keyfile";
+
+// Private keys tried after the configured ones and those ~/.ssh/config names for the host
+const KEY_FILES: [&str; 3] = ["id_ed25519", "id_ecdsa", "id_rsa"];
+
+// How the remote callbacks authenticate
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum AuthMethod {
+    Auto,     // Everything below, in order
+    Agent,    // The running ssh agent
+    KeyFile,  // Private keys from guitar.keyfile and ~/.ssh
+    Helper,   // git's credential.helper
+    Plain,    // Username and password or token
+}
+
+impl AuthMethod {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AuthMethod::Auto => "automatic",
+            AuthMethod::Agent => "ssh agent",
+            AuthMethod::KeyFile => "ssh key files",
+            AuthMethod::Helper => "credential helper",
+            AuthMethod::Plain => "username and password",
+        }
+    }
+
+    pub fn next(&self) -> AuthMethod {
+        match self {
+            AuthMethod::Auto => AuthMethod::Agent,
+            AuthMethod::Agent => AuthMethod::KeyFile,
+            AuthMethod::KeyFile => AuthMethod::Helper,
+            AuthMethod::Helper => AuthMethod::Plain,
+            AuthMethod::Plain => AuthMethod::Auto,
+        }
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            AuthMethod::Auto => "auto",
+            AuthMethod::Agent => "agent",
+            AuthMethod::KeyFile => "keyfile",
+            AuthMethod::Helper => "helper",
+            AuthMethod::Plain => "plain",
+        }
+    }
+
+    fn from_key(key: &str) -> AuthMethod {
+        match key {
+            "agent" => AuthMethod::Agent,
+            "keyfile" => AuthMethod::KeyFile,
+            "helper" => AuthMethod::Helper,
+            "plain" => AuthMethod::Plain,
+            _ => AuthMethod::Auto,
+        }
+    }
+
+    fn allows(&self, method: AuthMethod) -> bool {
+        *self == AuthMethod::Auto || *self == method
+    }
+}
+
+// What the user has to type in before a network action can go on
+#[derive(Clone, PartialEq, Eq)]
+pub enum CredentialPrompt {
+    Passphrase(PathBuf),      // Passphrase of an encrypted key file
+    Username(String),         // Username for a url
+    Password(String, String), // Password or token for a url and username
+}
+
+// Secrets typed in during this session, shared with the network threads
+#[derive(Default)]
+struct Secrets {
+    passphrases: HashMap<PathBuf, String>,
+    username: Option<String>,
+    password: Option<String>,
+    prompt: Option<CredentialPrompt>,
+}
+
+#[derive(Clone)]
+pub struct Credentials {
+    pub method: AuthMethod,
+    pub key_files: Vec<PathBuf>, // Keys set with guitar.keyfile
+    secrets: Arc<Mutex<Secrets>>,
+}
+
+// Where a single network action got to, so a failed attempt is not offered twice
+#[derive(Default)]
+struct Attempt {
+    agent: bool,
+    key_files: Option<Vec<PathBuf>>,
+    key_file: usize,
+    passphrase: Option<PathBuf>,
+    helper: bool,
+    plain: bool,
+}
+
+impl Credentials {
+
+    pub fn new(repo: &Repository) -> Self {
+        let method = repo.config()
+            .and_then(|config| config.get_string(CONFIG_KEY))
+            .map(|key| AuthMethod::from_key(&key))
+            .unwrap_or(AuthMethod::Auto);
+        let mut key_files = Vec::new();
+        if let Ok(config) = repo.config()
+            && let Ok(mut entries) = config.multivar(KEY_FILE_KEY, None) {
+                while let Some(Ok(entry)) = entries.next() {
+                    if let Some(value) = entry.value() {
+                        key_files.push(expand_home(value));
+                    }
+                }
+            }
+
+        Self {
+            method,
+            key_files,
+            secrets: Arc::new(Mutex::new(Secrets::default())),
+        }
+    }
+
+    // Switches to the next method and saves it in the repository config, like the other settings
+    pub fn cycle(&mut self, repo: &Repository) -> Result<(), Error> {
+        self.method = self.method.next();
+        let mut config = repo.config()?.open_level(ConfigLevel::Local)?;
+        config.set_str(CONFIG_KEY, self.method.key())
+    }
+
+    // Takes the prompt the last network action stopped at
+    pub fn take_prompt(&self) -> Option<CredentialPrompt> {
+        self.secrets.lock().unwrap().prompt.take()
+    }
+
+    // Stores what the user typed in for a prompt
+    pub fn answer(&self, prompt: &CredentialPrompt, value: &str) {
+        let mut secrets = self.secrets.lock().unwrap();
+        match prompt {
+            CredentialPrompt::Passphrase(path) => {
+                secrets.passphrases.insert(path.clone(), value.to_string());
+            }
+            CredentialPrompt::Username(_) => {
+                secrets.username = Some(value.to_string());
+                secrets.password = None;
+            }
+            CredentialPrompt::Password(_, _) => {
+                secrets.password = Some(value.to_string());
+            }
+        }
+    }
+
+    // Drops the typed in username and password, for when they were wrong
+    pub fn forget(&self) {
+        let mut secrets = self.secrets.lock().unwrap();
+        secrets.username = None;
+        secrets.password = None;
+    }
+
+    // Remote callbacks that authenticate with the chosen method
+    pub fn callbacks<'a>(&self) -> RemoteCallbacks<'a> {
+        let credentials = self.clone();
+        let mut attempt = Attempt::default();
+        let mut callbacks = RemoteCallbacks::new();
+        callbacks.credentials(move |url, username_from_url, allowed| {
+            credentials.credential(&mut attempt, url, username_from_url, allowed)
+        });
+        callbacks
+    }
+
+    // Offers the next credential, libgit2 calls back as long as the previous one was refused
+    fn credential(&self, attempt: &mut Attempt, url: &str, username_from_url: Option<&str>, allowed: CredentialType) -> Result<Cred, Error> {
+        let mut secrets = self.secrets.lock().unwrap();
+
+        // The passphrase offered last time was refused
+        if let Some(path) = attempt.passphrase.take() {
+            secrets.passphrases.remove(&path);
+        }
+
+        if allowed.contains(CredentialType::USERNAME) {
+            return Cred::username(username_from_url.unwrap_or("git"));
+        }
+
+        if allowed.contains(CredentialType::SSH_KEY) {
+            let username = username_from_url.unwrap_or("git");
+
+            if self.method.allows(AuthMethod::Agent) && !attempt.agent {
+                attempt.agent = true;
+                if env::var_os("SSH_AUTH_SOCK").is_some() {
+                    return Cred::ssh_key_from_agent(username);
+                }
+            }
+
+            if self.method.allows(AuthMethod::KeyFile) {
+                let key_files = attempt.key_files.get_or_insert_with(|| self.get_key_files(url)).clone();
+                while let Some(path) = key_files.get(attempt.key_file) {
+                    attempt.key_file += 1;
+                    if !path.exists() {
+                        continue;
+                    }
+                    if !is_encrypted(path) {
+                        return Cred::ssh_key(username, None, path, None);
+                    }
+                    match secrets.passphrases.get(path) {
+                        Some(passphrase) => {
+                            attempt.passphrase = Some(path.clone());
+                            return Cred::ssh_key(username, None, path, Some(passphrase));
+                        }
+                        None => {
+                            let message = format!("passphrase needed for {}", path.display());
+                            secrets.prompt = Some(CredentialPrompt::Passphrase(path.clone()));
+                            return Err(prompt_error(&message));
+                        }
+                    }
+                }
+            }
+        }
+
+        if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+            if self.method.allows(AuthMethod::Helper) && !attempt.helper {
+                attempt.helper = true;
+                if let Ok(config) = Config::open_default()
+                    && let Ok(cred) = Cred::credential_helper(&config, url, username_from_url) {
+                        return Ok(cred);
+                    }
+            }
+
+            if self.method.allows(AuthMethod::Plain) {
+
+                // The password offered last time was refused
+                if attempt.plain {
+                    secrets.password = None;
+                }
+                attempt.plain = true;
+
+                let username = secrets.username.clone().or(username_from_url.map(|username| username.to_string()));
+                match (username, secrets.password.clone()) {
+                    (Some(username), Some(password)) => return Cred::userpass_plaintext(&username, &password),
+                    (Some(username), None) => {
+                        secrets.prompt = Some(CredentialPrompt::Password(url.to_string(), username));
+                        return Err(prompt_error(&format!("password needed for {}", url)));
+                    }
+                    (None, _) => {
+                        secrets.prompt = Some(CredentialPrompt::Username(url.to_string()));
+                        return Err(prompt_error(&format!("username needed for {}", url)));
+                    }
+                }
+            }
+        }
+
+        Err(Error::new(ErrorCode::Auth, ErrorClass::Callback, format!("no accepted credentials for {} using {}", url, self.method.label())))
+    }
+
+    // Keys to try for a url: the configured ones, then what ~/.ssh/config says for its host, then the usual names
+    fn get_key_files(&self, url: &str) -> Vec<PathBuf> {
+        let mut key_files = self.key_files.clone();
+        let ssh = env::var_os("HOME").map(|home| Path::new(&home).join(".ssh"));
+        if let Some(ssh) = ssh {
+            if let Some(host) = get_url_host(url) {
+                key_files.extend(get_identity_files(&ssh.join("config"), &host));
+            }
+            key_files.extend(KEY_FILES.iter().map(|name| ssh.join(name)));
+        }
+        let mut seen = Vec::new();
+        key_files.retain(|path| {
+            let is_new = !seen.contains(path);
+            seen.push(path.clone());
+            is_new
+        });
+        key_files
+    }
+}
+
+fn prompt_error(message: &str) -> Error {
+    Error::new(ErrorCode::Auth, ErrorClass::Callback, message)
+}
+
+// The host of an ssh url, both ssh://user@host:port/path and user@host:path
+fn get_url_host(url: &str) -> Option<String> {
+    let rest = match url.split_once("://") {
+        Some((scheme, rest)) if scheme.contains("ssh") => rest,
+        Some(_) => return None,
+        None => url,
+    };
+    let rest = rest.split_once('@').map(|(_, host)| host).unwrap_or(rest);
+    let host = rest.split([':', '/']).next()?;
+    (!host.is_empty()).then(|| host.to_string())
+}
+
+// The IdentityFile lines of ~/.ssh/config that apply to a host, in the order ssh tries them
+fn get_identity_files(config: &Path, host: &str) -> Vec<PathBuf> {
+    let Ok(content) = fs::read_to_string(config) else {
+        return Vec::new();
+    };
+
+    let mut files = Vec::new();
+    let mut is_matching = true; // Lines before the first Host apply to every host
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (keyword, value) = line
+            .split_once(|c: char| c.is_whitespace() || c == '=')
+            .map(|(keyword, value)| (keyword, value.trim_start_matches(|c: char| c.is_whitespace() || c == '=').trim()))
+            .unwrap_or((line, ""));
+        match keyword.to_lowercase().as_str() {
+            "host" => is_matching = is_host_match(value, host),
+            // Match blocks depend on more than the host, leave them out
+            "match" => is_matching = false,
+            "identityfile" if is_matching => files.push(expand_home(value.trim_matches('"'))),
+            _ => {}
+        }
+    }
+    files
+}
+
+// Whether a Host line matches, any positive pattern has to match and no negated one
+fn is_host_match(patterns: &str, host: &str) -> bool {
+    let mut is_match = false;
+    for pattern in patterns.split_whitespace() {
+        match pattern.strip_prefix('!') {
+            Some(pattern) if is_glob_match(pattern, host) => return false,
+            Some(_) => {}
+            None => is_match |= is_glob_match(pattern, host),
+        }
+    }
+    is_match
+}
+
+// Matches ssh patterns where * is any run of characters and ? a single one
+fn is_glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+// Resolves a leading ~ the way ssh and git do
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), env::var_os("HOME")) {
+        (Some(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+// Tells from the header whether a private key needs a passphrase
+fn is_encrypted(path: &Path) -> bool {
+    let Ok(content) = fs::read_to_string(path) else {
+        return false;
+    };
+
+    // Old PEM keys say so in a header
+    if content.contains("ENCRYPTED") {
+        return true;
+    }
+
+    // OpenSSH keys name their cipher right after the magic, "none" when unencrypted
+    if content.contains("BEGIN OPENSSH PRIVATE KEY") {
+        let body = content.lines().nth(1).unwrap_or_default();
+        return !body.starts_with("b3BlbnNzaC1rZXktdjEAAAAABG5vbmU");
+    }
+    false
+}
@@ -0,0 +1,112 @@
+#[rustfmt::skip]
+use std::sync::{
+    Arc,
+    atomic::{
+        AtomicBool,
+        Ordering
+    },
+    mpsc::{
+        Receiver,
+        Sender,
+        channel
+    }
+};
+#[rustfmt::skip]
+use git2::{
+    Error,
+    ErrorClass,
+    ErrorCode,
+    PackBuilderStage,
+    Progress as TransferProgress,
+    RemoteCallbacks
+};
+
+// Reports what a network thread is doing and tells it when to stop
+#[derive(Clone)]
+pub struct Progress {
+    tx: Sender<String>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl Progress {
+
+    pub fn new() -> (Self, Receiver<String>) {
+        let (tx, rx) = channel();
+        (Self { tx, cancel: Arc::new(AtomicBool::new(false)) }, rx)
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::SeqCst)
+    }
+
+    // The receiving end may be gone already, the thread just keeps going then
+    fn send(&self, message: String) {
+        let _ = self.tx.send(message);
+    }
+
+    // Streams transfer stats and remote messages, and stops the transfer once cancelled
+    pub fn attach(&self, callbacks: &mut RemoteCallbacks) {
+        let progress = self.clone();
+        callbacks.transfer_progress(move |stats| {
+            progress.send(describe_transfer(&stats));
+            !progress.is_cancelled()
+        });
+
+        let progress = self.clone();
+        callbacks.sideband_progress(move |data| {
+            let text = String::from_utf8_lossy(data);
+            if let Some(line) = text.split(['\r', '\n']).map(str::trim).rfind(|line| !line.is_empty()) {
+                progress.send(format!("remote: {}", line));
+            }
+            !progress.is_cancelled()
+        });
+
+        let progress = self.clone();
+        callbacks.pack_progress(move |stage, current, total| {
+            if stage == PackBuilderStage::AddingObjects {
+                progress.send(format!("counting objects {}", current));
+            } else {
+                progress.send(format!("compressing objects {}/{}", current, total));
+            }
+        });
+
+        let progress = self.clone();
+        callbacks.push_transfer_progress(move |current, total, bytes| {
+            progress.send(format!("writing objects {}/{}, {}", current, total, format_bytes(bytes)));
+        });
+
+        // A push can only be stopped before it starts sending
+        let progress = self.clone();
+        callbacks.push_negotiation(move |_| {
+            if progress.is_cancelled() {
+                return Err(cancelled());
+            }
+            Ok(())
+        });
+    }
+}
+
+// The error a cancelled transfer ends with
+pub fn cancelled() -> Error {
+    Error::new(ErrorCode::User, ErrorClass::Net, "cancelled")
+}
+
+fn describe_transfer(stats: &TransferProgress) -> String {
+    if stats.total_objects() > 0 && stats.received_objects() == stats.total_objects() {
+        format!("resolving deltas {}/{}", stats.indexed_deltas(), stats.total_deltas())
+    } else {
+        format!("receiving objects {}/{}, {}", stats.received_objects(), stats.total_objects(), format_bytes(stats.received_bytes()))
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    match bytes {
+        b if b >= 1 << 20 => format!("{:.1} MiB", b as f64 / (1 << 20) as f64),
+        b if b >= 1 << 10 => format!("{:.1} KiB", b as f64 / (1 << 10) as f64),
+        b => format!("{} B", b),
+    }
+}
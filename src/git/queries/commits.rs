@@ -1,14 +1,22 @@
 #[rustfmt::skip]
-use std::collections::HashMap;
+use std::collections::{
+    HashMap,
+    HashSet
+};
 #[rustfmt::skip]
 use git2::{
     Oid,
     Repository,
+    RepositoryState,
     Time
 };
 #[rustfmt::skip]
 use crate::{
     core::{
+        error::{
+            AppResult,
+            Context
+        },
         oids::{
             Oids
         },
@@ -18,14 +26,17 @@ use crate::{
     }
 };
 
+// Commit aliases mapped to the names of the refs pointing at them
+pub type RefNames = HashMap<u32, Vec<String>>;
+
 // Returns a map of commit OIDs to the branch names that point to them
-pub fn get_tip_oids(repo: &Repository, oids: &mut Oids) -> (HashMap<u32, Vec<String>>, HashMap<u32, Vec<String>>) {
+pub fn get_tip_oids(repo: &Repository, oids: &mut Oids) -> AppResult<(RefNames, RefNames)> {
     
     let mut local: HashMap<u32, Vec<String>> = HashMap::new();
     let mut remote: HashMap<u32, Vec<String>> = HashMap::new();
 
     // Iterate all refs once
-    for reference in repo.references().unwrap().flatten() {
+    for reference in repo.references().context("listing the branches")?.flatten() {
         // Only handle direct refs (skip symbolic ones like HEAD)
         if let Some(oid) = reference.target() {
             
@@ -41,16 +52,18 @@ pub fn get_tip_oids(repo: &Repository, oids: &mut Oids) -> (HashMap<u32, Vec<Str
         }
     }
 
-    (local, remote)
+    Ok((local, remote))
 }
 
 // Get all tags in a repo
-pub fn get_tag_oids(repo: &Repository, oids: &mut Oids) -> HashMap<u32, Vec<String>> {
+pub fn get_tag_oids(repo: &Repository, oids: &mut Oids) -> AppResult<HashMap<u32, Vec<String>>> {
     let mut local: HashMap<u32, Vec<String>> = HashMap::new();
 
-    // Iterate all references
-    for reference in repo.references().unwrap().flatten() {
-        if let Some(oid) = reference.target() {
+    // Iterate all tag references
+    for reference in repo.references_glob("refs/tags/*").context("listing the tags")?.flatten() {
+        // Annotated tags point at a tag object, show them on the commit it tags
+        let oid = reference.peel_to_commit().map(|commit| commit.id()).ok().or(reference.target());
+        if let Some(oid) = oid {
             let alias = oids.get_alias_by_oid(oid);
             let name = reference.name().unwrap_or("unknown");
 
@@ -60,9 +73,43 @@ pub fn get_tag_oids(repo: &Repository, oids: &mut Oids) -> HashMap<u32, Vec<Stri
         }
     }
 
+    Ok(local)
+}
+
+// Get all stashes in a repo, labeled by their position in the stash reflog
+pub fn get_stash_oids(repo: &Repository, oids: &mut Oids) -> HashMap<u32, Vec<String>> {
+    let mut local: HashMap<u32, Vec<String>> = HashMap::new();
+
+    // The stash reflog lists the most recent stash first
+    if let Ok(reflog) = repo.reflog("refs/stash") {
+        for (idx, entry) in reflog.iter().enumerate() {
+            let alias = oids.get_alias_by_oid(entry.id_new());
+            local.entry(alias).or_default().push(format!("stash@{{{}}}", idx));
+        }
+    }
+
     local
 }
 
+// Get the helper commits git creates for each stash (staged and untracked snapshots)
+pub fn get_stash_internal_oids(repo: &Repository, oids: &mut Oids) -> HashSet<u32> {
+    let mut internal: HashSet<u32> = HashSet::new();
+
+    if let Ok(reflog) = repo.reflog("refs/stash") {
+        for entry in reflog.iter() {
+            if let Ok(commit) = repo.find_commit(entry.id_new()) {
+
+                // The first parent is the commit the stash was made on, the rest are internal
+                for parent in commit.parent_ids().skip(1) {
+                    internal.insert(oids.get_alias_by_oid(parent));
+                }
+            }
+        }
+    }
+
+    internal
+}
+
 // Outcomes:
 // Update the oids vector
 pub fn get_sorted_oids(
@@ -87,9 +134,34 @@ pub fn get_sorted_oids(
     }
 }
 
-// Returns the name of the currently checked-out branch, or None if detached HEAD
+// Tips of all local and remote branches, apart from the one named
+pub fn get_branch_tips(repo: &Repository, except: Option<&str>) -> AppResult<Vec<Oid>> {
+    let mut tips = Vec::new();
+    for branch in repo.branches(None).context("listing the branches")? {
+        let (branch, _) = branch.context("listing the branches")?;
+        if except.is_some() && branch.name().ok().flatten() == except {
+            continue;
+        }
+        if let Some(oid) = branch.get().target() {
+            tips.push(oid);
+        }
+    }
+    Ok(tips)
+}
+
+// Counts the commits reachable from a tip but from none of the others, the ones nothing else keeps
+pub fn count_unique_commits(repo: &Repository, tip: Oid, others: &[Oid]) -> AppResult<usize> {
+    let mut revwalk = repo.revwalk().context("walking the history")?;
+    revwalk.push(tip).context(format!("walking from #{:.6}", tip))?;
+    for other in others {
+        revwalk.hide(*other).context(format!("walking from #{:.6}", other))?;
+    }
+    Ok(revwalk.count())
+}
+
+// Returns the name of the currently checked-out branch, or None if detached HEAD or unborn
 pub fn get_current_branch(repo: &Repository) -> Option<String> {
-    let head = repo.head().unwrap();
+    let head = repo.head().ok()?;
     if head.is_branch() {
         head.shorthand().map(|s| s.to_string())
     } else {
@@ -97,6 +169,19 @@ pub fn get_current_branch(repo: &Repository) -> Option<String> {
     }
 }
 
+// Returns a short label for an operation in progress, or None if the repository is clean
+pub fn get_repo_state(repo: &Repository) -> Option<&'static str> {
+    match repo.state() {
+        RepositoryState::Clean => None,
+        RepositoryState::Merge => Some("merging"),
+        RepositoryState::Revert | RepositoryState::RevertSequence => Some("reverting"),
+        RepositoryState::CherryPick | RepositoryState::CherryPickSequence => Some("cherry-picking"),
+        RepositoryState::Bisect => Some("bisecting"),
+        RepositoryState::Rebase | RepositoryState::RebaseInteractive | RepositoryState::RebaseMerge => Some("rebasing"),
+        RepositoryState::ApplyMailbox | RepositoryState::ApplyMailboxOrRebase => Some("applying patches"),
+    }
+}
+
 // Returns a map of commit OIDs to their timestamps:
 // (commit time, committer time, author time)
 pub fn get_timestamps(
@@ -105,20 +190,20 @@ pub fn get_timestamps(
 ) -> HashMap<Oid, (Time, Time, Time)> {
     _branches
         .keys()
-        .map(|&sha| {
-            let commit = repo.find_commit(sha).unwrap();
+        .filter_map(|&sha| {
+            let commit = repo.find_commit(sha).ok()?;
             let author_time = commit.author().when();
             let committer_time = commit.committer().when();
             let time = commit.time();
             // Map each OID to its associated timestamps
-            (sha, (time, committer_time, author_time))
+            Some((sha, (time, committer_time, author_time)))
         })
         .collect()
 }
 
 pub fn get_git_user_info(
     repo: &Repository,
-) -> Result<(Option<String>, Option<String>), git2::Error> {
+) -> AppResult<(Option<String>, Option<String>)> {
     let config = repo.config()?;
     let name = config.get_string("user.name").ok();
     let email = config.get_string("user.email").ok();
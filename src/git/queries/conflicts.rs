@@ -0,0 +1,140 @@
+#[rustfmt::skip]
+use std::{
+    path::Path
+};
+#[rustfmt::skip]
+use git2::{
+    Diff,
+    DiffOptions,
+    Error,
+    IndexEntry,
+    MergeFileOptions,
+    Patch,
+    Repository
+};
+#[rustfmt::skip]
+use crate::{
+    core::{
+        conflicts::{
+            ConflictChunk,
+            ConflictFile
+        },
+        error::{
+            AppResult
+        }
+    },
+    git::{
+        queries::{
+            helpers::{
+                Hunk,
+                diff_to_hunks
+            }
+        }
+    },
+    helpers::{
+        text::{
+            decode
+        }
+    }
+};
+
+// Lists the paths that have conflict entries in the index
+pub fn get_conflicted_paths(repo: &Repository) -> AppResult<Vec<String>> {
+    // Pick up conflicts resolved outside of the app
+    let mut index = repo.index()?;
+    index.read(false)?;
+
+    let mut paths = Vec::new();
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        let entry = conflict.our.as_ref().or(conflict.their.as_ref()).or(conflict.ancestor.as_ref());
+        if let Some(entry) = entry {
+            let path = decode(&entry.path);
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+    }
+    paths.sort();
+
+    Ok(paths)
+}
+
+// Builds the three-way view of a conflicted file from the working directory, so picked sides and edits survive
+pub fn get_conflict_file(repo: &Repository, path: &str) -> AppResult<ConflictFile> {
+    let mut index = repo.index()?;
+    index.read(false)?;
+
+    let conflict = index
+        .conflicts()?
+        .filter_map(|conflict| conflict.ok())
+        .find(|conflict| {
+            [&conflict.ancestor, &conflict.our, &conflict.their]
+                .iter()
+                .any(|entry| entry.as_ref().is_some_and(|entry| entry.path == path.as_bytes()))
+        })
+        .ok_or_else(|| Error::from_str(&format!("'{}' is not conflicted", path)))?;
+
+    match (&conflict.ancestor, &conflict.our, &conflict.their) {
+        (Some(ancestor), Some(ours), Some(theirs)) => {
+            let mut options = MergeFileOptions::new();
+            options
+                .ancestor_label("base")
+                .our_label("ours")
+                .their_label("theirs")
+                .style_diff3(true);
+            let merged = repo.merge_file_from_index(ancestor, ours, theirs, Some(&mut options))?;
+            let merged = ConflictFile::from_merged(path, &decode(merged.content()));
+
+            // Binary files get no markers, so only the index can tell their sides apart
+            let is_binary = repo.find_blob(ours.id).is_ok_and(|blob| blob.is_binary());
+            let content = repo.workdir().and_then(|root| std::fs::read(root.join(path)).ok());
+            match content {
+                Some(content) if !is_binary => {
+                    // A text file without markers was resolved by hand and shows as such
+                    let mut file = ConflictFile::from_merged(path, &decode(&content));
+                    file.fill_bases(&merged);
+                    Ok(file)
+                }
+                _ => Ok(merged),
+            }
+        }
+        // Added on both sides or deleted on one, the whole file is a single hunk
+        (ancestor, ours, theirs) => {
+            Ok(ConflictFile {
+                path: path.to_string(),
+                chunks: vec![ConflictChunk::Conflict {
+                    base: get_entry_lines(repo, ancestor.as_ref()),
+                    ours: get_entry_lines(repo, ours.as_ref()),
+                    theirs: get_entry_lines(repo, theirs.as_ref()),
+                    resolution: None,
+                }],
+                is_ours: ours.is_some(),
+                is_theirs: theirs.is_some(),
+            })
+        }
+    }
+}
+
+// What one side of a conflicting hunk made of the base, every line kept as context
+pub fn get_side_hunks(path: &str, base: &[String], side: &[String]) -> AppResult<Vec<Hunk>> {
+    let base = base.concat();
+    let side = side.concat();
+    let mut options = DiffOptions::new();
+    options.context_lines(u32::MAX);
+    let mut patch = Patch::from_buffers(
+        base.as_bytes(),
+        Some(Path::new(path)),
+        side.as_bytes(),
+        Some(Path::new(path)),
+        Some(&mut options),
+    )?;
+    diff_to_hunks(Diff::from_buffer(&patch.to_buf()?)?)
+}
+
+fn get_entry_lines(repo: &Repository, entry: Option<&IndexEntry>) -> Vec<String> {
+    entry
+        .and_then(|entry| repo.find_blob(entry.id).ok())
+        .map(|blob| decode(blob.content()).split_inclusive('\n').map(|line| line.to_string()).collect())
+        .unwrap_or_default()
+}
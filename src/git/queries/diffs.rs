@@ -4,7 +4,6 @@ use std::{
 };
 #[rustfmt::skip]
 use git2::{
-    Error,
     DiffOptions,
     Delta,
     Oid,
@@ -13,6 +12,10 @@ use git2::{
 };
 #[rustfmt::skip]
 use crate::{
+    core::error::{
+        AppResult,
+        Context
+    },
     helpers::{
         text::{
             decode,
@@ -35,7 +38,7 @@ use crate::{
 };
 
 // Collects and categorizes uncommitted changes in the working directory and index
-pub fn get_filenames_diff_at_workdir(repo: &Repository) -> Result<UncommittedChanges, Error> {
+pub fn get_filenames_diff_at_workdir(repo: &Repository) -> AppResult<UncommittedChanges> {
     let mut options = StatusOptions::new();
     options
         .include_untracked(true)
@@ -57,6 +60,12 @@ pub fn get_filenames_diff_at_workdir(repo: &Repository) -> Result<UncommittedCha
             continue;
         }
 
+        // Conflicted files are neither staged nor unstaged until resolved
+        if status.is_conflicted() {
+            changes.conflicted.push(path);
+            continue;
+        }
+
         // Record staged changes (index vs HEAD)
         if status.is_index_modified() {
             changes.staged.modified.push(path.clone());
@@ -92,25 +101,40 @@ pub fn get_filenames_diff_at_workdir(repo: &Repository) -> Result<UncommittedCha
     changes.is_unstaged = !changes.unstaged.modified.is_empty()
         || !changes.unstaged.added.is_empty()
         || !changes.unstaged.deleted.is_empty();
-    changes.is_clean = !changes.is_staged && !changes.is_unstaged;
+    changes.is_conflicted = !changes.conflicted.is_empty();
+    changes.is_clean = !changes.is_staged && !changes.is_unstaged && !changes.is_conflicted;
 
     Ok(changes)
 }
 
+// Whether tracked files differ from HEAD, the changes a hard reset would throw away
+pub fn has_tracked_changes(repo: &Repository) -> AppResult<bool> {
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(false)
+        .include_ignored(false)
+        .show(git2::StatusShow::IndexAndWorkdir);
+    let statuses = repo.statuses(Some(&mut options))?;
+    Ok(statuses.iter().any(|entry| !entry.status().is_empty()))
+}
+
 // Lists all files changed in a given commit compared to its parent
-pub fn get_filenames_diff_at_oid(repo: &Repository, oid: Oid) -> Vec<FileChange> {
-    let commit = repo.find_commit(oid).unwrap();
-    let tree = commit.tree().unwrap();
+pub fn get_filenames_diff_at_oid(repo: &Repository, oid: Oid) -> AppResult<Vec<FileChange>> {
+    let commit = repo.find_commit(oid).context(format!("finding #{:.6}", oid))?;
+    let tree = commit.tree().context(format!("reading the tree of #{:.6}", oid))?;
     let mut changes = Vec::new();
 
     // Handle the initial commit (no parent)
     if commit.parent_count() == 0 {
         walk_tree(repo, &tree, "", &mut changes);
-        return changes;
+        return Ok(changes);
     }
 
     // Diff current commit tree against its parent tree
-    let parent_tree = commit.parent(0).unwrap().tree().unwrap();
+    let parent_tree = commit
+        .parent(0)
+        .and_then(|parent| parent.tree())
+        .context(format!("reading the parent of #{:.6}", oid))?;
     let mut opts = DiffOptions::new();
     opts.include_untracked(false)
         .recurse_untracked_dirs(false)
@@ -122,17 +146,14 @@ pub fn get_filenames_diff_at_oid(repo: &Repository, oid: Oid) -> Vec<FileChange>
 
     let diff = repo
         .diff_tree_to_tree(Some(&parent_tree), Some(&tree), Some(&mut opts))
-        .unwrap();
+        .context(format!("diffing #{:.6}", oid))?;
 
     // Iterate through all deltas (changed files)
     for delta in diff.deltas() {
-        let path = delta
-            .new_file()
-            .path()
-            .or_else(|| delta.old_file().path())
-            .unwrap()
-            .display()
-            .to_string();
+        let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path()) else {
+            continue;
+        };
+        let path = path.display().to_string();
 
         // Rough check for folders (no '.' in name)
         let is_folder = !path.contains('.');
@@ -156,14 +177,14 @@ pub fn get_filenames_diff_at_oid(repo: &Repository, oid: Oid) -> Vec<FileChange>
         });
     }
 
-    changes
+    Ok(changes)
 }
 
 // Generate a line-by-line diff for a file in the working directory
 pub fn get_file_diff_at_workdir(
     repo: &Repository,
     filename: &str,
-) -> Result<Vec<Hunk>, git2::Error> {
+) -> AppResult<Vec<Hunk>> {
     // Get the current HEAD tree (if available)
     let head_tree = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
 
@@ -177,12 +198,36 @@ pub fn get_file_diff_at_workdir(
     )
 }
 
+// Generate a line-by-line diff for a file between HEAD and the index
+pub fn get_file_diff_staged(repo: &Repository, filename: &str) -> AppResult<Vec<Hunk>> {
+    let head_tree = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
+
+    // Diff options limited to the specific file
+    let mut diff_options = DiffOptions::new();
+    diff_options.pathspec(filename);
+
+    // Pick up changes staged outside of the app
+    let mut index = repo.index()?;
+    index.read(false)?;
+    diff_to_hunks(repo.diff_tree_to_index(head_tree.as_ref(), Some(&index), Some(&mut diff_options))?)
+}
+
+// Generate a line-by-line diff for a file between the index and the working directory
+pub fn get_file_diff_unstaged(repo: &Repository, filename: &str) -> AppResult<Vec<Hunk>> {
+    let mut diff_options = DiffOptions::new();
+    diff_options.pathspec(filename).include_untracked(true).show_untracked_content(true);
+
+    let mut index = repo.index()?;
+    index.read(false)?;
+    diff_to_hunks(repo.diff_index_to_workdir(Some(&index), Some(&mut diff_options))?)
+}
+
 // Generate a line-by-line diff for a file between a commit and its parent
 pub fn get_file_diff_at_oid(
     repo: &Repository,
     commit_oid: Oid,
     filename: &str,
-) -> std::result::Result<Vec<Hunk>, git2::Error> {
+) -> AppResult<Vec<Hunk>> {
     let commit = repo.find_commit(commit_oid)?;
     let tree = commit.tree()?;
     let parent_tree = if commit.parent_count() > 0 {
@@ -205,9 +250,9 @@ pub fn get_file_diff_at_oid(
 
 // Retrieve the contents of a file at a specific commit
 pub fn get_file_at_oid(repo: &Repository, commit_oid: Oid, filename: &str) -> Vec<String> {
-    let commit = repo.find_commit(commit_oid).unwrap();
-    let tree = commit.tree().unwrap();
-    tree.get_path(Path::new(filename))
+    repo.find_commit(commit_oid)
+        .and_then(|commit| commit.tree())
+        .and_then(|tree| tree.get_path(Path::new(filename)))
         .ok()
         .and_then(|entry| repo.find_blob(entry.id()).ok())
         .map(|blob| {
@@ -219,6 +264,24 @@ pub fn get_file_at_oid(repo: &Repository, commit_oid: Oid, filename: &str) -> Ve
         .unwrap_or_default()
 }
 
+// Retrieve the contents of a file as it is staged in the index
+pub fn get_file_at_index(repo: &Repository, filename: &str) -> Vec<String> {
+    repo.index()
+        .ok()
+        .and_then(|mut index| {
+            index.read(false).ok()?;
+            let entry = index.get_path(Path::new(filename), 0)?;
+            repo.find_blob(entry.id).ok()
+        })
+        .map(|blob| {
+            sanitize(decode(blob.content()))
+                .lines()
+                .map(|s| s.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 // Retrieve the contents of a file from the working directory
 pub fn get_file_at_workdir(repo: &Repository, filename: &str) -> Vec<String> {
     let full_path = repo
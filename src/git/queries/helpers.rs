@@ -15,6 +15,11 @@ use git2::{
 };
 #[rustfmt::skip]
 use crate::{
+    core::{
+        error::{
+            AppResult
+        }
+    },
     helpers::{
         text::{
             decode,
@@ -28,12 +33,27 @@ use crate::{
 pub struct UncommittedChanges {
     pub unstaged: FileChanges, // Changes in the working directory not yet staged
     pub staged: FileChanges,   // Changes that have been staged
+    pub conflicted: Vec<String>, // Files with unresolved merge conflicts
     pub modified_count: usize, // Number of modified files (deduplicated)
     pub added_count: usize,    // Number of added files (deduplicated)
     pub deleted_count: usize,  // Number of deleted files (deduplicated)
     pub is_clean: bool,        // True if there are no changes
     pub is_staged: bool,       // True if there are staged changes
     pub is_unstaged: bool,     // True if there are unstaged changes
+    pub is_conflicted: bool,   // True if there are unresolved conflicts
+}
+
+impl UncommittedChanges {
+
+    // Finds the file on a row of the staged list
+    pub fn get_staged_file(&self, idx: usize) -> Option<FileChange> {
+        self.staged.get_file(idx)
+    }
+
+    // Finds the file on a row of the unstaged list, the conflicted files listed above it are not counted
+    pub fn get_unstaged_file(&self, idx: usize) -> Option<FileChange> {
+        self.unstaged.get_file(idx)
+    }
 }
 
 // Structure representing a set of file changes (added, modified, deleted)
@@ -44,6 +64,18 @@ pub struct FileChanges {
     pub deleted: Vec<String>,
 }
 
+impl FileChanges {
+
+    // Files are listed modified first, then added, then deleted
+    pub fn get_file(&self, idx: usize) -> Option<FileChange> {
+        self.modified.iter().map(|file| (file, FileStatus::Modified))
+            .chain(self.added.iter().map(|file| (file, FileStatus::Added)))
+            .chain(self.deleted.iter().map(|file| (file, FileStatus::Deleted)))
+            .nth(idx)
+            .map(|(file, status)| FileChange { filename: file.clone(), status })
+    }
+}
+
 // Represents a single file change (filename + status)
 #[derive(Debug)]
 pub struct FileChange {
@@ -109,13 +141,14 @@ pub fn walk_tree(repo: &Repository, tree: &git2::Tree, base: &str, changes: &mut
 }
 
 // Convert a Git diff into structured hunks and line changes
-pub fn diff_to_hunks(diff: Diff) -> Result<Vec<Hunk>, git2::Error> {
+pub fn diff_to_hunks(diff: Diff) -> AppResult<Vec<Hunk>> {
     let mut hunks = Vec::new();
 
     // Print diff in patch format and collect hunks
     diff.print(Patch, |_, hunk_opt, line| {
-        // Start a new hunk if encountered
-        if let Some(hunk) = hunk_opt {
+        // Start a new hunk at its header, the other lines of the hunk carry it too
+        if let Some(hunk) = hunk_opt
+            && line.origin() == 'H' {
             hunks.push(Hunk {
                 header: sanitize(decode(hunk.header())).to_string(),
                 lines: Vec::new(),
@@ -0,0 +1,165 @@
+#[rustfmt::skip]
+use std::collections::HashMap;
+#[rustfmt::skip]
+use git2::{
+    Direction,
+    Error,
+    Oid,
+    Repository
+};
+#[rustfmt::skip]
+use crate::core::error::AppResult;
+
+// A configured remote with where it fetches from and pushes to
+#[derive(Clone)]
+pub struct RemoteInfo {
+    pub name: String,
+    pub fetch_url: String,
+    pub push_url: String,
+    pub fetch_refspecs: Vec<String>,
+    pub push_refspecs: Vec<String>,
+}
+
+pub fn get_remotes(repo: &Repository) -> AppResult<Vec<RemoteInfo>> {
+    let mut remotes = Vec::new();
+    for name in repo.remotes()?.iter().flatten() {
+        let remote = repo.find_remote(name)?;
+        let fetch_url = remote.url().unwrap_or_default().to_string();
+
+        // Without a push url git pushes to the fetch url
+        let push_url = remote.pushurl().map(|url| url.to_string()).unwrap_or_else(|| fetch_url.clone());
+
+        let mut fetch_refspecs = Vec::new();
+        let mut push_refspecs = Vec::new();
+        for refspec in remote.refspecs() {
+            let Some(text) = refspec.str().map(|text| text.to_string()) else {
+                continue;
+            };
+            match refspec.direction() {
+                Direction::Fetch => fetch_refspecs.push(text),
+                Direction::Push => push_refspecs.push(text),
+            }
+        }
+
+        remotes.push(RemoteInfo {
+            name: name.to_string(),
+            fetch_url,
+            push_url,
+            fetch_refspecs,
+            push_refspecs,
+        });
+    }
+
+    Ok(remotes)
+}
+
+// The remote to fetch from, the current branch's upstream remote wins, then origin, then the only one there is
+pub fn get_fetch_remote(repo: &Repository) -> AppResult<String> {
+    if let Some(remote) = get_branch_config(repo, "remote") {
+        return Ok(remote);
+    }
+    get_fallback_remote(repo)
+}
+
+// The remote to push to, following branch.<name>.pushRemote and remote.pushDefault like git does
+pub fn get_push_remote(repo: &Repository) -> AppResult<String> {
+    if let Some(remote) = get_branch_config(repo, "pushRemote") {
+        return Ok(remote);
+    }
+    if let Ok(remote) = repo.config().and_then(|config| config.get_string("remote.pushDefault")) {
+        return Ok(remote);
+    }
+    get_fetch_remote(repo)
+}
+
+fn get_branch_config(repo: &Repository, key: &str) -> Option<String> {
+    let head = repo.head().ok()?;
+    if !head.is_branch() {
+        return None;
+    }
+    let branch = head.shorthand()?.to_string();
+    repo.config().ok()?.get_string(&format!("branch.{}.{}", branch, key)).ok()
+}
+
+fn get_fallback_remote(repo: &Repository) -> AppResult<String> {
+    let remotes = repo.remotes()?;
+    let names: Vec<&str> = remotes.iter().flatten().collect();
+    if names.contains(&"origin") {
+        return Ok("origin".to_string());
+    }
+    match names.as_slice() {
+        [] => Err(Error::from_str("No remotes configured").into()),
+        [name] => Ok(name.to_string()),
+        _ => Err(Error::from_str("Several remotes and no upstream, pick one from the remotes pane").into()),
+    }
+}
+
+// What a fetch changed among the remote tracking branches and tags
+#[derive(Default)]
+pub struct FetchSummary {
+    pub new: Vec<String>,
+    pub updated: Vec<String>,
+    pub deleted: Vec<String>,
+}
+
+impl FetchSummary {
+    pub fn is_empty(&self) -> bool {
+        self.new.is_empty() && self.updated.is_empty() && self.deleted.is_empty()
+    }
+
+    // A short line like "2 new (origin/a, v1.0), 1 updated (origin/main)"
+    pub fn describe(&self) -> String {
+        if self.is_empty() {
+            return "nothing new".to_string();
+        }
+        [("new", &self.new), ("updated", &self.updated), ("pruned", &self.deleted)]
+            .iter()
+            .filter(|(_, names)| !names.is_empty())
+            .map(|(kind, names)| {
+                let mut listed = names.iter().take(3).cloned().collect::<Vec<_>>().join(", ");
+                if names.len() > 3 {
+                    listed.push_str(", …");
+                }
+                format!("{} {} ({})", names.len(), kind, listed)
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+// Remote tracking branches and tags with where they point
+pub fn get_fetched_refs(repo: &Repository) -> AppResult<HashMap<String, Oid>> {
+    let mut refs = HashMap::new();
+    for glob in ["refs/remotes/*", "refs/tags/*"] {
+        for reference in repo.references_glob(glob)?.flatten() {
+
+            // Symbolic refs like origin/HEAD have no target of their own and are left out
+            if let (Some(name), Some(oid)) = (reference.name(), reference.target()) {
+                refs.insert(name.to_string(), oid);
+            }
+        }
+    }
+    Ok(refs)
+}
+
+// Compares the refs from before and after a fetch
+pub fn get_fetch_summary(before: &HashMap<String, Oid>, after: &HashMap<String, Oid>) -> FetchSummary {
+    let short = |name: &String| name.trim_start_matches("refs/remotes/").trim_start_matches("refs/tags/").to_string();
+    let mut summary = FetchSummary::default();
+
+    for (name, oid) in after {
+        match before.get(name) {
+            None => summary.new.push(short(name)),
+            Some(old) if old != oid => summary.updated.push(short(name)),
+            _ => {}
+        }
+    }
+    for name in before.keys().filter(|name| !after.contains_key(*name)) {
+        summary.deleted.push(short(name));
+    }
+
+    summary.new.sort();
+    summary.updated.sort();
+    summary.deleted.sort();
+    summary
+}
@@ -9,4 +9,9 @@ pub const SYM_MERGE_RIGHT_FROM: &str = "╭";
 pub const SYM_BRANCH_UP: &str = "╯";
 pub const SYM_BRANCH_DOWN: &str = "╮";
 pub const SYM_MERGE: &str = "•";
+pub const SYM_MERGE_FAN: &str = "┬";
+pub const SYM_MERGE_CROSS: &str = "┼";
 pub const SYM_UNCOMMITED: &str = "◌";
+pub const SYM_STASH: &str = "◍";
+pub const SYM_TAG: &str = "⚑";
+pub const SYM_REMOTE: &str = "◆";
@@ -149,6 +149,11 @@ pub fn editor_state_to_string(state: &EditorState) -> String {
         .collect::<String>()
 }
 
+// Same as above, but keeps the line breaks, for multi-line messages
+pub fn editor_state_to_message(state: &EditorState) -> String {
+    state.lines.flatten(&Some('\n')).into_iter().collect()
+}
+
 // Attempt to decode raw byte data into a string, handling UTF-8 and UTF-16 (LE/BE)
 pub fn decode(bytes: &[u8]) -> String {
     if bytes.starts_with(&[0xFF, 0xFE]) {
@@ -196,6 +201,7 @@ pub fn sanitize(string: String) -> String {
 // A helper to convert KeyCode to string
 pub fn keycode_to_string(code: &KeyCode) -> String {
     match code {
+        KeyCode::Char(' ') => "Space".into(),
         KeyCode::Char(c) => c.to_string(),
         KeyCode::Enter => "Enter".into(),
         KeyCode::Tab => "Tab".into(),
@@ -248,3 +254,14 @@ pub fn fill_width(left: &str, right: &str, width: usize) -> String {
 
     format!(" {}{}{} ", left, " ".repeat(spaces - 2), right)
 }
+
+// Split the arguments after the repo path into a revision range and, after "--", a path to follow
+pub fn split_filter_args(args: &[String]) -> (Option<String>, Option<String>) {
+    let (revisions, path) = match args.iter().position(|arg| arg == "--") {
+        Some(idx) => (&args[..idx], &args[idx + 1..]),
+        None => (args, &[][..]),
+    };
+    let range = Some(revisions.join(" ")).filter(|range| !range.is_empty());
+    let pathspec = Some(path.join(" ")).filter(|pathspec| !pathspec.is_empty());
+    (range, pathspec)
+}
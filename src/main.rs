@@ -1,5 +1,11 @@
 #[rustfmt::skip]
-use std::io;
+use std::{
+    env,
+    io,
+    process
+};
+#[rustfmt::skip]
+use git2::Repository;
 #[rustfmt::skip]
 mod app {
     #[allow(clippy::module_inception)]
@@ -9,9 +15,15 @@ mod app {
     pub mod app_layout;
     pub mod app_draw_title;
     pub mod app_draw_branches;
+    pub mod app_draw_stashes;
+    pub mod app_draw_tags;
+    pub mod app_draw_remotes;
     pub mod app_draw_graph;
     pub mod app_draw_editor;
     pub mod app_draw_viewer;
+    pub mod app_draw_rebase;
+    pub mod app_draw_conflicts;
+    pub mod app_draw_log;
     pub mod app_draw_settings;
     pub mod app_draw_splash;
     pub mod app_draw_inspector;
@@ -21,7 +33,18 @@ mod app {
     pub mod app_draw_modal_solo;
     pub mod app_draw_modal_commit;
     pub mod app_draw_modal_create_branch;
+    pub mod app_draw_modal_rename_branch;
+    pub mod app_draw_modal_create_tag;
+    pub mod app_draw_modal_remote;
+    pub mod app_draw_modal_range;
+    pub mod app_draw_modal_path;
     pub mod app_draw_modal_delete_branch;
+    pub mod app_draw_modal_stash;
+    pub mod app_draw_modal_merge;
+    pub mod app_draw_modal_revert;
+    pub mod app_draw_modal_confirm;
+    pub mod app_draw_modal_credentials;
+    pub mod app_draw_modal_error;
 }
 mod core {
     pub mod buffer;
@@ -33,16 +56,34 @@ mod core {
     pub mod oids;
     pub mod branches;
     pub mod tags;
+    pub mod stashes;
+    pub mod rebase;
+    pub mod conflicts;
+    pub mod log;
+    pub mod search;
+    pub mod error;
 }
 pub mod git {
     pub mod actions {
         pub mod commits;
+        pub mod stashes;
+        pub mod rebase;
+        pub mod merges;
+        pub mod pull;
+        pub mod hunks;
+        pub mod conflicts;
+        pub mod tags;
+        pub mod remotes;
     }
     pub mod queries {
         pub mod commits;
         pub mod diffs;
         pub mod helpers;
+        pub mod conflicts;
+        pub mod remotes;
     }
+    pub mod credentials;
+    pub mod network;
 }
 pub mod helpers {
     pub mod colors;
@@ -53,9 +94,32 @@ pub mod helpers {
     pub mod time;
 }
 
-use crate::app::app::App;
+#[rustfmt::skip]
+use crate::{
+    app::app::App,
+    core::batcher::push_revisions,
+    helpers::text::split_filter_args
+};
 
 fn main() -> io::Result<()> {
+    // Say what is wrong before taking over the terminal
+    let path = env::args().nth(1).unwrap_or(".".to_string());
+    let repo = match Repository::open(&path) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("guitar: cannot open {}: {}", path, e.message());
+            process::exit(1);
+        }
+    };
+
+    // Anything after the path is a revision range, e.g. guitar . main..feature -- src/
+    let args: Vec<String> = env::args().skip(2).collect();
+    if let (Some(range), _) = split_filter_args(&args) && let Err(e) = repo.revwalk().and_then(|mut revwalk| push_revisions(&repo, &mut revwalk, &range)) {
+        eprintln!("guitar: cannot resolve {}: {}", range, e.message());
+        process::exit(1);
+    }
+    drop(repo);
+
     let mut terminal = ratatui::init();
     let app_result = App::default().run(&mut terminal);
     ratatui::restore();